@@ -6,6 +6,7 @@ pub struct Context {
     pub function_table: FunctionTable,
     pub module_table: ModuleTable,
     pub type_table: TypeTable,
+    pub span_table: SpanTable,
 }
 
 impl Context {
@@ -14,6 +15,7 @@ impl Context {
             function_table: FunctionTable::new(),
             module_table: ModuleTable::new(),
             type_table: TypeTable::new(),
+            span_table: SpanTable::new(),
         }
     }
 }
\ No newline at end of file
@@ -5,9 +5,11 @@
 //! used, and are not used after they exit scope. At this stage, types have
 //! not necessarily been resolved.
 
-use super::identifier::{Identifier, Identify, Symbol, Symbolise};
+use super::identifier::{Identifier, Identify, Name, Symbol, Symbolise};
+use super::lexer::Span;
 
 use std::collections::HashMap;
+use std::fmt;
 
 pub mod context;
 
@@ -35,14 +37,31 @@ impl From<Box<Expr>> for Air {
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub struct AliasType {
-    inner: Type,
+    pub inner: Type,
 }
 
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub struct AssignExpr {
-    lhs: Expr,
-    rhs: Expr,
+    pub identifier: Identifier,
+    pub lhs: Expr,
+    pub rhs: Expr,
+}
+
+impl AssignExpr {
+    pub fn new(identifier: Identifier, lhs: Expr, rhs: Expr) -> AssignExpr {
+        AssignExpr {
+            identifier: identifier,
+            lhs: lhs,
+            rhs: rhs,
+        }
+    }
+}
+
+impl Identify for AssignExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
 }
 
 ///
@@ -84,6 +103,24 @@ impl Identify for BlockExpr {
 ///
 pub type BlockExprs = Vec<BlockExpr>;
 
+/// A `BreakExpr` ends the innermost loop that encloses it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BreakExpr {
+    identifier: Identifier,
+}
+
+impl BreakExpr {
+    pub fn new(identifier: Identifier) -> BreakExpr {
+        BreakExpr { identifier: identifier }
+    }
+}
+
+impl Identify for BreakExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub struct CallExpr {
@@ -108,34 +145,281 @@ impl Identify for CallExpr {
     }
 }
 
+/// A `ChannelExpr` creates an empty channel, e.g. `(..)`. Its type is the
+/// type of the values that are sent through the channel.
 ///
+/// A channel without a capacity is unbounded, e.g. `(..)`, and a channel
+/// with a capacity is bounded, e.g. `(..16)`. A capacity of zero creates a
+/// rendezvous channel, e.g. `(..0)`.
 #[derive(Clone, Debug, PartialEq)]
-pub struct DerefExpr {
+pub struct ChannelExpr {
+    pub identifier: Identifier,
+    pub capacity: Option<Expr>,
+    pub ty: Type,
+}
+
+impl ChannelExpr {
+    pub fn new(identifier: Identifier, capacity: Option<Expr>) -> ChannelExpr {
+        ChannelExpr {
+            identifier: identifier,
+            capacity: capacity,
+            ty: Type::infer(),
+        }
+    }
+}
+
+impl Identify for ChannelExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `ChannelOp` is an operation on a channel. Sending on a closed channel
+/// is a runtime error, and so is receiving from a channel that is closed and
+/// empty.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChannelOp {
+    Close(Expr),
+    Recv(Expr),
+    Send(Expr, Expr),
+}
+
+/// A `ChannelOpExpr` applies a `ChannelOp` to a channel, e.g. `xs <- x` or
+/// `<- xs`. Its type is the type of the values in the channel.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChannelOpExpr {
+    pub identifier: Identifier,
+    pub op: ChannelOp,
+    pub ty: Type,
+}
+
+impl ChannelOpExpr {
+    pub fn new(identifier: Identifier, op: ChannelOp) -> ChannelOpExpr {
+        ChannelOpExpr {
+            identifier: identifier,
+            op: op,
+            ty: Type::infer(),
+        }
+    }
+}
+
+impl Identify for ChannelOpExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChannelType {
+    pub element: Type,
+}
+
+impl ChannelType {
+    pub fn new(element: Type) -> ChannelType {
+        ChannelType { element: element }
+    }
+}
+
+/// A `CoalesceExpr` evaluates to the value inside of an optional, or to its
+/// default when the optional is `nil`, e.g. `x ?? 0`. Its type is the type
+/// of the value inside of the optional.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoalesceExpr {
+    pub identifier: Identifier,
+    pub lhs: Expr,
+    pub rhs: Expr,
+    pub ty: Type,
+}
+
+impl CoalesceExpr {
+    pub fn new(identifier: Identifier, lhs: Expr, rhs: Expr) -> CoalesceExpr {
+        CoalesceExpr {
+            identifier: identifier,
+            lhs: lhs,
+            rhs: rhs,
+            ty: Type::infer(),
+        }
+    }
+}
+
+impl Identify for CoalesceExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `ContinueExpr` skips to the next iteration of the innermost loop that
+/// encloses it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContinueExpr {
     identifier: Identifier,
-    inner: Expr,
+}
+
+impl ContinueExpr {
+    pub fn new(identifier: Identifier) -> ContinueExpr {
+        ContinueExpr { identifier: identifier }
+    }
+}
+
+impl Identify for ContinueExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
 }
 
 ///
 #[derive(Clone, Debug, PartialEq)]
+pub struct DerefExpr {
+    pub identifier: Identifier,
+    pub inner: Expr,
+}
+
+impl DerefExpr {
+    pub fn new(identifier: Identifier, inner: Expr) -> DerefExpr {
+        DerefExpr {
+            identifier: identifier,
+            inner: inner,
+        }
+    }
+}
+
+impl Identify for DerefExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `DoExpr` evaluates every expression of its `do` expression concurrently,
+/// and then every expression of its `then` expression concurrently, e.g.
+/// `do { a; b } then { c; d }`. All of the `do` expression ends before any
+/// of the `then` expression begins.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DoExpr {
+    pub identifier: Identifier,
+    pub do_expr: Expr,
+    pub then_expr: Expr,
+}
+
+impl DoExpr {
+    pub fn new(identifier: Identifier, do_expr: Expr, then_expr: Expr) -> DoExpr {
+        DoExpr {
+            identifier: identifier,
+            do_expr: do_expr,
+            then_expr: then_expr,
+        }
+    }
+}
+
+impl Identify for DoExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// An `EnumExpr` builds a value of an `EnumType` from one of its variants,
+/// and the elements of that variant.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumExpr {
+    pub identifier: Identifier,
+    pub variant: Variable,
+    pub elements: Vec<(Variable, Expr)>,
+    pub ty: Type,
+}
+
+impl EnumExpr {
+    pub fn new(identifier: Identifier, variant: Variable, elements: Vec<(Variable, Expr)>, ty: Type) -> EnumExpr {
+        EnumExpr {
+            identifier: identifier,
+            variant: variant,
+            elements: elements,
+            ty: ty,
+        }
+    }
+}
+
+impl Identify for EnumExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// The variants of an `EnumType` are the elements of a `StructType`. Each
+/// variant is a `Variable` whose type is a `StructType` that holds the
+/// elements of the variant. The elements of a tuple variant are named by
+/// their position.
+#[derive(Clone, Debug, PartialEq)]
 pub struct EnumType {
-    symbol: Symbol,
-    params: Types,
-    variants: Box<StructType>,
+    pub symbol: Symbol,
+    pub params: Types,
+    pub variants: Box<StructType>,
 }
 
-///
+impl EnumType {
+    pub fn new(symbol: Symbol, params: Types, variants: Variables) -> EnumType {
+        EnumType {
+            symbol: symbol.clone(),
+            params: params.clone(),
+            variants: Box::new(StructType::new(symbol, params, variants)),
+        }
+    }
+
+    pub fn variant(&self, variant: &Variable) -> Option<&Variable> {
+        self.variants.elements.iter().find(|other| other.symbol == variant.symbol)
+    }
+
+    /// Get the elements of a variant of an `EnumType`.
+    pub fn variant_elements(variant: &Variable) -> Variables {
+        match variant.ty {
+            Type::Struct(ref ty) => ty.elements.clone(),
+            _ => Variables::new(),
+        }
+    }
+}
+
+/// A `ForExpr` iterates over a list, or over the values received from a
+/// channel until it is closed. Its type is the type of the iterator. A
+/// concurrent `ForExpr` is a `forall`, which runs every iteration in a
+/// process of its own and joins them all at the end of the loop.
 #[derive(Clone, Debug, PartialEq)]
 pub struct ForExpr {
-    identifier: Identifier,
-    formals: Variables,
-    iterator: Expr,
-    iteration: Box<BlockExpr>,
+    pub identifier: Identifier,
+    pub is_concurrent: bool,
+    pub formals: Variables,
+    pub iterator: Expr,
+    pub iteration: Box<BlockExpr>,
+    pub ty: Type,
+}
+
+impl ForExpr {
+    pub fn new(identifier: Identifier,
+               is_concurrent: bool,
+               formals: Variables,
+               iterator: Expr,
+               iteration: BlockExpr)
+               -> ForExpr {
+        ForExpr {
+            identifier: identifier,
+            is_concurrent: is_concurrent,
+            formals: formals,
+            iterator: iterator,
+            iteration: Box::new(iteration),
+            ty: Type::infer(),
+        }
+    }
+}
+
+impl Identify for ForExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
 }
 
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub struct Function {
     pub symbol: Symbol,
+    pub type_params: Types,
     pub formals: Variables,
     pub ret: Type,
     pub body: Option<Expr>,
@@ -143,13 +427,35 @@ pub struct Function {
 
 impl Function {
     pub fn new(symbol: Symbol, formals: Variables, ret: Type, body: Option<Expr>) -> Function {
+        Function::new_generic(symbol, Types::new(), formals, ret, body)
+    }
+
+    pub fn new_generic(symbol: Symbol,
+                       type_params: Types,
+                       formals: Variables,
+                       ret: Type,
+                       body: Option<Expr>)
+                       -> Function {
         Function {
             symbol: symbol,
+            type_params: type_params,
             formals: formals,
             ret: ret,
             body: body,
         }
     }
+
+    pub fn is_generic(&self) -> bool {
+        !self.type_params.is_empty()
+    }
+
+    pub fn lambda_type(&self) -> LambdaType {
+        LambdaType::new(self.formals
+                            .iter()
+                            .map(|formal| formal.ty.clone())
+                            .collect(),
+                        self.ret.clone())
+    }
 }
 
 impl Identify for Function {
@@ -173,130 +479,779 @@ pub type FunctionTable = HashMap<Identifier, Function>;
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub struct IfExpr {
-    identifier: Identifier,
-    condition: Expr,
-    then_block: Box<BlockExpr>,
-    else_block: Box<BlockExpr>,
+    pub identifier: Identifier,
+    pub condition: Expr,
+    pub then_block: Box<BlockExpr>,
+    pub else_block: Box<BlockExpr>,
 }
 
-///
+impl IfExpr {
+    pub fn new(identifier: Identifier,
+               condition: Expr,
+               then_block: BlockExpr,
+               else_block: BlockExpr)
+               -> IfExpr {
+        IfExpr {
+            identifier: identifier,
+            condition: condition,
+            then_block: Box::new(then_block),
+            else_block: Box::new(else_block),
+        }
+    }
+}
+
+impl Identify for IfExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A type that has not been annotated, and must be inferred from usage.
+/// Every `InferType` is identified, so that all occurrences of the same
+/// unannotated variable are inferred to the same `Type`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InferType {
+    pub identifier: Identifier,
+    pub kind: InferKind,
+}
+
+impl InferType {
+    pub fn new(kind: InferKind) -> InferType {
+        InferType {
+            identifier: Identifier::id(),
+            kind: kind,
+        }
+    }
+}
+
+impl Identify for InferType {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// The kind of an `InferType` restricts the types that it can be inferred to.
+/// Integer literals are inferred to an integer type, float literals are
+/// inferred to a float type, and everything else can be inferred to any type.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InferKind {
+    Any,
+    Float,
+    Int,
+}
+
+/// An `ItemExpr` that refers to a generic `Function` carries the type
+/// arguments with which the `Function` is instantiated. The type arguments
+/// are inferred by the type checker.
 #[derive(Clone, Debug, PartialEq)]
 pub struct ItemExpr {
-    identifier: Identifier,
-    item: Item,
+    pub identifier: Identifier,
+    pub item: Item,
+    pub type_args: Types,
 }
 
-///
+impl ItemExpr {
+    pub fn new(identifier: Identifier, item: Item) -> ItemExpr {
+        ItemExpr {
+            identifier: identifier,
+            item: item,
+            type_args: Types::new(),
+        }
+    }
+}
+
+impl Identify for ItemExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A type parameter of a generic `Function`, `StructType` or `EnumType`.
 #[derive(Clone, Debug, PartialEq)]
 pub struct GenericType {
-    identifier: Identifier,
-    params: Types,
+    pub identifier: Identifier,
+    pub params: Types,
+}
+
+impl GenericType {
+    pub fn new(identifier: Identifier) -> GenericType {
+        GenericType {
+            identifier: identifier,
+            params: Types::new(),
+        }
+    }
+}
+
+impl Identify for GenericType {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `LambdaExpr` is an anonymous function. Its return type is inferred from
+/// its body, and the variables that it uses from the enclosing scope are
+/// captured when it is normalised.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LambdaExpr {
+    pub identifier: Identifier,
+    pub formals: Variables,
+    pub ret: Type,
+    pub body: Expr,
+}
+
+impl LambdaExpr {
+    pub fn new(identifier: Identifier, formals: Variables, body: Expr) -> LambdaExpr {
+        LambdaExpr {
+            identifier: identifier,
+            formals: formals,
+            ret: Type::infer(),
+            body: body,
+        }
+    }
+
+    pub fn lambda_type(&self) -> LambdaType {
+        LambdaType::new(self.formals
+                            .iter()
+                            .map(|formal| formal.ty.clone())
+                            .collect(),
+                        self.ret.clone())
+    }
+}
+
+impl Identify for LambdaExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
 }
 
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub struct LambdaType {
-    formals: Types,
-    ret: Type,
+    pub formals: Types,
+    pub ret: Type,
+}
+
+impl LambdaType {
+    pub fn new(formals: Types, ret: Type) -> LambdaType {
+        LambdaType {
+            formals: formals,
+            ret: ret,
+        }
+    }
+}
+
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct LetExpr {
+    pub identifier: Identifier,
+    pub variable: Variable,
+    pub definition: Expr,
+}
+
+impl LetExpr {
+    pub fn new(identifier: Identifier, variable: Variable, definition: Expr) -> LetExpr {
+        LetExpr {
+            identifier: identifier,
+            variable: variable,
+            definition: definition,
+        }
+    }
+}
+
+impl Identify for LetExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `LetTupleExpr` binds each element of a tuple to a variable, in order,
+/// e.g. `let (a, b) := f()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LetTupleExpr {
+    pub identifier: Identifier,
+    pub variables: Variables,
+    pub definition: Expr,
+}
+
+impl LetTupleExpr {
+    pub fn new(identifier: Identifier, variables: Variables, definition: Expr) -> LetTupleExpr {
+        LetTupleExpr {
+            identifier: identifier,
+            variables: variables,
+            definition: definition,
+        }
+    }
+}
+
+impl Identify for LetTupleExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `ListExpr` builds a list from its elements, e.g. `[1, 2, 3]`. Its type
+/// is the type of the elements.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListExpr {
+    pub identifier: Identifier,
+    pub elements: Exprs,
+    pub ty: Type,
+}
+
+impl ListExpr {
+    pub fn new(identifier: Identifier, elements: Exprs) -> ListExpr {
+        ListExpr {
+            identifier: identifier,
+            elements: elements,
+            ty: Type::infer(),
+        }
+    }
+}
+
+impl Identify for ListExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `ListOp` is an operation on a list. Indexing and slicing are bounds
+/// checked at runtime, and popping from an empty list gives `nil`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ListOp {
+    Index(Expr, Expr),
+    Len(Expr),
+    Pop(Expr),
+    Push(Expr, Expr),
+    Slice(Expr, Expr, Expr),
+}
+
+/// A `ListOpExpr` applies a `ListOp` to a list. Its type is the type of the
+/// elements of the list.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListOpExpr {
+    pub identifier: Identifier,
+    pub op: ListOp,
+    pub ty: Type,
+}
+
+impl ListOpExpr {
+    pub fn new(identifier: Identifier, op: ListOp) -> ListOpExpr {
+        ListOpExpr {
+            identifier: identifier,
+            op: op,
+            ty: Type::infer(),
+        }
+    }
+}
+
+impl Identify for ListOpExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListType {
+    pub element: Type,
+}
+
+impl ListType {
+    pub fn new(element: Type) -> ListType {
+        ListType { element: element }
+    }
+}
+
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct LiteralExpr {
+    pub identifier: Identifier,
+    pub literal: Literal,
+}
+
+impl LiteralExpr {
+    pub fn new(identifier: Identifier, literal: Literal) -> LiteralExpr {
+        LiteralExpr {
+            identifier: identifier,
+            literal: literal,
+        }
+    }
+}
+
+impl Identify for LiteralExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `MatchExpr` evaluates the body of the arm that matches the variant of
+/// its subject. An arm without a variant matches every variant.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchExpr {
+    pub identifier: Identifier,
+    pub subject: Expr,
+    pub arms: MatchArms,
+}
+
+impl MatchExpr {
+    pub fn new(identifier: Identifier, subject: Expr, arms: MatchArms) -> MatchExpr {
+        MatchExpr {
+            identifier: identifier,
+            subject: subject,
+            arms: arms,
+        }
+    }
+}
+
+impl Identify for MatchExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// The bindings of a `MatchArm` are bound to the elements of its variant,
+/// in the order that they are declared.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchArm {
+    pub identifier: Identifier,
+    pub variant: Option<Variable>,
+    pub bindings: Variables,
+    pub body: BlockExpr,
+}
+
+impl MatchArm {
+    pub fn new(identifier: Identifier,
+               variant: Option<Variable>,
+               bindings: Variables,
+               body: BlockExpr)
+               -> MatchArm {
+        MatchArm {
+            identifier: identifier,
+            variant: variant,
+            bindings: bindings,
+            body: body,
+        }
+    }
+}
+
+impl Identify for MatchArm {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+///
+pub type MatchArms = Vec<MatchArm>;
+
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Module {
+    pub symbol: Symbol,
+    pub function_table: FunctionTable,
+    pub module_table: ModuleTable,
+    pub type_table: TypeTable,
+}
+
+impl Module {
+    pub fn new<S, F, M, T>(symbol: S, function_table: F, module_table: M, type_table: T) -> Module
+        where S: Into<Symbol>,
+              F: Into<FunctionTable>,
+              M: Into<ModuleTable>,
+              T: Into<TypeTable>
+    {
+        Module {
+            symbol: symbol.into(),
+            function_table: function_table.into(),
+            module_table: module_table.into(),
+            type_table: type_table.into(),
+        }
+    }
+}
+
+impl Identify for Module {
+    fn identify(&self) -> Identifier {
+        self.symbol.identify()
+    }
+}
+
+impl Symbolise for Module {
+    fn symbolise(&self) -> Symbol {
+        self.symbol.clone()
+    }
+}
+
+///
+pub type Modules = Vec<Module>;
+
+///
+pub type ModuleTable = HashMap<Identifier, Module>;
+
+/// An `OptionalExpr` builds a value of an `OptionalType`. It is `nil` when
+/// it has no value. The type checker wraps a value in an `OptionalExpr`
+/// wherever a `T` is used as a `T?`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionalExpr {
+    pub identifier: Identifier,
+    pub value: Option<Expr>,
+    pub ty: Type,
+}
+
+impl OptionalExpr {
+    pub fn new(identifier: Identifier, value: Expr) -> OptionalExpr {
+        OptionalExpr {
+            identifier: identifier,
+            value: Some(value),
+            ty: Type::infer(),
+        }
+    }
+
+    pub fn nil(identifier: Identifier) -> OptionalExpr {
+        OptionalExpr {
+            identifier: identifier,
+            value: None,
+            ty: Type::infer(),
+        }
+    }
+}
+
+impl Identify for OptionalExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `ProcessExpr` evaluates its body concurrently with the rest of the
+/// enclosing block. Every process is joined at the end of the block that
+/// encloses it, so no process outlives the scope that started it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProcessExpr {
+    pub identifier: Identifier,
+    pub body: BlockExpr,
+}
+
+impl ProcessExpr {
+    pub fn new(identifier: Identifier, body: BlockExpr) -> ProcessExpr {
+        ProcessExpr {
+            identifier: identifier,
+            body: body,
+        }
+    }
+}
+
+impl Identify for ProcessExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionalType {
+    pub inner: Type,
+}
+
+impl OptionalType {
+    pub fn new(inner: Type) -> OptionalType {
+        OptionalType { inner: inner }
+    }
+}
+
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct PtrType {
+    pub inner: Type,
+}
+
+/// A `RefType` that is mutable, e.g. `mut ref i64`, is mutually exclusive:
+/// no other reference to its value can exist alongside it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RefType {
+    pub inner: Type,
+    pub is_mut: bool,
+}
+
+impl RefType {
+    pub fn new(inner: Type) -> RefType {
+        RefType {
+            inner: inner,
+            is_mut: false,
+        }
+    }
+
+    pub fn new_mut(inner: Type) -> RefType {
+        RefType {
+            inner: inner,
+            is_mut: true,
+        }
+    }
+}
+
+/// A `RangeExpr` is the list of integers from `start` up to, but not
+/// including, `end`, e.g. `1 .. n`. Its type is the type of the integers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeExpr {
+    pub identifier: Identifier,
+    pub start: Expr,
+    pub end: Expr,
+    pub ty: Type,
+}
+
+impl RangeExpr {
+    pub fn new(identifier: Identifier, start: Expr, end: Expr) -> RangeExpr {
+        RangeExpr {
+            identifier: identifier,
+            start: start,
+            end: end,
+            ty: Type::infer(),
+        }
+    }
+}
+
+impl Identify for RangeExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `RefExpr` takes a reference to its inner expression, e.g. `ref x`, or a
+/// mutable reference, e.g. `mut ref x`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RefExpr {
+    pub identifier: Identifier,
+    pub inner: Expr,
+    pub is_mut: bool,
+}
+
+impl RefExpr {
+    pub fn new(identifier: Identifier, inner: Expr) -> RefExpr {
+        RefExpr {
+            identifier: identifier,
+            inner: inner,
+            is_mut: false,
+        }
+    }
+
+    pub fn new_mut(identifier: Identifier, inner: Expr) -> RefExpr {
+        RefExpr {
+            identifier: identifier,
+            inner: inner,
+            is_mut: true,
+        }
+    }
+}
+
+impl Identify for RefExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `SelectExpr` waits until one of its guards can send to, or receive from,
+/// its channel, and then evaluates the body of that guard, e.g.
+/// `select { when <- xs as x { .. }, when ys <- y { .. } }`. When several
+/// guards are ready, one of them is chosen at random. A select with an `else`
+/// body does not wait, and evaluates the `else` body when no guard is ready.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectExpr {
+    pub identifier: Identifier,
+    pub guards: SelectGuards,
+    pub else_body: Option<BlockExpr>,
+}
+
+impl SelectExpr {
+    pub fn new(identifier: Identifier, guards: SelectGuards, else_body: Option<BlockExpr>) -> SelectExpr {
+        SelectExpr {
+            identifier: identifier,
+            guards: guards,
+            else_body: else_body,
+        }
+    }
+}
+
+impl Identify for SelectExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `SelectOp` is the channel operation of a `SelectGuard`. A received value
+/// is bound to the variable of the guard, if it has one.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SelectOp {
+    Recv(Expr, Option<Variable>),
+    Send(Expr, Expr),
+}
+
+/// The type of a `SelectGuard` is the type of the values in its channel.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectGuard {
+    pub identifier: Identifier,
+    pub op: SelectOp,
+    pub body: BlockExpr,
+    pub ty: Type,
+}
+
+impl SelectGuard {
+    pub fn new(identifier: Identifier, op: SelectOp, body: BlockExpr) -> SelectGuard {
+        SelectGuard {
+            identifier: identifier,
+            op: op,
+            body: body,
+            ty: Type::infer(),
+        }
+    }
+}
+
+impl Identify for SelectGuard {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+///
+pub type SelectGuards = Vec<SelectGuard>;
+
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct StructExpr {
+    pub identifier: Identifier,
+    pub elements: Vec<(Variable, Expr)>,
+    pub ty: Type,
 }
 
-///
-#[derive(Clone, Debug, PartialEq)]
-pub struct LetExpr {
-    identifier: Identifier,
-    variable: Variable,
-    definition: Expr,
+impl StructExpr {
+    pub fn new(identifier: Identifier, elements: Vec<(Variable, Expr)>, ty: Type) -> StructExpr {
+        StructExpr {
+            identifier: identifier,
+            elements: elements,
+            ty: ty,
+        }
+    }
 }
 
-///
-#[derive(Clone, Debug, PartialEq)]
-pub struct LiteralExpr {
-    identifier: Identifier,
-    literal: Literal,
+impl Identify for StructExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
 }
 
 ///
 #[derive(Clone, Debug, PartialEq)]
-pub struct Module {
+pub struct StructType {
     pub symbol: Symbol,
-    pub function_table: FunctionTable,
-    pub module_table: ModuleTable,
-    pub type_table: TypeTable,
+    pub params: Types,
+    pub elements: Variables,
 }
 
-impl Module {
-    pub fn new<S, F, M, T>(symbol: S, function_table: F, module_table: M, type_table: T) -> Module
-        where S: Into<Symbol>,
-              F: Into<FunctionTable>,
-              M: Into<ModuleTable>,
-              T: Into<TypeTable>
-    {
-        Module {
-            symbol: symbol.into(),
-            function_table: function_table.into(),
-            module_table: module_table.into(),
-            type_table: type_table.into(),
+impl StructType {
+    pub fn new(symbol: Symbol, params: Types, elements: Variables) -> StructType {
+        StructType {
+            symbol: symbol,
+            params: params,
+            elements: elements,
         }
     }
-}
 
-impl Identify for Module {
-    fn identify(&self) -> Identifier {
-        self.symbol.identify()
+    /// Instantiate a generic `StructType` by substituting its type
+    /// parameters with concrete types.
+    ///
+    /// # Arguments
+    /// * `type_args` - The types that substitute the type parameters, in the
+    ///   order that the parameters were declared.
+    ///
+    /// # Return
+    /// A `StructType` with the type arguments as its params.
+    pub fn instantiate(&self, type_args: Types) -> StructType {
+        let substitution = self.params
+            .iter()
+            .zip(type_args.iter())
+            .filter_map(|(param, type_arg)| match *param {
+                Type::Generic(ref param) => Some((param.identify(), type_arg.clone())),
+                _ => None,
+            })
+            .collect::<Substitution>();
+        self.substitute(&substitution)
     }
-}
 
-impl Symbolise for Module {
-    fn symbolise(&self) -> Symbol {
-        self.symbol.clone()
+    pub fn substitute(&self, substitution: &Substitution) -> StructType {
+        StructType {
+            symbol: self.symbol.clone(),
+            params: self.params.iter().map(|param| param.substitute(substitution)).collect(),
+            elements: self.elements
+                .iter()
+                .map(|element| Variable::new(element.symbol.clone(), element.ty.substitute(substitution)))
+                .collect(),
+        }
     }
 }
 
 ///
-pub type Modules = Vec<Module>;
+#[derive(Clone, Debug, PartialEq)]
+pub struct TupleExpr {
+    pub identifier: Identifier,
+    pub elements: Exprs,
+}
 
-///
-pub type ModuleTable = HashMap<Identifier, Module>;
+impl TupleExpr {
+    pub fn new(identifier: Identifier, elements: Exprs) -> TupleExpr {
+        TupleExpr {
+            identifier: identifier,
+            elements: elements,
+        }
+    }
+}
 
-///
-#[derive(Clone, Debug, PartialEq)]
-pub struct PtrType {
-    inner: Type,
+impl Identify for TupleExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
 }
 
 ///
 #[derive(Clone, Debug, PartialEq)]
-pub struct RefType {
-    inner: Type,
+pub struct TupleType {
+    pub elements: Types,
 }
 
-///
-#[derive(Clone, Debug, PartialEq)]
-pub struct RefExpr {
-    identifier: Identifier,
-    inner: Expr,
+impl TupleType {
+    pub fn new(elements: Types) -> TupleType {
+        TupleType { elements: elements }
+    }
 }
 
-///
+/// An `UnwrapExpr` evaluates to the value inside of an optional, e.g. `x!`.
+/// Unwrapping `nil` is a runtime error. Its type is the type of the value
+/// inside of the optional.
 #[derive(Clone, Debug, PartialEq)]
-pub struct StructExpr {
-    elements: Vec<(Variable, Expr)>,
-    ty: Type,
+pub struct UnwrapExpr {
+    pub identifier: Identifier,
+    pub inner: Expr,
+    pub ty: Type,
 }
 
-///
-#[derive(Clone, Debug, PartialEq)]
-pub struct StructType {
-    symbol: Symbol,
-    params: Types,
-    elements: Variables,
+impl UnwrapExpr {
+    pub fn new(identifier: Identifier, inner: Expr) -> UnwrapExpr {
+        UnwrapExpr {
+            identifier: identifier,
+            inner: inner,
+            ty: Type::infer(),
+        }
+    }
 }
 
-///
+impl Identify for UnwrapExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `Variable` that is mutable, e.g. `let mut x`, can be assigned to, and
+/// can be referenced by a `mut ref`.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Variable {
     pub symbol: Symbol,
     pub ty: Type,
+    pub is_mut: bool,
 }
 
 impl Variable {
@@ -304,8 +1259,23 @@ impl Variable {
         Variable {
             symbol: symbol,
             ty: ty,
+            is_mut: false,
         }
     }
+
+    pub fn new_mut(symbol: Symbol, ty: Type) -> Variable {
+        Variable {
+            symbol: symbol,
+            ty: ty,
+            is_mut: true,
+        }
+    }
+}
+
+impl Identify for Variable {
+    fn identify(&self) -> Identifier {
+        self.symbol.identify()
+    }
 }
 
 impl Symbolise for Variable {
@@ -317,11 +1287,38 @@ impl Symbolise for Variable {
 ///
 pub type Variables = Vec<Variable>;
 
-///
+/// A `VariableExpr` with a parent refers to an element of the struct that
+/// its parent evaluates to, e.g. `p.x`. The parent can also be a `ref` to a
+/// struct, in which case the element is accessed through the reference.
 #[derive(Clone, Debug, PartialEq)]
 pub struct VariableExpr {
-    variable: Variable,
-    parent: Exprs,
+    pub identifier: Identifier,
+    pub variable: Variable,
+    pub parent: Exprs,
+}
+
+impl VariableExpr {
+    pub fn new(identifier: Identifier, variable: Variable) -> VariableExpr {
+        VariableExpr {
+            identifier: identifier,
+            variable: variable,
+            parent: Exprs::new(),
+        }
+    }
+
+    pub fn new_with_parent(identifier: Identifier, parent: Expr, variable: Variable) -> VariableExpr {
+        VariableExpr {
+            identifier: identifier,
+            variable: variable,
+            parent: vec![parent],
+        }
+    }
+}
+
+impl Identify for VariableExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
 }
 
 ///
@@ -347,19 +1344,75 @@ impl Identify for VoidExpr {
 pub enum Expr {
     Assign(Box<AssignExpr>),
     Block(Box<BlockExpr>),
+    Break(Box<BreakExpr>),
     Call(Box<CallExpr>),
+    Channel(Box<ChannelExpr>),
+    ChannelOp(Box<ChannelOpExpr>),
+    Coalesce(Box<CoalesceExpr>),
+    Continue(Box<ContinueExpr>),
     Deref(Box<DerefExpr>),
+    Do(Box<DoExpr>),
+    Enum(Box<EnumExpr>),
     For(Box<ForExpr>),
     If(Box<IfExpr>),
     Item(Box<ItemExpr>),
+    Lambda(Box<LambdaExpr>),
     Let(Box<LetExpr>),
+    LetTuple(Box<LetTupleExpr>),
+    List(Box<ListExpr>),
+    ListOp(Box<ListOpExpr>),
     Literal(Box<LiteralExpr>),
+    Match(Box<MatchExpr>),
+    Optional(Box<OptionalExpr>),
+    Process(Box<ProcessExpr>),
+    Range(Box<RangeExpr>),
     Struct(Box<StructExpr>),
     Ref(Box<RefExpr>),
+    Select(Box<SelectExpr>),
+    Tuple(Box<TupleExpr>),
+    Unwrap(Box<UnwrapExpr>),
     Variable(Box<VariableExpr>),
     Void(Box<VoidExpr>),
 }
 
+impl Identify for Expr {
+    fn identify(&self) -> Identifier {
+        match *self {
+            Expr::Assign(ref expr) => expr.identify(),
+            Expr::Block(ref expr) => expr.identify(),
+            Expr::Break(ref expr) => expr.identify(),
+            Expr::Call(ref expr) => expr.identify(),
+            Expr::Channel(ref expr) => expr.identify(),
+            Expr::ChannelOp(ref expr) => expr.identify(),
+            Expr::Coalesce(ref expr) => expr.identify(),
+            Expr::Continue(ref expr) => expr.identify(),
+            Expr::Deref(ref expr) => expr.identify(),
+            Expr::Do(ref expr) => expr.identify(),
+            Expr::Enum(ref expr) => expr.identify(),
+            Expr::For(ref expr) => expr.identify(),
+            Expr::If(ref expr) => expr.identify(),
+            Expr::Item(ref expr) => expr.identify(),
+            Expr::Lambda(ref expr) => expr.identify(),
+            Expr::Let(ref expr) => expr.identify(),
+            Expr::LetTuple(ref expr) => expr.identify(),
+            Expr::List(ref expr) => expr.identify(),
+            Expr::ListOp(ref expr) => expr.identify(),
+            Expr::Literal(ref expr) => expr.identify(),
+            Expr::Match(ref expr) => expr.identify(),
+            Expr::Optional(ref expr) => expr.identify(),
+            Expr::Process(ref expr) => expr.identify(),
+            Expr::Range(ref expr) => expr.identify(),
+            Expr::Struct(ref expr) => expr.identify(),
+            Expr::Ref(ref expr) => expr.identify(),
+            Expr::Select(ref expr) => expr.identify(),
+            Expr::Tuple(ref expr) => expr.identify(),
+            Expr::Unwrap(ref expr) => expr.identify(),
+            Expr::Variable(ref expr) => expr.identify(),
+            Expr::Void(ref expr) => expr.identify(),
+        }
+    }
+}
+
 impl From<AssignExpr> for Expr {
     fn from(assign_expr: AssignExpr) -> Expr {
         Expr::Assign(assign_expr.into())
@@ -372,12 +1425,174 @@ impl From<BlockExpr> for Expr {
     }
 }
 
+impl From<BreakExpr> for Expr {
+    fn from(break_expr: BreakExpr) -> Expr {
+        Expr::Break(break_expr.into())
+    }
+}
+
 impl From<CallExpr> for Expr {
     fn from(call_expr: CallExpr) -> Expr {
         Expr::Call(call_expr.into())
     }
 }
 
+impl From<ChannelExpr> for Expr {
+    fn from(channel_expr: ChannelExpr) -> Expr {
+        Expr::Channel(channel_expr.into())
+    }
+}
+
+impl From<ChannelOpExpr> for Expr {
+    fn from(channel_op_expr: ChannelOpExpr) -> Expr {
+        Expr::ChannelOp(channel_op_expr.into())
+    }
+}
+
+impl From<CoalesceExpr> for Expr {
+    fn from(coalesce_expr: CoalesceExpr) -> Expr {
+        Expr::Coalesce(coalesce_expr.into())
+    }
+}
+
+impl From<ContinueExpr> for Expr {
+    fn from(continue_expr: ContinueExpr) -> Expr {
+        Expr::Continue(continue_expr.into())
+    }
+}
+
+impl From<DerefExpr> for Expr {
+    fn from(deref_expr: DerefExpr) -> Expr {
+        Expr::Deref(deref_expr.into())
+    }
+}
+
+impl From<DoExpr> for Expr {
+    fn from(do_expr: DoExpr) -> Expr {
+        Expr::Do(do_expr.into())
+    }
+}
+
+impl From<EnumExpr> for Expr {
+    fn from(enum_expr: EnumExpr) -> Expr {
+        Expr::Enum(enum_expr.into())
+    }
+}
+
+impl From<ForExpr> for Expr {
+    fn from(for_expr: ForExpr) -> Expr {
+        Expr::For(for_expr.into())
+    }
+}
+
+impl From<IfExpr> for Expr {
+    fn from(if_expr: IfExpr) -> Expr {
+        Expr::If(if_expr.into())
+    }
+}
+
+impl From<ItemExpr> for Expr {
+    fn from(item_expr: ItemExpr) -> Expr {
+        Expr::Item(item_expr.into())
+    }
+}
+
+impl From<LambdaExpr> for Expr {
+    fn from(lambda_expr: LambdaExpr) -> Expr {
+        Expr::Lambda(lambda_expr.into())
+    }
+}
+
+impl From<LetExpr> for Expr {
+    fn from(let_expr: LetExpr) -> Expr {
+        Expr::Let(let_expr.into())
+    }
+}
+
+impl From<LetTupleExpr> for Expr {
+    fn from(let_tuple_expr: LetTupleExpr) -> Expr {
+        Expr::LetTuple(let_tuple_expr.into())
+    }
+}
+
+impl From<ListExpr> for Expr {
+    fn from(list_expr: ListExpr) -> Expr {
+        Expr::List(list_expr.into())
+    }
+}
+
+impl From<ListOpExpr> for Expr {
+    fn from(list_op_expr: ListOpExpr) -> Expr {
+        Expr::ListOp(list_op_expr.into())
+    }
+}
+
+impl From<LiteralExpr> for Expr {
+    fn from(literal_expr: LiteralExpr) -> Expr {
+        Expr::Literal(literal_expr.into())
+    }
+}
+
+impl From<MatchExpr> for Expr {
+    fn from(match_expr: MatchExpr) -> Expr {
+        Expr::Match(match_expr.into())
+    }
+}
+
+impl From<OptionalExpr> for Expr {
+    fn from(optional_expr: OptionalExpr) -> Expr {
+        Expr::Optional(optional_expr.into())
+    }
+}
+
+impl From<ProcessExpr> for Expr {
+    fn from(process_expr: ProcessExpr) -> Expr {
+        Expr::Process(process_expr.into())
+    }
+}
+
+impl From<RangeExpr> for Expr {
+    fn from(range_expr: RangeExpr) -> Expr {
+        Expr::Range(range_expr.into())
+    }
+}
+
+impl From<RefExpr> for Expr {
+    fn from(ref_expr: RefExpr) -> Expr {
+        Expr::Ref(ref_expr.into())
+    }
+}
+
+impl From<SelectExpr> for Expr {
+    fn from(select_expr: SelectExpr) -> Expr {
+        Expr::Select(select_expr.into())
+    }
+}
+
+impl From<StructExpr> for Expr {
+    fn from(struct_expr: StructExpr) -> Expr {
+        Expr::Struct(struct_expr.into())
+    }
+}
+
+impl From<TupleExpr> for Expr {
+    fn from(tuple_expr: TupleExpr) -> Expr {
+        Expr::Tuple(tuple_expr.into())
+    }
+}
+
+impl From<UnwrapExpr> for Expr {
+    fn from(unwrap_expr: UnwrapExpr) -> Expr {
+        Expr::Unwrap(unwrap_expr.into())
+    }
+}
+
+impl From<VariableExpr> for Expr {
+    fn from(variable_expr: VariableExpr) -> Expr {
+        Expr::Variable(variable_expr.into())
+    }
+}
+
 impl From<VoidExpr> for Expr {
     fn from(void_expr: VoidExpr) -> Expr {
         Expr::Void(void_expr.into())
@@ -393,7 +1608,6 @@ pub enum Item {
     Function(Box<Function>),
     Module(Box<Module>),
     Type(Box<Type>),
-    Uresolved(Box<Unresolved>),
     Variable(Box<Variable>),
 }
 
@@ -425,16 +1639,17 @@ impl From<Variable> for Item {
 #[derive(Clone, Debug, PartialEq)]
 pub enum Literal {
     Bool(bool),
-    Channel(Expr, Expr),
     Char(char),
     F32(f32),
     F64(f64),
+    Float(f64),
     I8(i8),
     I16(i16),
     I32(i32),
     I64(i64),
-    List(Exprs),
-    ListRange(Expr, Expr),
+    /// An integer whose type is yet to be inferred, which is wide enough to
+    /// hold a value of any integer type.
+    Int(i128),
     Str(String),
     U8(u8),
     U16(u16),
@@ -463,21 +1678,258 @@ pub enum PrimitiveType {
     Void,
 }
 
+impl fmt::Display for PrimitiveType {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        use self::PrimitiveType::*;
+
+        match *self {
+            Bool => write!(formatter, "bool"),
+            Char => write!(formatter, "char"),
+            F32 => write!(formatter, "f32"),
+            F64 => write!(formatter, "f64"),
+            I8 => write!(formatter, "i8"),
+            I16 => write!(formatter, "i16"),
+            I32 => write!(formatter, "i32"),
+            I64 => write!(formatter, "i64"),
+            Str => write!(formatter, "string"),
+            U8 => write!(formatter, "u8"),
+            U16 => write!(formatter, "u16"),
+            U32 => write!(formatter, "u32"),
+            U64 => write!(formatter, "u64"),
+            USize => write!(formatter, "usize"),
+            Void => write!(formatter, "void"),
+        }
+    }
+}
+
+impl PrimitiveType {
+    pub fn is_float(&self) -> bool {
+        match *self {
+            PrimitiveType::F32 | PrimitiveType::F64 => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_int(&self) -> bool {
+        match *self {
+            PrimitiveType::I8 |
+            PrimitiveType::I16 |
+            PrimitiveType::I32 |
+            PrimitiveType::I64 |
+            PrimitiveType::U8 |
+            PrimitiveType::U16 |
+            PrimitiveType::U32 |
+            PrimitiveType::U64 |
+            PrimitiveType::USize => true,
+            _ => false,
+        }
+    }
+}
+
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub enum Type {
     Alias(Box<AliasType>),
+    Channel(Box<ChannelType>),
     Enum(Box<EnumType>),
     Generic(Box<GenericType>),
+    Infer(Box<InferType>),
     Lambda(Box<LambdaType>),
+    List(Box<ListType>),
+    Optional(Box<OptionalType>),
     Primitive(Box<PrimitiveType>),
     Ptr(Box<PtrType>),
     Ref(Box<RefType>),
     Struct(Box<StructType>),
+    Tuple(Box<TupleType>),
+}
+
+impl Type {
+    pub fn infer() -> Type {
+        InferType::new(InferKind::Any).into()
+    }
+
+    /// Substitute every `GenericType` that appears in the `Substitution`.
+    pub fn substitute(&self, substitution: &Substitution) -> Type {
+        match *self {
+            Type::Alias(ref ty) => Type::Alias(Box::new(AliasType { inner: ty.inner.substitute(substitution) })),
+            Type::Channel(ref ty) => ChannelType::new(ty.element.substitute(substitution)).into(),
+            Type::Enum(ref ty) => {
+                Type::Enum(Box::new(EnumType {
+                    symbol: ty.symbol.clone(),
+                    params: ty.params.iter().map(|param| param.substitute(substitution)).collect(),
+                    variants: Box::new(ty.variants.substitute(substitution)),
+                }))
+            }
+            Type::Generic(ref ty) => {
+                match substitution.get(&ty.identify()) {
+                    Some(ty) => ty.clone(),
+                    None => self.clone(),
+                }
+            }
+            Type::Lambda(ref ty) => {
+                LambdaType::new(ty.formals.iter().map(|formal| formal.substitute(substitution)).collect(),
+                                ty.ret.substitute(substitution))
+                    .into()
+            }
+            Type::List(ref ty) => ListType::new(ty.element.substitute(substitution)).into(),
+            Type::Optional(ref ty) => OptionalType::new(ty.inner.substitute(substitution)).into(),
+            Type::Ptr(ref ty) => Type::Ptr(Box::new(PtrType { inner: ty.inner.substitute(substitution) })),
+            Type::Ref(ref ty) => Type::Ref(Box::new(RefType { inner: ty.inner.substitute(substitution), is_mut: ty.is_mut })),
+            Type::Struct(ref ty) => ty.substitute(substitution).into(),
+            Type::Tuple(ref ty) => {
+                TupleType::new(ty.elements.iter().map(|element| element.substitute(substitution)).collect()).into()
+            }
+            Type::Infer(..) |
+            Type::Primitive(..) => self.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Type::Alias(ref ty) => write!(formatter, "{}", ty.inner),
+            Type::Channel(ref ty) => write!(formatter, "..{}", ty.element),
+            Type::Enum(ref ty) => {
+                write!(formatter, "{}", ty.symbol.name())?;
+                for param in ty.params.iter() {
+                    write!(formatter, " {}", param)?;
+                }
+                Ok(())
+            }
+            Type::Generic(ref ty) => write!(formatter, "{}", ty.identifier.name()),
+            Type::Infer(ref ty) => {
+                match ty.kind {
+                    InferKind::Any => write!(formatter, "_"),
+                    InferKind::Float => write!(formatter, "{{float}}"),
+                    InferKind::Int => write!(formatter, "{{integer}}"),
+                }
+            }
+            Type::Lambda(ref ty) => {
+                write!(formatter, "fn(")?;
+                for (i, formal) in ty.formals.iter().enumerate() {
+                    if i > 0 {
+                        write!(formatter, ", ")?;
+                    }
+                    write!(formatter, "{}", formal)?;
+                }
+                write!(formatter, ") {}", ty.ret)
+            }
+            Type::List(ref ty) => write!(formatter, "[{}]", ty.element),
+            Type::Optional(ref ty) => write!(formatter, "{}?", ty.inner),
+            Type::Primitive(ref ty) => write!(formatter, "{}", ty),
+            Type::Ptr(ref ty) => write!(formatter, "ptr {}", ty.inner),
+            Type::Ref(ref ty) if ty.is_mut => write!(formatter, "mut ref {}", ty.inner),
+            Type::Ref(ref ty) => write!(formatter, "ref {}", ty.inner),
+            Type::Struct(ref ty) => {
+                write!(formatter, "{}", ty.symbol.name())?;
+                for param in ty.params.iter() {
+                    write!(formatter, " {}", param)?;
+                }
+                Ok(())
+            }
+            Type::Tuple(ref ty) => {
+                write!(formatter, "(")?;
+                for (i, element) in ty.elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(formatter, ", ")?;
+                    }
+                    write!(formatter, "{}", element)?;
+                }
+                write!(formatter, ")")
+            }
+        }
+    }
+}
+
+impl From<AliasType> for Type {
+    fn from(ty: AliasType) -> Type {
+        Type::Alias(ty.into())
+    }
+}
+
+impl From<ChannelType> for Type {
+    fn from(ty: ChannelType) -> Type {
+        Type::Channel(ty.into())
+    }
+}
+
+impl From<EnumType> for Type {
+    fn from(ty: EnumType) -> Type {
+        Type::Enum(ty.into())
+    }
+}
+
+impl From<GenericType> for Type {
+    fn from(ty: GenericType) -> Type {
+        Type::Generic(ty.into())
+    }
+}
+
+impl From<InferType> for Type {
+    fn from(ty: InferType) -> Type {
+        Type::Infer(ty.into())
+    }
+}
+
+impl From<LambdaType> for Type {
+    fn from(ty: LambdaType) -> Type {
+        Type::Lambda(ty.into())
+    }
+}
+
+impl From<ListType> for Type {
+    fn from(ty: ListType) -> Type {
+        Type::List(ty.into())
+    }
+}
+
+impl From<OptionalType> for Type {
+    fn from(ty: OptionalType) -> Type {
+        Type::Optional(ty.into())
+    }
+}
+
+impl From<PrimitiveType> for Type {
+    fn from(ty: PrimitiveType) -> Type {
+        Type::Primitive(ty.into())
+    }
+}
+
+impl From<PtrType> for Type {
+    fn from(ty: PtrType) -> Type {
+        Type::Ptr(ty.into())
+    }
+}
+
+impl From<RefType> for Type {
+    fn from(ty: RefType) -> Type {
+        Type::Ref(ty.into())
+    }
+}
+
+impl From<StructType> for Type {
+    fn from(ty: StructType) -> Type {
+        Type::Struct(ty.into())
+    }
+}
+
+impl From<TupleType> for Type {
+    fn from(ty: TupleType) -> Type {
+        Type::Tuple(ty.into())
+    }
 }
 
 ///
 pub type Types = Vec<Type>;
 
 ///
-pub type TypeTable = HashMap<Identifier, Type>;
\ No newline at end of file
+pub type TypeTable = HashMap<Identifier, Type>;
+
+/// A mapping from the `Identifier` of a `GenericType` to the `Type` that
+/// substitutes it.
+pub type Substitution = HashMap<Identifier, Type>;
+
+///
+pub type SpanTable = HashMap<Identifier, Span>;
@@ -75,6 +75,7 @@ impl FunctionDecl {
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub struct Function {
+    pub type_params: TypeParams,
     pub formals: FunctionFormals,
     pub ret: Type,
     pub body: Option<Expr>,
@@ -84,8 +85,16 @@ impl Function {
     pub fn new<Formals, Ty>(formals: Formals, ret: Ty, body: Option<Expr>) -> Function
         where Formals: Into<FunctionFormals>,
               Ty: Into<Type>
+    {
+        Function::new_generic(TypeParams::new(), formals, ret, body)
+    }
+
+    pub fn new_generic<Formals, Ty>(type_params: TypeParams, formals: Formals, ret: Ty, body: Option<Expr>) -> Function
+        where Formals: Into<FunctionFormals>,
+              Ty: Into<Type>
     {
         Function {
+            type_params: type_params,
             formals: formals.into(),
             ret: ret.into(),
             body: body,
@@ -206,6 +215,7 @@ pub struct StructFields {
 #[derive(Clone, Debug, PartialEq)]
 pub enum Type {
     Channel(Box<ChannelType>),
+    Lambda(Box<LambdaType>),
     List(Box<ListType>),
     Optional(Box<OptionalType>),
     Ref(Box<Type>),
@@ -230,6 +240,25 @@ pub struct ChannelType {
     pub generic_type: Type,
 }
 
+/// A `LambdaType` is the type of a function value, e.g. `fn(i64) i64`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LambdaType {
+    pub formals: Types,
+    pub ret: Type,
+}
+
+impl LambdaType {
+    pub fn new<Tys, Ty>(formals: Tys, ret: Ty) -> LambdaType
+        where Tys: Into<Types>,
+              Ty: Into<Type>
+    {
+        LambdaType {
+            formals: formals.into(),
+            ret: ret.into(),
+        }
+    }
+}
+
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub struct ListType {
@@ -282,11 +311,14 @@ pub enum Expr {
     Call(Box<CallExpr>),
     Channel(Box<ChannelExpr>),
     Deref(Box<DerefExpr>),
+    Do(Box<DoExpr>),
     For(Box<ForExpr>),
     If(Box<IfExpr>),
     Item(Box<ItemExpr>),
+    Lambda(Box<LambdaExpr>),
     List(Box<ListExpr>),
     Literal(Box<LiteralExpr>),
+    Match(Box<MatchExpr>),
     Operator(Box<OperatorExpr>),
     Ref(Box<RefExpr>),
     Select(Box<SelectExpr>),
@@ -352,6 +384,61 @@ impl From<Box<LiteralExpr>> for Expr {
     }
 }
 
+/// An `Expr` can be created from a `SuffixOperatorExpr`.
+impl From<SuffixOperatorExpr> for Expr {
+    fn from(expr: SuffixOperatorExpr) -> Expr {
+        Expr::Operator(Box::new(OperatorExpr::Suffix(expr.into())))
+    }
+}
+
+/// An `Expr` can be created from a `ChannelExpr`.
+impl From<ChannelExpr> for Expr {
+    fn from(expr: ChannelExpr) -> Expr {
+        Expr::Channel(expr.into())
+    }
+}
+
+/// An `Expr` can be created from a `PrefixOperatorExpr`.
+impl From<PrefixOperatorExpr> for Expr {
+    fn from(expr: PrefixOperatorExpr) -> Expr {
+        Expr::Operator(OperatorExpr::Prefix(expr.into()).into())
+    }
+}
+
+/// An `Expr` can be created from a `DoExpr`.
+impl From<DoExpr> for Expr {
+    fn from(expr: DoExpr) -> Expr {
+        Expr::Do(expr.into())
+    }
+}
+
+impl From<ForExpr> for Expr {
+    fn from(expr: ForExpr) -> Expr {
+        Expr::For(expr.into())
+    }
+}
+
+/// An `Expr` can be created from a `LambdaExpr`.
+impl From<LambdaExpr> for Expr {
+    fn from(expr: LambdaExpr) -> Expr {
+        Expr::Lambda(expr.into())
+    }
+}
+
+/// An `Expr` can be created from a `ListExpr`.
+impl From<ListExpr> for Expr {
+    fn from(expr: ListExpr) -> Expr {
+        Expr::List(expr.into())
+    }
+}
+
+/// An `Expr` can be created from a `TupleExpr`.
+impl From<TupleExpr> for Expr {
+    fn from(expr: TupleExpr) -> Expr {
+        Expr::Tuple(expr.into())
+    }
+}
+
 /// An `Expr` can be created from a `VoidExpr`.
 impl From<VoidExpr> for Expr {
     fn from(expr: VoidExpr) -> Expr {
@@ -410,7 +497,7 @@ pub type BlockStatements = Vec<BlockStatement>;
 pub struct LetStatement {
     pub is_mut: bool,
     pub lhs: Pattern,
-    pub ty: Type,
+    pub ty: Option<Type>,
     pub rhs: Expr,
 }
 
@@ -428,15 +515,66 @@ pub struct ChannelExpr {
     pub end: Expr,
 }
 
+impl ChannelExpr {
+    pub fn new<Begin, End>(begin: Begin, end: End) -> ChannelExpr
+        where Begin: Into<Option<Expr>>,
+              End: Into<Expr>
+    {
+        ChannelExpr {
+            begin: begin.into(),
+            end: end.into(),
+        }
+    }
+}
+
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub struct DerefExpr {
     pub dereferent: Expr,
 }
 
-///
+/// A `DoExpr` evaluates its `do` expression before its `then` expression,
+/// e.g. `do a then b`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DoExpr {
+    pub do_expr: Expr,
+    pub then_expr: Expr,
+}
+
+impl DoExpr {
+    pub fn new<D, T>(do_expr: D, then_expr: T) -> DoExpr
+        where D: Into<Expr>,
+              T: Into<Expr>
+    {
+        DoExpr {
+            do_expr: do_expr.into(),
+            then_expr: then_expr.into(),
+        }
+    }
+}
+
+/// A `ForExpr` evaluates its iteration for every element of its iterator,
+/// e.g. `for x in xs { .. }`. A `forall` runs every iteration concurrently.
 #[derive(Clone, Debug, PartialEq)]
-pub struct ForExpr {}
+pub struct ForExpr {
+    pub is_concurrent: bool,
+    pub pattern: Pattern,
+    pub iterator: Expr,
+    pub iteration: BlockExpr,
+}
+
+impl ForExpr {
+    pub fn new<I>(is_concurrent: bool, pattern: Pattern, iterator: I, iteration: BlockExpr) -> ForExpr
+        where I: Into<Expr>
+    {
+        ForExpr {
+            is_concurrent: is_concurrent,
+            pattern: pattern,
+            iterator: iterator.into(),
+            iteration: iteration,
+        }
+    }
+}
 
 ///
 #[derive(Clone, Debug, PartialEq)]
@@ -453,12 +591,38 @@ pub struct ItemExpr {
     pub item: Identifier,
 }
 
+/// A `LambdaExpr` is an anonymous function, e.g. `|x i64| -> x + 1`. It
+/// captures the variables that it uses from the enclosing scope.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LambdaExpr {
+    pub formals: FunctionFormals,
+    pub body: Expr,
+}
+
+impl LambdaExpr {
+    pub fn new<Formals, Body>(formals: Formals, body: Body) -> LambdaExpr
+        where Formals: Into<FunctionFormals>,
+              Body: Into<Expr>
+    {
+        LambdaExpr {
+            formals: formals.into(),
+            body: body.into(),
+        }
+    }
+}
+
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub struct ListExpr {
     pub items: Exprs,
 }
 
+impl ListExpr {
+    pub fn new<Items: Into<Exprs>>(items: Items) -> ListExpr {
+        ListExpr { items: items.into() }
+    }
+}
+
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub enum LiteralExpr {
@@ -466,6 +630,7 @@ pub enum LiteralExpr {
     Char(char, Span),
     Float(f64, Span),
     Int(i64, Span),
+    Nil(Span),
     Str(String, Span),
 }
 
@@ -478,6 +643,7 @@ impl Spanned for LiteralExpr {
             Char(_, ref span, ..) => span,
             Float(_, ref span, ..) => span,
             Int(_, ref span, ..) => span,
+            Nil(ref span, ..) => span,
             Str(_, ref span, ..) => span,
         }
     }
@@ -490,11 +656,30 @@ impl Spanned for LiteralExpr {
             Char(_, ref mut span, ..) => span,
             Float(_, ref mut span, ..) => span,
             Int(_, ref mut span, ..) => span,
+            Nil(ref mut span, ..) => span,
             Str(_, ref mut span, ..) => span,
         }
     }
 }
 
+/// A `MatchExpr` evaluates the body of the first arm whose pattern matches
+/// its subject. The arms must cover every variant of the subject.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchExpr {
+    pub subject: Expr,
+    pub arms: MatchArms,
+}
+
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub body: Expr,
+}
+
+///
+pub type MatchArms = Vec<MatchArm>;
+
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub enum OperatorExpr {
@@ -532,6 +717,18 @@ pub struct PrefixOperatorExpr {
     pub rhs: Expr,
 }
 
+impl PrefixOperatorExpr {
+    pub fn new<Op, RHS>(operator: Op, rhs: RHS) -> PrefixOperatorExpr
+        where Op: Into<Operator>,
+              RHS: Into<Expr>
+    {
+        PrefixOperatorExpr {
+            operator: operator.into(),
+            rhs: rhs.into(),
+        }
+    }
+}
+
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub struct SuffixOperatorExpr {
@@ -539,6 +736,18 @@ pub struct SuffixOperatorExpr {
     pub lhs: Expr,
 }
 
+impl SuffixOperatorExpr {
+    pub fn new<Op, LHS>(operator: Op, lhs: LHS) -> SuffixOperatorExpr
+        where Op: Into<Operator>,
+              LHS: Into<Expr>
+    {
+        SuffixOperatorExpr {
+            operator: operator.into(),
+            lhs: lhs.into(),
+        }
+    }
+}
+
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub enum Operator {
@@ -546,6 +755,7 @@ pub enum Operator {
     AddEq,
     And,
     Assign,
+    Coalesce,
     Div,
     DivEq,
     Equal,
@@ -557,8 +767,10 @@ pub enum Operator {
     MulEq,
     Or,
     PushPop,
+    Range,
     Sub,
     SubEq,
+    Unwrap,
 }
 
 ///
@@ -604,6 +816,12 @@ pub struct TupleExpr {
     pub fields: Exprs,
 }
 
+impl TupleExpr {
+    pub fn new<Fields: Into<Exprs>>(fields: Fields) -> TupleExpr {
+        TupleExpr { fields: fields.into() }
+    }
+}
+
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub struct VoidExpr {
@@ -624,6 +842,7 @@ pub enum Pattern {
     List(Box<ListPattern>),
     Tuple(Box<TuplePattern>),
     Variable(Box<VariablePattern>),
+    Variant(Box<VariantPattern>),
 }
 
 ///
@@ -656,4 +875,12 @@ pub struct TuplePattern {
 }
 
 ///
-pub type VariablePattern = Identifier;
\ No newline at end of file
+pub type VariablePattern = Identifier;
+
+/// A `VariantPattern` matches a variant of an enum, and matches the fields
+/// of the variant against its own patterns.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariantPattern {
+    pub identifier: Identifier,
+    pub fields: Patterns,
+}
\ No newline at end of file
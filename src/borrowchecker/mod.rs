@@ -0,0 +1,637 @@
+//! # Borrow Checker
+//!
+//! The borrow checker enforces the rules of `mut ref`, which is a mutable,
+//! and mutually exclusive, reference. No other reference to a value can exist
+//! alongside a `mut ref` to the same value. This is what stops processes from
+//! racing to read and write the same value.
+//!
+//! A reference lives until the end of the block in which it is taken. A
+//! reference that is taken in the arguments of a call only lives until the
+//! end of the call, so `f(ref x, ref x)` is fine but `f(mut ref x, ref x)` is
+//! not. The branches of an `if`, and the arms of a `match`, are blocks of their
+//! own, so their references never meet.
+//!
+//! A value can only be mutated, by assigning to it or by pushing to and popping
+//! from a list, if it belongs to a mutable variable, e.g. `let mut x`, or if it
+//! is reached through a `mut ref`. The same goes for taking a `mut ref` to the
+//! value. A plain `ref` can only be read through. A variable cannot be mutated
+//! directly while it is referenced.
+//!
+//! A process captures every variable that it uses, but does not declare. The
+//! processes of a block run at the same time until the end of the block, the
+//! sides of a `do` run at the same time as each other, and every iteration of
+//! a `forall` runs at the same time as every other iteration. A variable that
+//! is captured by processes that run at the same time must not be mutable,
+//! unless it is a channel. A mutable variable, or a `mut ref`, can only be
+//! moved into a single process, and cannot be used by the enclosing block
+//! until the process is joined at the end of the block.
+//!
+//! A lambda captures a copy of every variable that it uses, but does not
+//! declare, so it cannot capture a mutable variable, or a `mut ref`, whose
+//! changes it would never see.
+//!
+//! The borrow checker runs on an AIR that has been type checked, and so the
+//! type of every variable is known.
+
+#[cfg(test)]
+pub mod mod_test;
+
+use super::air::*;
+use super::identifier::{Identifier, Identify, Name, Symbolise};
+use super::lexer::Span;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::mem;
+
+pub struct BorrowErr {
+    span: Option<Span>,
+    message: String,
+    notes: Vec<(Option<Span>, String)>,
+}
+
+impl BorrowErr {
+    pub fn new(span: Option<Span>, message: String) -> BorrowErr {
+        BorrowErr::new_with_notes(span, message, Vec::new())
+    }
+
+    pub fn new_with_notes(span: Option<Span>, message: String, notes: Vec<(Option<Span>, String)>) -> BorrowErr {
+        BorrowErr {
+            span: span,
+            message: message,
+            notes: notes,
+        }
+    }
+
+    pub fn span(&self) -> Option<&Span> {
+        self.span.as_ref()
+    }
+
+    pub fn message(&self) -> &String {
+        &self.message
+    }
+
+    /// The notes point at the other places that are involved in the error,
+    /// such as the other process in a data race.
+    pub fn notes(&self) -> &Vec<(Option<Span>, String)> {
+        &self.notes
+    }
+}
+
+impl fmt::Display for BorrowErr {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.span {
+            Some(ref span) => write!(formatter, "{}: {}", span.begin(), self.message)?,
+            None => write!(formatter, "{}", self.message)?,
+        }
+        for &(ref span, ref note) in self.notes.iter() {
+            match *span {
+                Some(ref span) => write!(formatter, "\n{}: note: {}", span.begin(), note)?,
+                None => write!(formatter, "\nnote: {}", note)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A `Borrow` is a reference that is alive, to the variable that owns the
+/// referenced value.
+struct Borrow {
+    owner: Identifier,
+    is_mut: bool,
+}
+
+/// A `Process` that is still running, with the mutable variables that it
+/// has captured.
+struct Process {
+    identifier: Identifier,
+    captures: Variables,
+}
+
+pub struct BorrowChecker {
+    context: Context,
+    borrows: Vec<Vec<Borrow>>,
+    /// The processes that are running in each of the enclosing blocks.
+    processes: Vec<Vec<Process>>,
+    /// The variables captured by each of the enclosing processes.
+    captures: Vec<Variables>,
+    /// The number of blocks with running processes at the start of each of
+    /// the enclosing processes, which run alongside the processes of those
+    /// blocks.
+    process_bases: Vec<usize>,
+    /// The number of enclosing processes at the declaration of each variable.
+    declarations: HashMap<Identifier, usize>,
+    errors: Vec<BorrowErr>,
+}
+
+impl BorrowChecker {
+    pub fn new(context: Context) -> BorrowChecker {
+        BorrowChecker {
+            context: context,
+            borrows: Vec::new(),
+            processes: Vec::new(),
+            captures: Vec::new(),
+            process_bases: Vec::new(),
+            declarations: HashMap::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn check_module(&mut self, module: &Module) {
+        for function in module.function_table.values() {
+            self.check_function(function);
+        }
+        for module in module.module_table.values() {
+            self.check_module(module);
+        }
+    }
+
+    pub fn check_function(&mut self, function: &Function) {
+        // references and processes never outlive the function that makes
+        // them, and a function cannot capture anything
+        let borrows = mem::replace(&mut self.borrows, Vec::new());
+        let processes = mem::replace(&mut self.processes, Vec::new());
+        let captures = mem::replace(&mut self.captures, Vec::new());
+        let process_bases = mem::replace(&mut self.process_bases, Vec::new());
+        if let Some(ref body) = function.body {
+            self.declare_variables(&function.formals);
+            self.borrows.push(Vec::new());
+            self.processes.push(Vec::new());
+            self.check_expr(body);
+            self.processes.pop();
+            self.borrows.pop();
+        }
+        self.borrows = borrows;
+        self.processes = processes;
+        self.captures = captures;
+        self.process_bases = process_bases;
+    }
+
+    pub fn check_assign_expr(&mut self, assign_expr: &AssignExpr) {
+        self.check_expr(&assign_expr.rhs);
+        self.check_expr(&assign_expr.lhs);
+        self.check_mutable(&assign_expr.lhs, "assign to");
+        self.check_unborrowed(&assign_expr.lhs, "assign to");
+    }
+
+    pub fn check_block_expr(&mut self, block_expr: &BlockExpr) {
+        for function in block_expr.function_table.values() {
+            self.check_function(function);
+        }
+        for module in block_expr.module_table.values() {
+            self.check_module(module);
+        }
+        self.borrows.push(Vec::new());
+        self.processes.push(Vec::new());
+        for expr in block_expr.body.iter() {
+            self.check_expr(expr);
+        }
+        self.check_expr(&block_expr.ret);
+        self.processes.pop();
+        self.borrows.pop();
+    }
+
+    pub fn check_call_expr(&mut self, call_expr: &CallExpr) {
+        self.check_expr(&call_expr.target);
+        self.borrows.push(Vec::new());
+        self.check_exprs(&call_expr.arguments);
+        self.borrows.pop();
+    }
+
+    /// Every side of a `do` is a block whose expressions all run at the same
+    /// time, in processes of their own.
+    pub fn check_do_expr(&mut self, do_expr: &DoExpr) {
+        self.check_concurrent_expr(&do_expr.do_expr);
+        self.check_concurrent_expr(&do_expr.then_expr);
+    }
+
+    /// Every iteration of a `forall` runs at the same time as every other, so
+    /// an iteration cannot capture anything mutable.
+    pub fn check_for_expr(&mut self, for_expr: &ForExpr) {
+        self.check_expr(&for_expr.iterator);
+        if !for_expr.is_concurrent {
+            self.declare_variables(&for_expr.formals);
+            return self.check_block_expr(&for_expr.iteration);
+        }
+        self.captures.push(Variables::new());
+        self.declare_variables(&for_expr.formals);
+        self.check_block_expr(&for_expr.iteration);
+        let captures = self.captures
+            .pop()
+            .expect("expected captures for the iteration")
+            .into_iter()
+            .filter(|capture| self.is_shared_mutable(capture))
+            .collect::<Variables>();
+        for capture in captures {
+            let message = format!("`{}` is mutable, and is captured by every iteration of a `forall`, which all run \
+                                   at the same time",
+                                  capture.symbolise().name());
+            self.error(&for_expr.identify(), message);
+        }
+    }
+
+    /// The body of a lambda runs whenever it is called, so its references
+    /// and processes are its own, like those of a function.
+    pub fn check_lambda_expr(&mut self, lambda_expr: &LambdaExpr) {
+        let borrows = mem::replace(&mut self.borrows, vec![Vec::new()]);
+        let processes = mem::replace(&mut self.processes, vec![Vec::new()]);
+        let process_bases = mem::replace(&mut self.process_bases, Vec::new());
+        self.captures.push(Variables::new());
+        self.declare_variables(&lambda_expr.formals);
+        self.check_expr(&lambda_expr.body);
+        let captures = self.captures
+            .pop()
+            .expect("expected captures for the lambda")
+            .into_iter()
+            .filter(|capture| self.is_shared_mutable(capture))
+            .collect::<Variables>();
+        self.borrows = borrows;
+        self.processes = processes;
+        self.process_bases = process_bases;
+        for capture in captures {
+            let message = format!("`{}` is mutable, and a lambda only captures a copy of it",
+                                  capture.symbolise().name());
+            self.error(&lambda_expr.identify(), message);
+        }
+    }
+
+    pub fn check_list_op_expr(&mut self, list_op_expr: &ListOpExpr) {
+        match list_op_expr.op {
+            ListOp::Index(ref list, ref index) => {
+                self.check_expr(list);
+                self.check_expr(index);
+            }
+            ListOp::Len(ref list) => self.check_expr(list),
+            ListOp::Pop(ref list) => {
+                self.check_expr(list);
+                self.check_mutable(list, "pop from");
+                self.check_unborrowed(list, "pop from");
+            }
+            ListOp::Push(ref list, ref element) => {
+                self.check_expr(element);
+                self.check_expr(list);
+                self.check_mutable(list, "push to");
+                self.check_unborrowed(list, "push to");
+            }
+            ListOp::Slice(ref list, ref start, ref end) => {
+                self.check_expr(list);
+                self.check_expr(start);
+                self.check_expr(end);
+            }
+        }
+    }
+
+    /// Check that a reference can be taken alongside every reference that is
+    /// still alive, and then keep it alive until the end of the enclosing
+    /// block or call.
+    pub fn check_ref_expr(&mut self, ref_expr: &RefExpr) {
+        self.check_expr(&ref_expr.inner);
+        if ref_expr.is_mut {
+            self.check_mutable(&ref_expr.inner, "take a `mut ref` of");
+        }
+        let owner = match self.owner(&ref_expr.inner) {
+            Some(owner) => owner,
+            None => return,
+        };
+        let is_conflict = self.borrows
+            .iter()
+            .flat_map(|borrows| borrows.iter())
+            .any(|borrow| borrow.owner == owner.identify() && (borrow.is_mut || ref_expr.is_mut));
+        if is_conflict {
+            let message = if ref_expr.is_mut {
+                format!("cannot take a `mut ref` of `{}`, because it is already referenced",
+                        owner.symbolise().name())
+            } else {
+                format!("cannot take a `ref` of `{}`, because it is already referenced by a `mut ref`",
+                        owner.symbolise().name())
+            };
+            self.error(&ref_expr.identify(), message);
+        }
+        if let Some(borrows) = self.borrows.last_mut() {
+            borrows.push(Borrow {
+                owner: owner.identify(),
+                is_mut: ref_expr.is_mut,
+            });
+        }
+    }
+
+    pub fn check_process_expr(&mut self, process_expr: &ProcessExpr) {
+        self.check_process(&process_expr.identify(), |checker| checker.check_block_expr(&process_expr.body));
+    }
+
+    pub fn check_exprs(&mut self, exprs: &Exprs) {
+        for expr in exprs.iter() {
+            self.check_expr(expr);
+        }
+    }
+
+    pub fn check_expr(&mut self, expr: &Expr) {
+        match *expr {
+            Expr::Assign(ref expr) => self.check_assign_expr(expr),
+            Expr::Block(ref expr) => self.check_block_expr(expr),
+            Expr::Break(..) => (),
+            Expr::Call(ref expr) => self.check_call_expr(expr),
+            Expr::Channel(ref expr) => {
+                if let Some(ref capacity) = expr.capacity {
+                    self.check_expr(capacity);
+                }
+            }
+            Expr::ChannelOp(ref expr) => {
+                match expr.op {
+                    ChannelOp::Close(ref channel) |
+                    ChannelOp::Recv(ref channel) => self.check_expr(channel),
+                    ChannelOp::Send(ref channel, ref value) => {
+                        self.check_expr(channel);
+                        self.check_expr(value);
+                    }
+                }
+            }
+            Expr::Coalesce(ref expr) => {
+                self.check_expr(&expr.lhs);
+                self.check_expr(&expr.rhs);
+            }
+            Expr::Continue(..) => (),
+            Expr::Deref(ref expr) => self.check_expr(&expr.inner),
+            Expr::Do(ref expr) => self.check_do_expr(expr),
+            Expr::Enum(ref expr) => {
+                for &(_, ref expr) in expr.elements.iter() {
+                    self.check_expr(expr);
+                }
+            }
+            Expr::For(ref expr) => self.check_for_expr(expr),
+            Expr::If(ref expr) => {
+                self.check_expr(&expr.condition);
+                self.check_block_expr(&expr.then_block);
+                self.check_block_expr(&expr.else_block);
+            }
+            Expr::Item(..) => (),
+            Expr::Lambda(ref expr) => self.check_lambda_expr(expr),
+            Expr::Let(ref expr) => {
+                self.check_expr(&expr.definition);
+                self.declare_variables(&vec![expr.variable.clone()]);
+            }
+            Expr::LetTuple(ref expr) => {
+                self.check_expr(&expr.definition);
+                self.declare_variables(&expr.variables);
+            }
+            Expr::List(ref expr) => self.check_exprs(&expr.elements),
+            Expr::ListOp(ref expr) => self.check_list_op_expr(expr),
+            Expr::Literal(..) => (),
+            Expr::Match(ref expr) => {
+                self.check_expr(&expr.subject);
+                for arm in expr.arms.iter() {
+                    self.declare_variables(&arm.bindings);
+                    self.check_block_expr(&arm.body);
+                }
+            }
+            Expr::Optional(ref expr) => {
+                if let Some(ref value) = expr.value {
+                    self.check_expr(value);
+                }
+            }
+            Expr::Process(ref expr) => self.check_process_expr(expr),
+            Expr::Range(ref expr) => {
+                self.check_expr(&expr.start);
+                self.check_expr(&expr.end);
+            }
+            Expr::Ref(ref expr) => self.check_ref_expr(expr),
+            Expr::Select(ref expr) => {
+                for guard in expr.guards.iter() {
+                    match guard.op {
+                        SelectOp::Recv(ref channel, ref variable) => {
+                            self.check_expr(channel);
+                            if let Some(ref variable) = *variable {
+                                self.declare_variables(&vec![variable.clone()]);
+                            }
+                        }
+                        SelectOp::Send(ref channel, ref value) => {
+                            self.check_expr(channel);
+                            self.check_expr(value);
+                        }
+                    }
+                    self.check_block_expr(&guard.body);
+                }
+                if let Some(ref else_body) = expr.else_body {
+                    self.check_block_expr(else_body);
+                }
+            }
+            Expr::Struct(ref expr) => {
+                for &(_, ref expr) in expr.elements.iter() {
+                    self.check_expr(expr);
+                }
+            }
+            Expr::Tuple(ref expr) => self.check_exprs(&expr.elements),
+            Expr::Unwrap(ref expr) => self.check_expr(&expr.inner),
+            Expr::Variable(ref expr) => {
+                if expr.parent.is_empty() {
+                    self.check_not_captured(&expr.variable, &expr.identify());
+                    self.capture(&expr.variable);
+                }
+                self.check_exprs(&expr.parent);
+            }
+            Expr::Void(..) => (),
+        }
+    }
+
+    pub fn errors(&self) -> &Vec<BorrowErr> {
+        &self.errors
+    }
+
+    pub fn print_errors(&self) {
+        for err in self.errors.iter() {
+            println!("{}", err);
+        }
+    }
+
+    /// Check an expression that runs in a process of its own, alongside every
+    /// process that is still running. A mutable variable that it captures
+    /// cannot be captured by any of them.
+    fn check_process<F>(&mut self, identifier: &Identifier, check_body: F)
+        where F: FnOnce(&mut BorrowChecker)
+    {
+        self.captures.push(Variables::new());
+        self.process_bases.push(self.processes.len());
+        check_body(self);
+        self.process_bases.pop();
+        let captures = self.captures
+            .pop()
+            .expect("expected captures for the process")
+            .into_iter()
+            .filter(|capture| self.is_shared_mutable(capture))
+            .collect::<Variables>();
+        let mut races = Vec::new();
+        for process in self.processes.iter().flat_map(|processes| processes.iter()) {
+            for capture in captures.iter() {
+                if process.captures.iter().any(|other| other.identify() == capture.identify()) {
+                    races.push((capture.clone(), process.identifier.clone()));
+                }
+            }
+        }
+        for (capture, other) in races {
+            let message = format!("`{}` is mutable, and is captured by processes that run at the same time",
+                                  capture.symbolise().name());
+            let note = format!("`{}` is also captured by this process", capture.symbolise().name());
+            self.error_with_note(identifier, message, &other, note);
+        }
+        if let Some(processes) = self.processes.last_mut() {
+            processes.push(Process {
+                identifier: identifier.clone(),
+                captures: captures,
+            });
+        }
+    }
+
+    /// The expressions of a block that is a side of a `do` run in processes
+    /// of their own, which end with the side.
+    fn check_concurrent_expr(&mut self, expr: &Expr) {
+        self.processes.push(Vec::new());
+        match *expr {
+            Expr::Block(ref block_expr) => {
+                for expr in block_expr.body.iter().chain(Some(&block_expr.ret)) {
+                    if let Expr::Void(..) = *expr {
+                        continue;
+                    }
+                    self.check_process(&expr.identify(), |checker| checker.check_expr(expr));
+                }
+            }
+            _ => self.check_process(&expr.identify(), |checker| checker.check_expr(expr)),
+        }
+        self.processes.pop();
+    }
+
+    /// Record the use of a variable by every enclosing process that it was
+    /// declared outside of.
+    fn capture(&mut self, variable: &Variable) {
+        let depth = self.declarations.get(&variable.identify()).cloned().unwrap_or(0);
+        for captures in self.captures.iter_mut().skip(depth) {
+            if !captures.iter().any(|capture| capture.identify() == variable.identify()) {
+                captures.push(variable.clone());
+            }
+        }
+    }
+
+    /// Check that a variable is not used while a process that has captured
+    /// it is still running. The processes that run alongside an enclosing
+    /// process are checked against its captures instead.
+    fn check_not_captured(&mut self, variable: &Variable, identifier: &Identifier) {
+        let base = self.process_bases.last().cloned().unwrap_or(0);
+        let process = self.processes
+            .iter()
+            .skip(base)
+            .flat_map(|processes| processes.iter())
+            .find(|process| process.captures.iter().any(|capture| capture.identify() == variable.identify()))
+            .map(|process| process.identifier.clone());
+        if let Some(process) = process {
+            let message = format!("`{}` is mutable, and is used while a process that captured it is running",
+                                  variable.symbolise().name());
+            let note = format!("`{}` is captured by this process", variable.symbolise().name());
+            self.error_with_note(identifier, message, &process, note);
+        }
+    }
+
+    fn declare_variables(&mut self, variables: &Variables) {
+        for variable in variables.iter() {
+            self.declarations.insert(variable.identify(), self.captures.len());
+        }
+    }
+
+    /// Check whether a variable cannot be shared by processes, because it is
+    /// mutable, or a `mut ref`. A channel can always be shared.
+    fn is_shared_mutable(&self, variable: &Variable) -> bool {
+        match variable.ty {
+            Type::Channel(..) => false,
+            Type::Ref(ref ty) => ty.is_mut || variable.is_mut,
+            _ => variable.is_mut,
+        }
+    }
+
+    /// Check that the value of an expression can be mutated. A variable must
+    /// be mutable, an element of a struct can be mutated if the struct can be,
+    /// and a referenced value can be mutated if the reference is a `mut ref`.
+    fn check_mutable(&mut self, expr: &Expr, action: &str) {
+        match *expr {
+            Expr::Variable(ref variable_expr) => {
+                match variable_expr.parent.first() {
+                    Some(&Expr::Variable(ref parent_expr)) if self.is_ref(&parent_expr.variable.ty) => {
+                        self.check_mutable_ref(&parent_expr.variable, &expr.identify(), action)
+                    }
+                    Some(parent) => self.check_mutable(parent, action),
+                    None if !variable_expr.variable.is_mut => {
+                        let message = format!("cannot {} immutable variable `{}`",
+                                              action,
+                                              variable_expr.variable.symbolise().name());
+                        self.error(&expr.identify(), message);
+                    }
+                    None => (),
+                }
+            }
+            Expr::Deref(ref deref_expr) => {
+                if let Expr::Variable(ref inner_expr) = deref_expr.inner {
+                    self.check_mutable_ref(&inner_expr.variable, &expr.identify(), action);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Check that a variable holds a `mut ref`, through which its value can be
+    /// mutated.
+    fn check_mutable_ref(&mut self, variable: &Variable, identifier: &Identifier, action: &str) {
+        if let Type::Ref(ref ty) = variable.ty {
+            if !ty.is_mut {
+                let message = format!("cannot {} a value through `{}`, which is a `ref` and not a `mut ref`",
+                                      action,
+                                      variable.symbolise().name());
+                self.error(identifier, message);
+            }
+        }
+    }
+
+    /// Check that the variable that owns the value of an expression is not
+    /// referenced, since the value would change underneath the reference.
+    fn check_unborrowed(&mut self, expr: &Expr, action: &str) {
+        let owner = match self.owner(expr) {
+            Some(owner) => owner,
+            None => return,
+        };
+        let is_borrowed = self.borrows
+            .iter()
+            .flat_map(|borrows| borrows.iter())
+            .any(|borrow| borrow.owner == owner.identify());
+        if is_borrowed {
+            let message = format!("cannot {} `{}`, because it is referenced", action, owner.symbolise().name());
+            self.error(&expr.identify(), message);
+        }
+    }
+
+    fn is_ref(&self, ty: &Type) -> bool {
+        if let Type::Ref(..) = *ty { true } else { false }
+    }
+
+    /// Find the variable that owns the value of an expression. An element of
+    /// a struct is owned by the variable that owns the struct, unless it is
+    /// reached through a reference.
+    fn owner(&self, expr: &Expr) -> Option<Variable> {
+        match *expr {
+            Expr::Variable(ref variable_expr) => {
+                match variable_expr.parent.first() {
+                    Some(&Expr::Variable(ref parent_expr)) if self.is_ref(&parent_expr.variable.ty) => None,
+                    Some(parent) => self.owner(parent),
+                    None => Some(variable_expr.variable.clone()),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn error(&mut self, identifier: &Identifier, message: String) {
+        let span = self.context.span_table.get(identifier).cloned();
+        self.errors.push(BorrowErr::new(span, message));
+    }
+
+    fn error_with_note(&mut self, identifier: &Identifier, message: String, note_identifier: &Identifier, note: String) {
+        let span = self.context.span_table.get(identifier).cloned();
+        let note_span = self.context.span_table.get(note_identifier).cloned();
+        self.errors.push(BorrowErr::new_with_notes(span, message, vec![(note_span, note)]));
+    }
+}
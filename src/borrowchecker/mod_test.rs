@@ -0,0 +1,230 @@
+use super::*;
+use super::super::air::*;
+use super::super::identifier::{Identifier, Symbol};
+use super::super::lexer::Span;
+
+fn block(body: Exprs) -> BlockExpr {
+    BlockExpr::new(Identifier::id(),
+                   body,
+                   VoidExpr::new(Identifier::id()).into(),
+                   FunctionTable::new(),
+                   ModuleTable::new(),
+                   TypeTable::new())
+}
+
+fn int(value: i64) -> Expr {
+    LiteralExpr::new(Identifier::id(), Literal::I64(value)).into()
+}
+
+fn variable(variable: &Variable) -> Expr {
+    VariableExpr::new(Identifier::id(), variable.clone()).into()
+}
+
+fn borrow_checker(context: Context, body: Exprs) -> BorrowChecker {
+    let mut borrow_checker = BorrowChecker::new(context);
+    borrow_checker.check_function(&Function::new(
+        Symbol::new("f"),
+        Variables::new(),
+        PrimitiveType::Void.into(),
+        Some(block(body).into()),
+    ));
+    borrow_checker
+}
+
+fn borrow_errors(body: Exprs) -> usize {
+    borrow_checker(Context::new(), body).errors().len()
+}
+
+#[test]
+fn check_mut_ref_exclusive() {
+    let x = Variable::new_mut(Symbol::new("x"), PrimitiveType::I64.into());
+    let a = Variable::new(Symbol::new("a"), RefType::new_mut(PrimitiveType::I64.into()).into());
+    let b = Variable::new(Symbol::new("b"), RefType::new(PrimitiveType::I64.into()).into());
+    let g = Function::new(
+        Symbol::new("g"),
+        vec![
+            Variable::new(Symbol::new("lhs"), RefType::new(PrimitiveType::I64.into()).into()),
+            Variable::new(Symbol::new("rhs"), RefType::new(PrimitiveType::I64.into()).into()),
+        ],
+        PrimitiveType::Void.into(),
+        None,
+    );
+    let ref_x = || -> Expr { RefExpr::new(Identifier::id(), variable(&x)).into() };
+    let mut_ref_x = || -> Expr { RefExpr::new_mut(Identifier::id(), variable(&x)).into() };
+    let let_x = || -> Expr { LetExpr::new(Identifier::id(), x.clone(), int(1)).into() };
+    let call_g = |lhs: Expr, rhs: Expr| -> Expr {
+        CallExpr::new(Identifier::id(), ItemExpr::new(Identifier::id(), g.clone().into()).into(), vec![lhs, rhs]).into()
+    };
+
+    // a `mut ref` excludes every other reference in its block
+    assert_eq!(borrow_errors(vec![
+        let_x(),
+        LetExpr::new(Identifier::id(), a.clone(), mut_ref_x()).into(),
+        LetExpr::new(Identifier::id(), b.clone(), ref_x()).into(),
+    ]), 1);
+    assert_eq!(borrow_errors(vec![
+        let_x(),
+        LetExpr::new(Identifier::id(), b.clone(), ref_x()).into(),
+        LetExpr::new(Identifier::id(), a.clone(), mut_ref_x()).into(),
+    ]), 1);
+    assert_eq!(borrow_errors(vec![
+        let_x(),
+        LetExpr::new(Identifier::id(), b.clone(), ref_x()).into(),
+        LetExpr::new(Identifier::id(), b.clone(), ref_x()).into(),
+    ]), 0);
+
+    // references in the arguments of a call end with the call
+    assert_eq!(borrow_errors(vec![let_x(), call_g(ref_x(), ref_x())]), 0);
+    assert_eq!(borrow_errors(vec![let_x(), call_g(mut_ref_x(), ref_x())]), 1);
+    assert_eq!(borrow_errors(vec![let_x(), call_g(mut_ref_x(), int(1)), call_g(mut_ref_x(), int(1))]), 0);
+
+    // references in a nested block end with the block
+    assert_eq!(borrow_errors(vec![
+        let_x(),
+        block(vec![LetExpr::new(Identifier::id(), a.clone(), mut_ref_x()).into()]).into(),
+        LetExpr::new(Identifier::id(), b.clone(), ref_x()).into(),
+    ]), 0);
+
+    // a referenced variable cannot be assigned to until the reference ends
+    let assign_x = || -> Expr { AssignExpr::new(Identifier::id(), variable(&x), int(5)).into() };
+    assert_eq!(borrow_errors(vec![
+        let_x(),
+        LetExpr::new(Identifier::id(), a.clone(), mut_ref_x()).into(),
+        assign_x(),
+    ]), 1);
+    assert_eq!(borrow_errors(vec![
+        let_x(),
+        LetExpr::new(Identifier::id(), b.clone(), ref_x()).into(),
+        assign_x(),
+    ]), 1);
+    assert_eq!(borrow_errors(vec![
+        let_x(),
+        block(vec![LetExpr::new(Identifier::id(), a.clone(), mut_ref_x()).into()]).into(),
+        assign_x(),
+    ]), 0);
+}
+
+#[test]
+fn check_mutation() {
+    let x = Variable::new(Symbol::new("x"), PrimitiveType::I64.into());
+    let y = Variable::new_mut(Symbol::new("y"), PrimitiveType::I64.into());
+    let r = Variable::new(Symbol::new("r"), RefType::new(PrimitiveType::I64.into()).into());
+    let m = Variable::new(Symbol::new("m"), RefType::new_mut(PrimitiveType::I64.into()).into());
+    let assign = |lhs: Expr| -> Expr { AssignExpr::new(Identifier::id(), lhs, int(2)).into() };
+    let deref = |variable: &Variable| -> Expr {
+        DerefExpr::new(Identifier::id(), VariableExpr::new(Identifier::id(), variable.clone()).into()).into()
+    };
+
+    // only a mutable variable can be assigned to
+    assert_eq!(borrow_errors(vec![assign(variable(&x))]), 1);
+    assert_eq!(borrow_errors(vec![assign(variable(&y))]), 0);
+
+    // only a `mut ref` can be assigned through
+    assert_eq!(borrow_errors(vec![assign(deref(&r))]), 1);
+    assert_eq!(borrow_errors(vec![assign(deref(&m))]), 0);
+
+    // only a mutable variable can be referenced by a `mut ref`
+    assert_eq!(borrow_errors(vec![RefExpr::new_mut(Identifier::id(), variable(&x)).into()]), 1);
+    assert_eq!(borrow_errors(vec![RefExpr::new_mut(Identifier::id(), variable(&y)).into()]), 0);
+    assert_eq!(borrow_errors(vec![RefExpr::new(Identifier::id(), variable(&x)).into()]), 0);
+}
+
+#[test]
+fn check_process_captures() {
+    let x = Variable::new(Symbol::new("x"), PrimitiveType::I64.into());
+    let y = Variable::new_mut(Symbol::new("y"), PrimitiveType::I64.into());
+    let ch = Variable::new_mut(Symbol::new("ch"), ChannelType::new(PrimitiveType::I64.into()).into());
+    let i = Variable::new(Symbol::new("i"), PrimitiveType::I64.into());
+    let let_vars = || -> Vec<Expr> {
+        vec![
+            LetExpr::new(Identifier::id(), x.clone(), int(1)).into(),
+            LetExpr::new(Identifier::id(), y.clone(), int(1)).into(),
+            LetExpr::new(Identifier::id(), ch.clone(), ChannelExpr::new(Identifier::id(), None).into()).into(),
+        ]
+    };
+    let process = |captured: &Variable| -> Expr {
+        ProcessExpr::new(Identifier::id(), block(vec![variable(captured)])).into()
+    };
+    let with_vars = |exprs: Vec<Expr>| -> Vec<Expr> { let_vars().into_iter().chain(exprs).collect() };
+
+    // only immutable variables and channels can be shared by processes
+    assert_eq!(borrow_errors(with_vars(vec![process(&x), process(&x)])), 0);
+    assert_eq!(borrow_errors(with_vars(vec![process(&ch), process(&ch)])), 0);
+    assert_eq!(borrow_errors(with_vars(vec![process(&y), process(&y)])), 1);
+    assert_eq!(borrow_errors(with_vars(vec![process(&y), block(vec![process(&y)]).into()])), 1);
+
+    // a mutable variable can be moved into a single process at a time
+    assert_eq!(borrow_errors(with_vars(vec![process(&y)])), 0);
+    assert_eq!(borrow_errors(with_vars(vec![block(vec![process(&y)]).into(), block(vec![process(&y)]).into()])), 0);
+    assert_eq!(borrow_errors(with_vars(vec![
+        DoExpr::new(Identifier::id(), block(vec![variable(&y)]).into(), variable(&y)).into(),
+    ])), 0);
+    assert_eq!(borrow_errors(with_vars(vec![
+        DoExpr::new(Identifier::id(), block(vec![variable(&y), variable(&y)]).into(), int(0)).into(),
+    ])), 1);
+
+    // the enclosing block cannot use a moved variable until the process is
+    // joined at the end of the block
+    let assign_y = || -> Expr { AssignExpr::new(Identifier::id(), variable(&y), int(3)).into() };
+    let assign_process = ProcessExpr::new(Identifier::id(),
+                                          block(vec![AssignExpr::new(Identifier::id(), variable(&y), int(2)).into()]));
+    assert_eq!(borrow_errors(with_vars(vec![assign_process.clone().into(), assign_y()])), 1);
+    assert_eq!(borrow_errors(with_vars(vec![process(&y), block(vec![variable(&y)]).into()])), 1);
+    assert_eq!(borrow_errors(with_vars(vec![block(vec![assign_process.into()]).into(), assign_y()])), 0);
+    assert_eq!(borrow_errors(with_vars(vec![process(&x), variable(&x), process(&ch), variable(&ch)])), 0);
+
+    // every iteration of a `forall` runs at the same time
+    let forall = |captured: &Variable| -> Expr {
+        ForExpr::new(Identifier::id(),
+                     true,
+                     vec![i.clone()],
+                     RangeExpr::new(Identifier::id(), int(0), int(8)).into(),
+                     block(vec![variable(captured)]))
+            .into()
+    };
+    assert_eq!(borrow_errors(with_vars(vec![forall(&i), forall(&x), forall(&ch)])), 0);
+    assert_eq!(borrow_errors(with_vars(vec![forall(&y)])), 1);
+}
+
+#[test]
+fn check_process_races_are_located() {
+    let y = Variable::new_mut(Symbol::new("y"), PrimitiveType::I64.into());
+    let first = Identifier::id();
+    let second = Identifier::id();
+    let mut context = Context::new();
+    context.span_table.insert(first.clone(), Span::new("main.arvo", 3, 5, 3, 20));
+    context.span_table.insert(second.clone(), Span::new("main.arvo", 4, 5, 4, 20));
+
+    let borrow_checker = borrow_checker(context, vec![
+        LetExpr::new(Identifier::id(), y.clone(), int(1)).into(),
+        ProcessExpr::new(first, block(vec![variable(&y)])).into(),
+        ProcessExpr::new(second, block(vec![variable(&y)])).into(),
+    ]);
+    assert_eq!(borrow_checker.errors().len(), 1);
+    assert_eq!(
+        format!("{}", borrow_checker.errors()[0]),
+        "main.arvo:4:5: `y` is mutable, and is captured by processes that run at the same time\n\
+         main.arvo:3:5: note: `y` is also captured by this process"
+    );
+}
+
+#[test]
+fn check_lambda_captures() {
+    let x = Variable::new(Symbol::new("x"), PrimitiveType::I64.into());
+    let y = Variable::new_mut(Symbol::new("y"), PrimitiveType::I64.into());
+    let z = Variable::new_mut(Symbol::new("z"), PrimitiveType::I64.into());
+    let lambda = |body: Expr| -> Expr { LambdaExpr::new(Identifier::id(), vec![z.clone()], body).into() };
+
+    // a lambda can capture an immutable variable, and mutate its own formals
+    assert_eq!(borrow_errors(vec![
+        LetExpr::new(Identifier::id(), x.clone(), int(1)).into(),
+        lambda(variable(&x)),
+        lambda(AssignExpr::new(Identifier::id(), variable(&z), int(2)).into()),
+    ]), 0);
+
+    // but it cannot capture a mutable variable
+    assert_eq!(borrow_errors(vec![
+        LetExpr::new(Identifier::id(), y.clone(), int(1)).into(),
+        lambda(variable(&y)),
+    ]), 1);
+}
@@ -1,19 +1,20 @@
 extern crate llvm_sys;
 
+use self::llvm_sys::{LLVMIntPredicate, LLVMRealPredicate, LLVMTypeKind};
 use self::llvm_sys::core::*;
 use self::llvm_sys::prelude::*;
 use self::llvm_sys::target::*;
 
-use super::super::identifier::{Identifier, Identify, Name, Symbol, Symbolise};
+use super::super::identifier::{Identifier, Identify, Name, Symbolise};
 use super::super::noir::*;
 use super::super::noir::context::*;
 use super::super::noir::prelude::*;
-use super::super::noir::runtime::*;
 
-use std::collections::HashMap;
-use std::env;
+use std::cmp;
+use std::collections::{HashMap, HashSet};
 use std::ffi::{CStr, CString};
 use std::io::Write;
+use std::mem;
 use std::process::{Command, Stdio};
 use std::ptr;
 
@@ -24,9 +25,19 @@ pub struct LLVMBuilder {
     llvm_module: LLVMModuleRef,
     llvm_builder: LLVMBuilderRef,
     llvm_values: HashMap<Identifier, LLVMValueRef>,
+    /// The stack allocation of every variable that lives in memory.
+    llvm_variables: HashMap<Identifier, LLVMValueRef>,
     llvm_types: HashMap<Identifier, LLVMTypeRef>,
+    llvm_element_positions: HashMap<Identifier, u32>,
     llvm_current_function: Option<LLVMValueRef>,
-    llvm_current_block: Option<LLVMBasicBlockRef>,
+    /// The loops that enclose the expression being built, innermost last.
+    llvm_loops: Vec<LLVMLoop>,
+    /// The processes that have been started, and not yet joined, by every
+    /// block that encloses the expression being built.
+    llvm_processes: Vec<Vec<LLVMValueRef>>,
+    /// The variables that are assigned to, or referenced, by the functions
+    /// that have been built.
+    written_variables: HashSet<Identifier>,
 }
 
 impl LLVMBuilder {
@@ -45,49 +56,394 @@ impl LLVMBuilder {
                 llvm_module: llvm_module,
                 llvm_builder: LLVMCreateBuilderInContext(llvm_context),
                 llvm_values: HashMap::new(),
+                llvm_variables: HashMap::new(),
                 llvm_types: HashMap::new(),
+                llvm_element_positions: HashMap::new(),
                 llvm_current_function: None,
-                llvm_current_block: None,
+                llvm_loops: Vec::new(),
+                llvm_processes: Vec::new(),
+                written_variables: HashSet::new(),
             }
         }
     }
 
-    pub fn codegen_block_expr<S>(&mut self,
-                                 block_name: S,
-                                 block_expr: &BlockExpr)
-                                 -> Option<LLVMValueRef>
-        where S: Into<String>
-    {
-        let llvm_name = llvm_string(block_name);
-        let block = unsafe {
-            LLVMAppendBasicBlockInContext(self.llvm_context, self.llvm_current_function.expect("expected Some(LLVMValueRef) for None"), llvm_name.as_ptr())
-        };
-        unsafe {
-            LLVMPositionBuilderAtEnd(self.llvm_builder, block);
-        };
+    pub fn codegen_assign_expr(&mut self, assign_expr: &AssignExpr) {
+        let llvm_rhs = self.codegen_expr(&assign_expr.rhs).expect("expected a value");
+        self.codegen_store(&assign_expr.lhs, llvm_rhs);
+    }
 
-        self.codegen_exprs(&block_expr.prelude);
-        self.codegen_exprs(&block_expr.body);
-        self.codegen_exprs(&block_expr.epilogue);
+    /// A nested block has no control flow of its own, so it is built in the
+    /// current basic block.
+    pub fn codegen_block_expr(&mut self, block_expr: &BlockExpr) -> Option<LLVMValueRef> {
+        self.codegen_block_body(block_expr)
+    }
 
-        // TODO: codegen block expression return
+    /// Build the expressions of a block in the current basic block. The value
+    /// of a block is evaluated before its epilogue, so that the epilogue can
+    /// join processes that were started by the value. Nothing is built after
+    /// an expression that leaves the block, such as a `break`.
+    ///
+    /// # Return
+    /// The value of the block, or `None` if the block has no value.
+    pub fn codegen_block_body(&mut self, block_expr: &BlockExpr) -> Option<LLVMValueRef> {
+        self.llvm_processes.push(Vec::new());
+        let mut llvm_ret = None;
+        for expr in block_expr.prelude.iter().chain(block_expr.body.iter()).chain(Some(&block_expr.ret)) {
+            if self.llvm_is_terminated() {
+                break;
+            }
+            llvm_ret = self.codegen_expr(expr);
+        }
+        if self.llvm_is_terminated() {
+            llvm_ret = None;
+        } else {
+            self.codegen_exprs(&block_expr.epilogue);
+        }
+        self.llvm_processes.pop();
+        llvm_ret
+    }
 
-        None
+    /// A `break` joins the processes that have been started within the
+    /// innermost loop, and then branches to the end of the loop.
+    pub fn codegen_break_expr(&mut self, _: &BreakExpr) {
+        let llvm_loop = self.llvm_loops.last().cloned().expect("`break` outside of a loop");
+        self.codegen_process_joins(llvm_loop.processes);
+        unsafe { LLVMBuildBr(self.llvm_builder, llvm_loop.llvm_break_block) };
     }
 
+    /// A closure is called through its function, which is given the
+    /// environment of the closure before the arguments. A call to a binary
+    /// or assignment operator of the prelude is built as instructions, so
+    /// that its runtime errors are located, and the prelude library is only
+    /// called for any other function.
     pub fn codegen_call_expr(&mut self, call_expr: &CallExpr) -> LLVMValueRef {
+        if let Expr::Item(ref item_expr) = call_expr.target {
+            if let Item::Function(ref function) = item_expr.item {
+                if let Some((op, overflow, ty)) = PreludeOp::of(function) {
+                    let llvm_lhs = self.codegen_expr(&call_expr.arguments[0]).expect("expected a value");
+                    let llvm_rhs = self.codegen_expr(&call_expr.arguments[1]).expect("expected a value");
+                    return self.codegen_prelude_op(call_expr, function, op, overflow, &ty, llvm_lhs, llvm_rhs);
+                }
+                if let Some((op, ty)) = PreludeOp::of_assign(function) {
+                    return self.codegen_prelude_assign_op(call_expr, function, op, &ty);
+                }
+            }
+        }
         let llvm_name = llvm_string("");
-        let llvm_target = self.codegen_expr(&call_expr.target);
+        let mut llvm_target = self.codegen_expr(&call_expr.target).expect("expected a function to call");
         let mut llvm_arguments = self.codegen_exprs(&call_expr.arguments).into_iter().map(|arg| arg.unwrap()).collect::<Vec<_>>();
         unsafe {
+            if llvm_is_struct(llvm_target) {
+                let llvm_environment = LLVMBuildExtractValue(self.llvm_builder, llvm_target, 1, llvm_name.as_ptr());
+                llvm_target = LLVMBuildExtractValue(self.llvm_builder, llvm_target, 0, llvm_name.as_ptr());
+                llvm_arguments.insert(0, llvm_environment);
+            }
             LLVMBuildCall(self.llvm_builder,
-                          llvm_target.unwrap(),
+                          llvm_target,
                           llvm_arguments.as_mut_ptr(),
                           llvm_arguments.len() as u32,
                           llvm_name.as_ptr())
         }
     }
 
+    /// An unbounded channel is given a negative capacity.
+    pub fn codegen_channel_expr(&mut self, channel_expr: &ChannelExpr) -> LLVMValueRef {
+        let llvm_element_type = self.codegen_type(&channel_expr.ty);
+        let llvm_size = unsafe { LLVMSizeOf(llvm_element_type) };
+        let llvm_capacity = match channel_expr.capacity {
+            Some(ref capacity) => self.codegen_expr(capacity).expect("expected a capacity"),
+            None => unsafe { LLVMConstInt(LLVMInt64Type(), -1i64 as u64, 1) },
+        };
+        let channel_new_fn = self.context.runtime.channel_new_fn.clone();
+        self.codegen_runtime_call(&channel_new_fn, vec![llvm_size, llvm_capacity])
+    }
+
+    /// Values are passed to, and from, the runtime through a pointer to a
+    /// stack allocation.
+    pub fn codegen_channel_op_expr(&mut self, channel_op_expr: &ChannelOpExpr) -> Option<LLVMValueRef> {
+        match channel_op_expr.op {
+            ChannelOp::Close(ref channel) => {
+                let llvm_channel = self.codegen_expr(channel).expect("expected a channel");
+                let channel_close_fn = self.context.runtime.channel_close_fn.clone();
+                self.codegen_runtime_call(&channel_close_fn, vec![llvm_channel]);
+                None
+            }
+            ChannelOp::Recv(ref channel) => {
+                let llvm_channel = self.codegen_expr(channel).expect("expected a channel");
+                let llvm_value_type = self.codegen_type(&channel_op_expr.ty);
+                let llvm_value_ptr = self.codegen_alloca(llvm_value_type);
+                let llvm_byte_ptr = self.codegen_byte_ptr(llvm_value_ptr);
+                let channel_recv_fn = self.context.runtime.channel_recv_fn.clone();
+                self.codegen_runtime_call(&channel_recv_fn, vec![llvm_channel, llvm_byte_ptr]);
+                let llvm_name = llvm_string("");
+                Some(unsafe { LLVMBuildLoad(self.llvm_builder, llvm_value_ptr, llvm_name.as_ptr()) })
+            }
+            ChannelOp::Send(ref channel, ref value) => {
+                let llvm_channel = self.codegen_expr(channel).expect("expected a channel");
+                let llvm_value = self.codegen_expr(value).expect("expected a value");
+                let llvm_value_ptr = self.codegen_alloca(unsafe { LLVMTypeOf(llvm_value) });
+                unsafe { LLVMBuildStore(self.llvm_builder, llvm_value, llvm_value_ptr) };
+                let llvm_byte_ptr = self.codegen_byte_ptr(llvm_value_ptr);
+                let channel_send_fn = self.context.runtime.channel_send_fn.clone();
+                self.codegen_runtime_call(&channel_send_fn, vec![llvm_channel, llvm_byte_ptr]);
+                None
+            }
+        }
+    }
+
+    /// Channels are managed by the runtime, and so every channel is a
+    /// pointer.
+    pub fn codegen_channel_type(&mut self, _: &ChannelType) -> LLVMTypeRef {
+        unsafe { LLVMPointerType(LLVMInt8Type(), 0) }
+    }
+
+    /// A closure is a pointer to its function, and a pointer to its
+    /// environment.
+    pub fn codegen_closure_type(&mut self, closure_type: &ClosureType) -> LLVMTypeRef {
+        let mut llvm_element_types = vec![
+            self.codegen_lambda_type(&closure_type.lambda_type()),
+            unsafe { LLVMPointerType(LLVMInt8Type(), 0) },
+        ];
+        unsafe {
+            LLVMStructType(llvm_element_types.as_mut_ptr(),
+                           llvm_element_types.len() as u32,
+                           0)
+        }
+    }
+
+    /// A `continue` joins the processes that have been started within the
+    /// innermost loop, and then branches to the next iteration of the loop.
+    pub fn codegen_continue_expr(&mut self, _: &ContinueExpr) {
+        let llvm_loop = self.llvm_loops.last().cloned().expect("`continue` outside of a loop");
+        self.codegen_process_joins(llvm_loop.processes);
+        unsafe { LLVMBuildBr(self.llvm_builder, llvm_loop.llvm_continue_block) };
+    }
+
+    pub fn codegen_deref_expr(&mut self, deref_expr: &DerefExpr) -> LLVMValueRef {
+        let llvm_inner = self.codegen_expr(&deref_expr.inner).expect("expected a reference");
+        let llvm_name = llvm_string("");
+        unsafe { LLVMBuildLoad(self.llvm_builder, llvm_inner, llvm_name.as_ptr()) }
+    }
+
+    pub fn codegen_enum_expr(&mut self, enum_expr: &EnumExpr) -> LLVMValueRef {
+        let elements = EnumType::variant_elements(&enum_expr.variant);
+        let mut llvm_elements = Vec::new();
+        for &(ref element, ref expr) in enum_expr.elements.iter() {
+            let llvm_element = self.codegen_expr(expr).expect("expected a value");
+            let llvm_position = elements.iter()
+                .position(|other| other.identify() == element.identify())
+                .expect("use of undefined variant element");
+            llvm_elements.push((llvm_position as u32, llvm_element));
+        }
+        self.codegen_enum_value(&enum_expr.ty, &enum_expr.variant, llvm_elements)
+    }
+
+    /// Build a value of an enum from a variant, and the position and value of
+    /// each of the elements of the variant.
+    fn codegen_enum_value(&mut self,
+                          enum_type: &EnumType,
+                          variant: &Variable,
+                          llvm_elements: Vec<(u32, LLVMValueRef)>)
+                          -> LLVMValueRef {
+        let llvm_enum_type = self.codegen_enum_type(enum_type);
+        let llvm_variant_type = self.codegen_variant_type(variant);
+        let llvm_tag = self.llvm_element_position(variant);
+        let mut llvm_variant = unsafe { LLVMGetUndef(llvm_variant_type) };
+        for (llvm_position, llvm_element) in llvm_elements {
+            let llvm_name = llvm_string("");
+            llvm_variant = unsafe {
+                LLVMBuildInsertValue(self.llvm_builder,
+                                     llvm_variant,
+                                     llvm_element,
+                                     llvm_position,
+                                     llvm_name.as_ptr())
+            };
+        }
+        unsafe {
+            let llvm_name = llvm_string("");
+            let llvm_enum = self.codegen_alloca(llvm_enum_type);
+            let llvm_tag_ptr = LLVMBuildStructGEP(self.llvm_builder, llvm_enum, 0, llvm_name.as_ptr());
+            LLVMBuildStore(self.llvm_builder,
+                           LLVMConstInt(LLVMInt32Type(), llvm_tag as u64, 0),
+                           llvm_tag_ptr);
+            let llvm_variant_ptr = self.codegen_variant_ptr(llvm_enum, llvm_variant_type);
+            LLVMBuildStore(self.llvm_builder, llvm_variant, llvm_variant_ptr);
+            LLVMBuildLoad(self.llvm_builder, llvm_enum, llvm_name.as_ptr())
+        }
+    }
+
+    /// Build a named LLVM struct type for a tagged union. The tag is the
+    /// position of the variant, and it is followed by enough space to hold
+    /// the elements of the largest variant.
+    pub fn codegen_enum_type(&mut self, enum_type: &EnumType) -> LLVMTypeRef {
+        if let Some(llvm_enum_type) = self.llvm_types.get(&enum_type.identify()) {
+            return llvm_enum_type.clone();
+        }
+        unsafe {
+            let llvm_name = llvm_string(enum_type.symbolise().name());
+            let llvm_enum_type = LLVMStructCreateNamed(LLVMGetModuleContext(self.llvm_module),
+                                                       llvm_name.as_ptr());
+            self.llvm_types.insert(enum_type.identify(), llvm_enum_type);
+            let mut llvm_size = 0;
+            for (i, variant) in enum_type.variants.elements.iter().enumerate() {
+                self.llvm_element_positions.insert(variant.identify(), i as u32);
+                let llvm_variant_type = self.codegen_variant_type(variant);
+                llvm_size = cmp::max(llvm_size,
+                                     LLVMABISizeOfType(LLVMGetModuleDataLayout(self.llvm_module),
+                                                       llvm_variant_type));
+            }
+            // the space for the variants is made of words, so that it is
+            // aligned for any of the variants
+            let mut llvm_elements = vec![LLVMInt32Type(), LLVMArrayType(LLVMInt64Type(), ((llvm_size + 7) / 8) as u32)];
+            LLVMStructSetBody(llvm_enum_type,
+                              llvm_elements.as_mut_ptr(),
+                              llvm_elements.len() as u32,
+                              0);
+            llvm_enum_type
+        }
+    }
+
+    /// Allocate the environment of a process, which holds the values of
+    /// `variables` in order.
+    ///
+    /// # Return
+    /// The environment, and its size in bytes.
+    fn codegen_environment(&mut self, variables: &Variables) -> (LLVMValueRef, LLVMValueRef) {
+        let llvm_environment_type = self.codegen_environment_type(variables);
+        let llvm_environment = self.codegen_alloca(llvm_environment_type);
+        (llvm_environment, unsafe { LLVMSizeOf(llvm_environment_type) })
+    }
+
+    /// Store the current values of `variables` into an environment, from the
+    /// element at `offset` onwards.
+    fn codegen_environment_store(&mut self, llvm_environment: LLVMValueRef, variables: &Variables, offset: usize) {
+        for (i, variable) in variables.iter().enumerate() {
+            let llvm_value = self.codegen_variable_expr(&VariableExpr::new(variable.clone()));
+            unsafe {
+                let llvm_ptr = LLVMBuildStructGEP(self.llvm_builder,
+                                                  llvm_environment,
+                                                  (offset + i) as u32,
+                                                  llvm_string("").as_ptr());
+                LLVMBuildStore(self.llvm_builder, llvm_value, llvm_ptr);
+            }
+        }
+    }
+
+    fn codegen_environment_type(&mut self, variables: &Variables) -> LLVMTypeRef {
+        let mut llvm_element_types = variables.iter()
+            .map(|variable| self.codegen_type(&variable.ty))
+            .collect::<Vec<_>>();
+        unsafe {
+            LLVMStructType(llvm_element_types.as_mut_ptr(),
+                           llvm_element_types.len() as u32,
+                           0)
+        }
+    }
+
+    /// Build a loop over the elements of a list, over the integers of a
+    /// range, or over the values received from a channel. A range is counted
+    /// without building the list. A concurrent loop starts a process for every
+    /// iteration, and joins them all at the end of the loop.
+    pub fn codegen_for_expr(&mut self, for_expr: &ForExpr) {
+        if for_expr.is_concurrent {
+            return self.codegen_forall_expr(for_expr);
+        }
+        self.codegen_for_elements(for_expr, |builder, llvm_element| {
+            builder.codegen_bind(&for_expr.variable, llvm_element);
+            builder.codegen_block_body(&for_expr.iteration);
+        });
+    }
+
+    /// Every process of a `forall` is given a pointer to its own copy of the
+    /// environment, which the runtime makes before it returns. This lets every
+    /// iteration store its element in the same environment, after the
+    /// captures have been stored once.
+    fn codegen_forall_expr(&mut self, for_expr: &ForExpr) {
+        let mut variables = vec![for_expr.variable.clone()];
+        variables.extend(for_expr.captures.iter().cloned());
+        let llvm_iteration_fn = self.codegen_closure_function(&for_expr.iteration_function(),
+                                                              &variables,
+                                                              &for_expr.iteration);
+        let (llvm_environment, llvm_size) = self.codegen_environment(&variables);
+        self.codegen_environment_store(llvm_environment, &for_expr.captures, 1);
+        let llvm_byte_ptr = self.codegen_byte_ptr(llvm_environment);
+        let process_group_new_fn = self.context.runtime.process_group_new_fn.clone();
+        let process_group_spawn_fn = self.context.runtime.process_group_spawn_fn.clone();
+        let process_group_join_fn = self.context.runtime.process_group_join_fn.clone();
+        let llvm_group = self.codegen_runtime_call(&process_group_new_fn, vec![]);
+        self.codegen_for_elements(for_expr, |builder, llvm_element| {
+            unsafe {
+                let llvm_element_ptr = LLVMBuildStructGEP(builder.llvm_builder,
+                                                          llvm_environment,
+                                                          0,
+                                                          llvm_string("").as_ptr());
+                LLVMBuildStore(builder.llvm_builder, llvm_element, llvm_element_ptr);
+            }
+            builder.codegen_runtime_call(&process_group_spawn_fn,
+                                         vec![llvm_group, llvm_iteration_fn, llvm_byte_ptr, llvm_size]);
+        });
+        self.codegen_runtime_call(&process_group_join_fn, vec![llvm_group]);
+    }
+
+    /// Build a loop that gives every element of the iterator of a `ForExpr`
+    /// to `codegen_element`, which builds the iteration for that element.
+    fn codegen_for_elements<F>(&mut self, for_expr: &ForExpr, mut codegen_element: F)
+        where F: FnMut(&mut LLVMBuilder, LLVMValueRef)
+    {
+        let llvm_name = llvm_string(for_expr.variable.symbolise().name());
+        match (&for_expr.ty, &for_expr.iterator) {
+            (&Type::Channel(..), _) => {
+                let llvm_channel = self.codegen_expr(&for_expr.iterator).expect("expected a channel");
+                let llvm_value_type = self.codegen_type(&for_expr.variable.ty);
+                let llvm_value_ptr = self.codegen_alloca(llvm_value_type);
+                let llvm_byte_ptr = self.codegen_byte_ptr(llvm_value_ptr);
+                let channel_next_fn = self.context.runtime.channel_next_fn.clone();
+                let llvm_cond_block = self.llvm_append_block("for.cond");
+                let llvm_body_block = self.llvm_append_block("for.body");
+                let llvm_end_block = self.llvm_append_block("for.end");
+                unsafe {
+                    LLVMBuildBr(self.llvm_builder, llvm_cond_block);
+
+                    // receive until the channel is closed
+                    LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_cond_block);
+                    let llvm_has_next = self.codegen_runtime_call(&channel_next_fn, vec![llvm_channel, llvm_byte_ptr]);
+                    LLVMBuildCondBr(self.llvm_builder, llvm_has_next, llvm_body_block, llvm_end_block);
+
+                    LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_body_block);
+                    let llvm_value = LLVMBuildLoad(self.llvm_builder, llvm_value_ptr, llvm_name.as_ptr());
+                    self.llvm_loops.push(LLVMLoop::new(llvm_cond_block, llvm_end_block, self.llvm_processes.len()));
+                    codegen_element(self, llvm_value);
+                    self.llvm_loops.pop();
+                    self.codegen_br(llvm_cond_block);
+
+                    LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_end_block);
+                }
+            }
+            (_, &Expr::Range(ref range_expr)) => {
+                let llvm_start = self.codegen_expr(&range_expr.start).expect("expected an integer");
+                let llvm_end = self.codegen_expr(&range_expr.end).expect("expected an integer");
+                self.codegen_count("for", llvm_start, llvm_end, |builder, llvm_count| {
+                    unsafe { LLVMSetValueName(llvm_count, llvm_name.as_ptr()) };
+                    codegen_element(builder, llvm_count);
+                });
+            }
+            _ => {
+                let llvm_list = self.codegen_expr(&for_expr.iterator).expect("expected a list");
+                let llvm_element_type = self.codegen_type(&for_expr.variable.ty);
+                let list_len_fn = self.context.runtime.list_len_fn.clone();
+                let llvm_len = self.codegen_runtime_call(&list_len_fn, vec![llvm_list]);
+                let llvm_start = unsafe { LLVMConstInt(LLVMInt64Type(), 0, 0) };
+                self.codegen_count("for", llvm_start, llvm_len, |builder, llvm_index| {
+                    let llvm_element = builder.codegen_list_index(llvm_list, llvm_index, llvm_element_type);
+                    unsafe { LLVMSetValueName(llvm_element, llvm_name.as_ptr()) };
+                    codegen_element(builder, llvm_element);
+                });
+            }
+        }
+    }
+
+    /// Build the definition of a function. The value of its body is the value
+    /// that it returns, which must have the type that the function returns.
     pub fn codegen_function(&mut self, function: &Function) -> LLVMValueRef {
         self.add_or_get_function_profile(function);
 
@@ -116,29 +472,26 @@ impl LLVMBuilder {
                 let llvm_param = LLVMGetParam(llvm_fn, i as u32);
                 let llvm_name = CString::new(formal.identify().name.clone()).unwrap();
                 LLVMSetValueName(llvm_param, llvm_name.as_ptr());
-                self.llvm_values
-                    .insert(formal.identify().clone(), llvm_param);
             }
 
             // Build the function body definition
             if let Some(ref block_expr) = function.body {
                 let llvm_restore_point = LLVMGetInsertBlock(self.llvm_builder);
                 LLVMClearInsertionPosition(self.llvm_builder);
-
-                // Build the block
                 self.llvm_current_function = Some(llvm_fn);
-                let llvm_ret = self.codegen_block_expr("entry", block_expr);
+                self.written_variables.extend(written_variables(block_expr));
 
-                // Build the return type
-                if let Some(llvm_ret) = llvm_ret {
-                    let llvm_ret_type = LLVMTypeOf(llvm_ret);
-                    if llvm_ret_type == LLVMVoidType() {
-                        LLVMBuildRetVoid(self.llvm_builder);
-                    } else {
-                        LLVMBuildRet(self.llvm_builder, llvm_ret);
-                    }
-                } else {
-                    LLVMBuildRetVoid(self.llvm_builder);
+                // Bind the formals in the entry block, where the body begins
+                let llvm_entry_block = self.llvm_append_block("entry");
+                LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_entry_block);
+                for (i, formal) in function.formals.iter().enumerate() {
+                    self.codegen_bind(formal, LLVMGetParam(llvm_fn, i as u32));
+                }
+
+                // Build the block, whose value is returned
+                let llvm_ret = self.codegen_block_body(block_expr);
+                if !self.llvm_is_terminated() {
+                    self.codegen_ret(function, llvm_ret);
                 }
 
                 LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_restore_point);
@@ -172,10 +525,66 @@ impl LLVMBuilder {
         }
     }
 
+    /// Build the branches of an `if`, which meet in a block where the value
+    /// of the `if` is merged from the branches that reach it.
+    pub fn codegen_if_expr(&mut self, if_expr: &IfExpr) -> Option<LLVMValueRef> {
+        let llvm_condition = self.codegen_expr(&if_expr.condition).expect("expected a condition");
+        let llvm_then_block = self.llvm_append_block("if.then");
+        let llvm_else_block = self.llvm_append_block("if.else");
+        let llvm_end_block = self.llvm_append_block("if.end");
+        let mut llvm_incoming = Vec::new();
+        unsafe {
+            LLVMBuildCondBr(self.llvm_builder, llvm_condition, llvm_then_block, llvm_else_block);
+            for &(llvm_block, block_expr) in [(llvm_then_block, &if_expr.then_block),
+                                              (llvm_else_block, &if_expr.else_block)].iter() {
+                LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_block);
+                let llvm_value = self.codegen_block_body(block_expr);
+                let llvm_exit_block = LLVMGetInsertBlock(self.llvm_builder);
+                if self.codegen_br(llvm_end_block) {
+                    llvm_incoming.push((llvm_value, llvm_exit_block));
+                }
+            }
+            LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_end_block);
+        }
+        self.codegen_merge(llvm_incoming)
+    }
+
     pub fn codegen_item_expr(&mut self, item_expr: &ItemExpr) -> LLVMValueRef {
         self.llvm_values.get(&item_expr.symbolise().identify()).expect("use of undefined value").clone()
     }
 
+    /// A lambda outlives the values of its captures, and so its environment is
+    /// copied to the heap by the runtime. A lambda without captures has no
+    /// environment.
+    pub fn codegen_lambda_expr(&mut self, lambda_expr: &LambdaExpr) -> LLVMValueRef {
+        let llvm_lambda_fn = self.codegen_closure_function(&lambda_expr.function(),
+                                                           &lambda_expr.captures,
+                                                           &lambda_expr.body);
+        let llvm_environment = if lambda_expr.captures.is_empty() {
+            unsafe { LLVMConstNull(LLVMPointerType(LLVMInt8Type(), 0)) }
+        } else {
+            let (llvm_environment, llvm_size) = self.codegen_environment(&lambda_expr.captures);
+            self.codegen_environment_store(llvm_environment, &lambda_expr.captures, 0);
+            let llvm_byte_ptr = self.codegen_byte_ptr(llvm_environment);
+            let environment_new_fn = self.context.runtime.environment_new_fn.clone();
+            self.codegen_runtime_call(&environment_new_fn, vec![llvm_byte_ptr, llvm_size])
+        };
+        let llvm_closure_type = self.codegen_closure_type(&lambda_expr.closure_type());
+        unsafe {
+            let llvm_name = llvm_string("");
+            let llvm_closure = LLVMBuildInsertValue(self.llvm_builder,
+                                                    LLVMGetUndef(llvm_closure_type),
+                                                    llvm_lambda_fn,
+                                                    0,
+                                                    llvm_name.as_ptr());
+            LLVMBuildInsertValue(self.llvm_builder,
+                                 llvm_closure,
+                                 llvm_environment,
+                                 1,
+                                 llvm_name.as_ptr())
+        }
+    }
+
     pub fn codegen_lambda_type(&mut self, lambda_type: &LambdaType) -> LLVMTypeRef {
         unsafe {
             LLVMPointerType(
@@ -195,58 +604,1171 @@ impl LLVMBuilder {
         self.codegen_literal(&literal_expr.literal)
     }
 
+    /// Every `let` variable lives in a stack allocation, which its definition
+    /// is stored in.
+    pub fn codegen_let_expr(&mut self, let_expr: &LetExpr) {
+        self.codegen_variable_alloca(&let_expr.variable);
+        self.codegen_assign_expr(&let_expr.definition);
+    }
+
+    /// Bind each element of a tuple to a variable.
+    pub fn codegen_let_tuple_expr(&mut self, let_tuple_expr: &LetTupleExpr) {
+        let llvm_tuple = self.codegen_expr(&let_tuple_expr.definition).expect("expected a tuple");
+        for (i, variable) in let_tuple_expr.variables.iter().enumerate() {
+            let llvm_name = llvm_string(variable.symbolise().name());
+            let llvm_element = unsafe {
+                LLVMBuildExtractValue(self.llvm_builder, llvm_tuple, i as u32, llvm_name.as_ptr())
+            };
+            self.codegen_bind(variable, llvm_element);
+        }
+    }
+
+    pub fn codegen_list_expr(&mut self, list_expr: &ListExpr) -> LLVMValueRef {
+        let llvm_element_type = self.codegen_type(&list_expr.ty);
+        let llvm_list = self.codegen_list_new(llvm_element_type);
+        for element in list_expr.elements.iter() {
+            let llvm_element = self.codegen_expr(element).expect("expected a value");
+            self.codegen_list_push(llvm_list, llvm_element);
+        }
+        llvm_list
+    }
+
+    pub fn codegen_list_op_expr(&mut self, list_op_expr: &ListOpExpr) -> Option<LLVMValueRef> {
+        match list_op_expr.op {
+            ListOp::Index(ref list, ref index) => {
+                let llvm_list = self.codegen_expr(list).expect("expected a list");
+                let llvm_index = self.codegen_expr(index).expect("expected an index");
+                let llvm_element_type = self.codegen_type(&list_op_expr.ty);
+                Some(self.codegen_list_index(llvm_list, llvm_index, llvm_element_type))
+            }
+            ListOp::Len(ref list) => {
+                let llvm_list = self.codegen_expr(list).expect("expected a list");
+                let list_len_fn = self.context.runtime.list_len_fn.clone();
+                Some(self.codegen_runtime_call(&list_len_fn, vec![llvm_list]))
+            }
+            ListOp::Pop(ref list, ref optional_type) => {
+                let llvm_list = self.codegen_expr(list).expect("expected a list");
+                Some(self.codegen_list_pop(llvm_list, optional_type))
+            }
+            ListOp::Push(ref list, ref value) => {
+                let llvm_list = self.codegen_expr(list).expect("expected a list");
+                let llvm_value = self.codegen_expr(value).expect("expected a value");
+                self.codegen_list_push(llvm_list, llvm_value);
+                None
+            }
+            ListOp::Slice(ref list, ref start, ref end) => {
+                let llvm_list = self.codegen_expr(list).expect("expected a list");
+                let llvm_start = self.codegen_expr(start).expect("expected an index");
+                let llvm_end = self.codegen_expr(end).expect("expected an index");
+                let list_slice_fn = self.context.runtime.list_slice_fn.clone();
+                Some(self.codegen_runtime_call(&list_slice_fn, vec![llvm_list, llvm_start, llvm_end]))
+            }
+        }
+    }
+
+    /// Lists are managed by the runtime, and so every list is a pointer.
+    pub fn codegen_list_type(&mut self, _: &ListType) -> LLVMTypeRef {
+        unsafe { LLVMPointerType(LLVMInt8Type(), 0) }
+    }
+
+    pub fn codegen_match_expr(&mut self, match_expr: &MatchExpr) -> Option<LLVMValueRef> {
+        let llvm_subject = self.codegen_expr(&match_expr.subject).expect("expected an enum");
+        let llvm_enum = self.codegen_enum_ptr(llvm_subject);
+        let mut llvm_incoming = Vec::new();
+        unsafe {
+            let llvm_name = llvm_string("");
+            let llvm_tag_ptr = LLVMBuildStructGEP(self.llvm_builder, llvm_enum, 0, llvm_name.as_ptr());
+            let llvm_tag = LLVMBuildLoad(self.llvm_builder, llvm_tag_ptr, llvm_name.as_ptr());
+            let llvm_default_block = self.llvm_append_block("match.default");
+            let llvm_end_block = self.llvm_append_block("match.end");
+            let llvm_switch = LLVMBuildSwitch(self.llvm_builder,
+                                              llvm_tag,
+                                              llvm_default_block,
+                                              match_expr.arms.len() as u32);
+
+            let mut has_default = false;
+            for arm in match_expr.arms.iter() {
+                match arm.variant {
+                    Some(ref variant) => {
+                        let llvm_arm_block = self.llvm_append_block("match.arm");
+                        let llvm_tag = self.llvm_element_position(variant);
+                        LLVMAddCase(llvm_switch,
+                                    LLVMConstInt(LLVMInt32Type(), llvm_tag as u64, 0),
+                                    llvm_arm_block);
+                        LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_arm_block);
+                        let llvm_variant_type = self.codegen_variant_type(variant);
+                        let llvm_variant_ptr = self.codegen_variant_ptr(llvm_enum, llvm_variant_type);
+                        for (i, binding) in arm.bindings.iter().enumerate() {
+                            let llvm_binding_name = llvm_string(binding.symbolise().name());
+                            let llvm_binding_ptr = LLVMBuildStructGEP(self.llvm_builder,
+                                                                      llvm_variant_ptr,
+                                                                      i as u32,
+                                                                      llvm_name.as_ptr());
+                            let llvm_binding = LLVMBuildLoad(self.llvm_builder,
+                                                             llvm_binding_ptr,
+                                                             llvm_binding_name.as_ptr());
+                            self.codegen_bind(binding, llvm_binding);
+                        }
+                    }
+                    None => {
+                        // only the first arm that matches everything can
+                        // ever be reached
+                        if has_default {
+                            continue;
+                        }
+                        has_default = true;
+                        LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_default_block);
+                    }
+                }
+                let llvm_value = self.codegen_block_body(&arm.body);
+                let llvm_exit_block = LLVMGetInsertBlock(self.llvm_builder);
+                if self.codegen_br(llvm_end_block) {
+                    llvm_incoming.push((llvm_value, llvm_exit_block));
+                }
+            }
+            if !has_default {
+                // the arms are exhaustive, so no other tag can occur
+                LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_default_block);
+                LLVMBuildUnreachable(self.llvm_builder);
+            }
+            LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_end_block);
+        }
+        self.codegen_merge(llvm_incoming)
+    }
+
+    /// A process is given a pointer to its own copy of its captures, which
+    /// the runtime makes before it returns.
     pub fn codegen_process_expr(&mut self, process_expr: &ProcessExpr) -> LLVMValueRef {
         // create a function for the process
-        let process_function = process_expr.function();
-        self.codegen_function(&process_function);
+        let llvm_process_fn = self.codegen_closure_function(&process_expr.function(),
+                                                            &process_expr.captures,
+                                                            &process_expr.body);
+        let (llvm_environment, llvm_size) = self.codegen_environment(&process_expr.captures);
+        self.codegen_environment_store(llvm_environment, &process_expr.captures, 0);
+        let llvm_byte_ptr = self.codegen_byte_ptr(llvm_environment);
         // create a function call to the runtime to launch the process function
-        let process_call_expr = CallExpr::new(
-            ItemExpr::new(self.context.runtime.process_fn.clone()),
-            vec![
-                ItemExpr::new(process_function)
-            ]
-        );
-        let llvm_call_expr = self.codegen_call_expr(&process_call_expr);
+        let process_fn = self.context.runtime.process_fn.clone();
+        let llvm_process = self.codegen_runtime_call(&process_fn, vec![llvm_process_fn, llvm_byte_ptr, llvm_size]);
         // add the process to the nodes
         self.llvm_values
-            .insert(process_expr.identify(), llvm_call_expr.clone());
-        llvm_call_expr
+            .insert(process_expr.identify(), llvm_process.clone());
+        if let Some(llvm_processes) = self.llvm_processes.last_mut() {
+            llvm_processes.push(llvm_process);
+        }
+        llvm_process
+    }
+
+    /// Build the function of a process, or of a closure, which loads the
+    /// `variables` of its environment and then evaluates the `body`. The
+    /// environment is the first formal of the `function`, and any other
+    /// formals follow it.
+    fn codegen_closure_function(&mut self,
+                                function: &Function,
+                                variables: &Variables,
+                                body: &BlockExpr)
+                                -> LLVMValueRef {
+        let llvm_fn = self.add_or_get_function_profile(function);
+        let llvm_environment_type = self.codegen_environment_type(variables);
+        self.written_variables.extend(written_variables(body));
+        unsafe {
+            let llvm_restore_point = LLVMGetInsertBlock(self.llvm_builder);
+            let llvm_restore_function = self.llvm_current_function;
+            let llvm_restore_loops = mem::replace(&mut self.llvm_loops, Vec::new());
+            let llvm_restore_processes = mem::replace(&mut self.llvm_processes, Vec::new());
+            self.llvm_current_function = Some(llvm_fn);
+            let llvm_entry_block = self.llvm_append_block("entry");
+            LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_entry_block);
+
+            let llvm_environment = LLVMBuildBitCast(self.llvm_builder,
+                                                    LLVMGetParam(llvm_fn, 0),
+                                                    LLVMPointerType(llvm_environment_type, 0),
+                                                    llvm_string("").as_ptr());
+            for (i, variable) in variables.iter().enumerate() {
+                let llvm_name = llvm_string(variable.symbolise().name());
+                let llvm_ptr = LLVMBuildStructGEP(self.llvm_builder,
+                                                  llvm_environment,
+                                                  i as u32,
+                                                  llvm_string("").as_ptr());
+                let llvm_value = LLVMBuildLoad(self.llvm_builder, llvm_ptr, llvm_name.as_ptr());
+                self.codegen_bind(variable, llvm_value);
+            }
+            for (i, formal) in function.formals.iter().enumerate().skip(1) {
+                let llvm_param = LLVMGetParam(llvm_fn, i as u32);
+                let llvm_name = llvm_string(formal.symbolise().name());
+                LLVMSetValueName(llvm_param, llvm_name.as_ptr());
+                self.codegen_bind(formal, llvm_param);
+            }
+            let llvm_ret = self.codegen_block_body(body);
+            if !self.llvm_is_terminated() {
+                self.codegen_ret(function, llvm_ret);
+            }
+
+            self.llvm_current_function = llvm_restore_function;
+            self.llvm_loops = llvm_restore_loops;
+            self.llvm_processes = llvm_restore_processes;
+            LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_restore_point);
+        }
+        llvm_fn
     }
 
     pub fn codegen_process_join_expr(&mut self,
                                      process_join_expr: &ProcessJoinExpr)
                                      -> LLVMValueRef {
-        // create a call target from the process join function
-        let llvm_target = self.llvm_values
-            .get(&self.context.runtime.process_join_fn.identify())
+        // create arguments from the process being joined
+        let llvm_process = self.llvm_values
+            .get(&process_join_expr.process_expr.identify())
+            .expect("use of undefined process")
+            .clone();
+        let process_join_fn = self.context.runtime.process_join_fn.clone();
+        self.codegen_runtime_call(&process_join_fn, vec![llvm_process])
+    }
+
+    pub fn codegen_ptr_type(&mut self, ptr_type: &PtrType) -> LLVMTypeRef {
+        unsafe { LLVMPointerType(self.codegen_type(&ptr_type.inner)    , 0) }
+    }
+
+    /// Build a list that holds the integers of a range.
+    pub fn codegen_range_expr(&mut self, range_expr: &RangeExpr) -> LLVMValueRef {
+        let llvm_start = self.codegen_expr(&range_expr.start).expect("expected an integer");
+        let llvm_end = self.codegen_expr(&range_expr.end).expect("expected an integer");
+        let llvm_list = self.codegen_list_new(unsafe { LLVMTypeOf(llvm_start) });
+        self.codegen_count("range", llvm_start, llvm_end, |builder, llvm_count| {
+            builder.codegen_list_push(llvm_list, llvm_count);
+        });
+        llvm_list
+    }
+
+    /// A reference is a pointer to the place of its inner expression.
+    pub fn codegen_ref_expr(&mut self, ref_expr: &RefExpr) -> LLVMValueRef {
+        self.codegen_place(&ref_expr.inner)
+    }
+
+    pub fn codegen_ref_type(&mut self, ref_type: &RefType) -> LLVMTypeRef {
+        unsafe { LLVMPointerType(self.codegen_type(&ref_type.inner)    , 0) }
+    }
+
+    /// Store a value in the place that an assignment refers to. Elements of
+    /// a struct that is held by value are updated by inserting the value into
+    /// the struct, and storing the updated struct in the place of the parent.
+    pub fn codegen_store(&mut self, place: &Expr, llvm_value: LLVMValueRef) {
+        match *place {
+            Expr::Deref(ref deref_expr) => {
+                let llvm_ptr = self.codegen_expr(&deref_expr.inner).expect("expected a reference");
+                unsafe {
+                    LLVMBuildStore(self.llvm_builder, llvm_value, llvm_ptr);
+                }
+            }
+            Expr::Variable(ref variable_expr) => {
+                let parent = match variable_expr.parent.first() {
+                    Some(parent) => parent,
+                    None => {
+                        match self.llvm_variables.get(&variable_expr.variable.identify()) {
+                            Some(&llvm_variable) => unsafe {
+                                LLVMBuildStore(self.llvm_builder, llvm_value, llvm_variable);
+                            },
+                            None => {
+                                self.llvm_values.insert(variable_expr.variable.identify(), llvm_value);
+                            }
+                        }
+                        return;
+                    }
+                };
+                let llvm_parent = self.codegen_expr(parent).expect("expected a struct");
+                let llvm_position = self.llvm_element_position(&variable_expr.variable);
+                let llvm_name = llvm_string("");
+                unsafe {
+                    if llvm_is_pointer(llvm_parent) {
+                        let llvm_ptr = LLVMBuildStructGEP(self.llvm_builder,
+                                                          llvm_parent,
+                                                          llvm_position,
+                                                          llvm_name.as_ptr());
+                        LLVMBuildStore(self.llvm_builder, llvm_value, llvm_ptr);
+                    } else {
+                        let llvm_parent = LLVMBuildInsertValue(self.llvm_builder,
+                                                               llvm_parent,
+                                                               llvm_value,
+                                                               llvm_position,
+                                                               llvm_name.as_ptr());
+                        self.codegen_store(parent, llvm_parent);
+                    }
+                }
+            }
+            _ => panic!("error: invalid left-hand side of assignment"),
+        }
+    }
+
+    /// Every guard of a select is given a stack allocation, that the runtime
+    /// receives into or sends from. The runtime returns the index of the
+    /// guard that it completed, or -1 if no guard was ready, and the body of
+    /// each guard is a branch of a switch on that index.
+    pub fn codegen_select_expr(&mut self, select_expr: &SelectExpr) -> Option<LLVMValueRef> {
+        let select_new_fn = self.context.runtime.select_new_fn.clone();
+        let llvm_select = self.codegen_runtime_call(&select_new_fn, Vec::new());
+        let mut llvm_value_ptrs = Vec::new();
+        for guard in select_expr.guards.iter() {
+            let llvm_value_ptr = match guard.op {
+                SelectOp::Recv(ref channel, _) => {
+                    let llvm_channel = self.codegen_expr(channel).expect("expected a channel");
+                    let llvm_value_type = self.codegen_type(&guard.ty);
+                    let llvm_value_ptr = self.codegen_alloca(llvm_value_type);
+                    let llvm_byte_ptr = self.codegen_byte_ptr(llvm_value_ptr);
+                    let select_recv_fn = self.context.runtime.select_recv_fn.clone();
+                    self.codegen_runtime_call(&select_recv_fn, vec![llvm_select, llvm_channel, llvm_byte_ptr]);
+                    llvm_value_ptr
+                }
+                SelectOp::Send(ref channel, ref value) => {
+                    let llvm_channel = self.codegen_expr(channel).expect("expected a channel");
+                    let llvm_value = self.codegen_expr(value).expect("expected a value");
+                    let llvm_value_ptr = self.codegen_alloca(unsafe { LLVMTypeOf(llvm_value) });
+                    unsafe { LLVMBuildStore(self.llvm_builder, llvm_value, llvm_value_ptr) };
+                    let llvm_byte_ptr = self.codegen_byte_ptr(llvm_value_ptr);
+                    let select_send_fn = self.context.runtime.select_send_fn.clone();
+                    self.codegen_runtime_call(&select_send_fn, vec![llvm_select, llvm_channel, llvm_byte_ptr]);
+                    llvm_value_ptr
+                }
+            };
+            llvm_value_ptrs.push(llvm_value_ptr);
+        }
+
+        // a select only waits for a guard when it has no `else` body
+        let llvm_is_blocking = unsafe { LLVMConstInt(LLVMInt1Type(), select_expr.else_body.is_none() as u64, 0) };
+        let select_wait_fn = self.context.runtime.select_wait_fn.clone();
+        let llvm_index = self.codegen_runtime_call(&select_wait_fn, vec![llvm_select, llvm_is_blocking]);
+
+        let mut llvm_incoming = Vec::new();
+        unsafe {
+            let llvm_else_block = self.llvm_append_block("select.else");
+            let llvm_end_block = self.llvm_append_block("select.end");
+            let llvm_switch = LLVMBuildSwitch(self.llvm_builder,
+                                              llvm_index,
+                                              llvm_else_block,
+                                              select_expr.guards.len() as u32);
+            for (i, (guard, llvm_value_ptr)) in select_expr.guards.iter().zip(llvm_value_ptrs).enumerate() {
+                let llvm_guard_block = self.llvm_append_block("select.guard");
+                LLVMAddCase(llvm_switch, LLVMConstInt(LLVMInt64Type(), i as u64, 0), llvm_guard_block);
+                LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_guard_block);
+                if let SelectOp::Recv(_, Some(ref variable)) = guard.op {
+                    let llvm_variable_name = llvm_string(variable.symbolise().name());
+                    let llvm_value = LLVMBuildLoad(self.llvm_builder, llvm_value_ptr, llvm_variable_name.as_ptr());
+                    self.codegen_bind(variable, llvm_value);
+                }
+                let llvm_value = self.codegen_block_body(&guard.body);
+                let llvm_exit_block = LLVMGetInsertBlock(self.llvm_builder);
+                if self.codegen_br(llvm_end_block) {
+                    llvm_incoming.push((llvm_value, llvm_exit_block));
+                }
+            }
+            LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_else_block);
+            match select_expr.else_body {
+                Some(ref else_body) => {
+                    let llvm_value = self.codegen_block_body(else_body);
+                    let llvm_exit_block = LLVMGetInsertBlock(self.llvm_builder);
+                    if self.codegen_br(llvm_end_block) {
+                        llvm_incoming.push((llvm_value, llvm_exit_block));
+                    }
+                }
+                // a select without an `else` body waits until a guard is
+                // completed
+                None => {
+                    LLVMBuildUnreachable(self.llvm_builder);
+                }
+            }
+            LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_end_block);
+        }
+        self.codegen_merge(llvm_incoming)
+    }
+
+    pub fn codegen_struct_expr(&mut self, struct_expr: &StructExpr) -> LLVMValueRef {
+        let llvm_struct_type = self.codegen_struct_type(&struct_expr.ty);
+        let mut llvm_struct = unsafe { LLVMGetUndef(llvm_struct_type) };
+        for &(ref element, ref expr) in struct_expr.elements.iter() {
+            let llvm_element = self.codegen_expr(expr).expect("expected a value");
+            let llvm_position = self.llvm_element_position(element);
+            let llvm_name = llvm_string("");
+            llvm_struct = unsafe {
+                LLVMBuildInsertValue(self.llvm_builder,
+                                     llvm_struct,
+                                     llvm_element,
+                                     llvm_position,
+                                     llvm_name.as_ptr())
+            };
+        }
+        llvm_struct
+    }
+
+    /// Build a named LLVM struct type. The elements are laid out in the order
+    /// that they are declared, so the layout of a struct is stable.
+    pub fn codegen_struct_type(&mut self, struct_type: &StructType) -> LLVMTypeRef {
+        if let Some(llvm_struct_type) = self.llvm_types.get(&struct_type.identify()) {
+            return llvm_struct_type.clone();
+        }
+        unsafe {
+            let llvm_name = llvm_string(struct_type.symbolise().name());
+            let llvm_struct_type = LLVMStructCreateNamed(LLVMGetModuleContext(self.llvm_module),
+                                                         llvm_name.as_ptr());
+            // the struct is registered before its body is built, so that it
+            // can contain references to itself
+            self.llvm_types.insert(struct_type.identify(), llvm_struct_type);
+            for (i, element) in struct_type.elements.iter().enumerate() {
+                self.llvm_element_positions.insert(element.identify(), i as u32);
+            }
+            let mut llvm_elements = struct_type.elements
+                .iter()
+                .map(|element| self.codegen_type(&element.ty))
+                .collect::<Vec<_>>();
+            LLVMStructSetBody(llvm_struct_type,
+                              llvm_elements.as_mut_ptr(),
+                              llvm_elements.len() as u32,
+                              0);
+            llvm_struct_type
+        }
+    }
+
+    pub fn codegen_unwrap_expr(&mut self, unwrap_expr: &UnwrapExpr) -> LLVMValueRef {
+        let llvm_inner = self.codegen_expr(&unwrap_expr.inner).expect("expected an enum");
+        let llvm_enum = self.codegen_enum_ptr(llvm_inner);
+        let llvm_tag = self.llvm_element_position(&unwrap_expr.variant);
+        let llvm_variant_type = self.codegen_variant_type(&unwrap_expr.variant);
+        let llvm_unwrap_nil_fn = self.llvm_values
+            .get(&self.context.runtime.unwrap_nil_fn.identify())
             .unwrap()
             .clone();
-        // create arguments from the process being joined
-        let mut llvm_arguments = vec![self.llvm_values
-                                          .get(&process_join_expr.process_expr.identify())
-                                          .unwrap()
-                                          .clone()];
         unsafe {
-            // create the function call
             let llvm_name = llvm_string("");
+            let llvm_tag_ptr = LLVMBuildStructGEP(self.llvm_builder, llvm_enum, 0, llvm_name.as_ptr());
+            let llvm_is_variant = LLVMBuildICmp(self.llvm_builder,
+                                                LLVMIntPredicate::LLVMIntEQ,
+                                                LLVMBuildLoad(self.llvm_builder, llvm_tag_ptr, llvm_name.as_ptr()),
+                                                LLVMConstInt(LLVMInt32Type(), llvm_tag as u64, 0),
+                                                llvm_name.as_ptr());
+            let llvm_ok_block = self.llvm_append_block("unwrap.ok");
+            let llvm_nil_block = self.llvm_append_block("unwrap.nil");
+            LLVMBuildCondBr(self.llvm_builder, llvm_is_variant, llvm_ok_block, llvm_nil_block);
+
+            // unwrapping any other variant is a runtime error
+            LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_nil_block);
             LLVMBuildCall(self.llvm_builder,
-                          llvm_target,
+                          llvm_unwrap_nil_fn,
+                          ptr::null_mut(),
+                          0,
+                          llvm_name.as_ptr());
+            LLVMBuildUnreachable(self.llvm_builder);
+
+            LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_ok_block);
+            let llvm_variant_ptr = self.codegen_variant_ptr(llvm_enum, llvm_variant_type);
+            let llvm_value_ptr = LLVMBuildStructGEP(self.llvm_builder, llvm_variant_ptr, 0, llvm_name.as_ptr());
+            LLVMBuildLoad(self.llvm_builder, llvm_value_ptr, llvm_name.as_ptr())
+        }
+    }
+
+    pub fn codegen_variable_expr(&mut self, variable_expr: &VariableExpr) -> LLVMValueRef {
+        let parent = match variable_expr.parent.first() {
+            Some(parent) => parent,
+            None => {
+                if let Some(&llvm_variable) = self.llvm_variables.get(&variable_expr.variable.identify()) {
+                    let llvm_name = llvm_string("");
+                    return unsafe { LLVMBuildLoad(self.llvm_builder, llvm_variable, llvm_name.as_ptr()) };
+                }
+                return self.llvm_values
+                    .get(&variable_expr.variable.identify())
+                    .expect("use of undefined variable")
+                    .clone();
+            }
+        };
+        let llvm_parent = self.codegen_expr(parent).expect("expected a struct");
+        let llvm_position = self.llvm_element_position(&variable_expr.variable);
+        let llvm_name = llvm_string("");
+        unsafe {
+            if llvm_is_pointer(llvm_parent) {
+                let llvm_ptr = LLVMBuildStructGEP(self.llvm_builder,
+                                                  llvm_parent,
+                                                  llvm_position,
+                                                  llvm_name.as_ptr());
+                LLVMBuildLoad(self.llvm_builder, llvm_ptr, llvm_name.as_ptr())
+            } else {
+                LLVMBuildExtractValue(self.llvm_builder,
+                                      llvm_parent,
+                                      llvm_position,
+                                      llvm_name.as_ptr())
+            }
+        }
+    }
+
+    pub fn codegen_tuple_expr(&mut self, tuple_expr: &TupleExpr) -> LLVMValueRef {
+        let llvm_elements = self.codegen_exprs(&tuple_expr.elements)
+            .into_iter()
+            .map(|llvm_element| llvm_element.expect("expected a value"))
+            .collect::<Vec<_>>();
+        let mut llvm_element_types = llvm_elements.iter()
+            .map(|llvm_element| unsafe { LLVMTypeOf(*llvm_element) })
+            .collect::<Vec<_>>();
+        unsafe {
+            let llvm_tuple_type = LLVMStructType(llvm_element_types.as_mut_ptr(),
+                                                 llvm_element_types.len() as u32,
+                                                 0);
+            let mut llvm_tuple = LLVMGetUndef(llvm_tuple_type);
+            for (i, llvm_element) in llvm_elements.into_iter().enumerate() {
+                let llvm_name = llvm_string("");
+                llvm_tuple = LLVMBuildInsertValue(self.llvm_builder,
+                                                  llvm_tuple,
+                                                  llvm_element,
+                                                  i as u32,
+                                                  llvm_name.as_ptr());
+            }
+            llvm_tuple
+        }
+    }
+
+    /// Build an anonymous LLVM struct type for a tuple. Anonymous struct
+    /// types are equal when their elements are equal, so tuples need no
+    /// names.
+    pub fn codegen_tuple_type(&mut self, tuple_type: &TupleType) -> LLVMTypeRef {
+        let mut llvm_elements = self.codegen_types(&tuple_type.elements);
+        unsafe { LLVMStructType(llvm_elements.as_mut_ptr(), llvm_elements.len() as u32, 0) }
+    }
+
+    /// Build an anonymous LLVM struct type that holds the elements of a
+    /// variant of an enum.
+    pub fn codegen_variant_type(&mut self, variant: &Variable) -> LLVMTypeRef {
+        let mut llvm_elements = EnumType::variant_elements(variant)
+            .iter()
+            .map(|element| self.codegen_type(&element.ty))
+            .collect::<Vec<_>>();
+        unsafe { LLVMStructType(llvm_elements.as_mut_ptr(), llvm_elements.len() as u32, 0) }
+    }
+
+    /// Get a pointer to an enum. An enum is inspected in memory, so that the
+    /// elements of its variants can be read through a pointer to the variant.
+    fn codegen_enum_ptr(&mut self, llvm_enum: LLVMValueRef) -> LLVMValueRef {
+        if llvm_is_pointer(llvm_enum) {
+            return llvm_enum;
+        }
+        unsafe {
+            let llvm_enum_ptr = self.codegen_alloca(LLVMTypeOf(llvm_enum));
+            LLVMBuildStore(self.llvm_builder, llvm_enum, llvm_enum_ptr);
+            llvm_enum_ptr
+        }
+    }
+
+    /// Get a pointer to the variant that is stored in an enum.
+    fn codegen_variant_ptr(&mut self, llvm_enum: LLVMValueRef, llvm_variant_type: LLVMTypeRef) -> LLVMValueRef {
+        unsafe {
+            let llvm_name = llvm_string("");
+            let llvm_variant_ptr = LLVMBuildStructGEP(self.llvm_builder, llvm_enum, 1, llvm_name.as_ptr());
+            LLVMBuildBitCast(self.llvm_builder,
+                             llvm_variant_ptr,
+                             LLVMPointerType(llvm_variant_type, 0),
+                             llvm_name.as_ptr())
+        }
+    }
+
+    /// Build a loop that counts from `llvm_start` up to, but not including,
+    /// `llvm_end`. The body of the loop is built by `codegen_body`, which is
+    /// given the count. The count is incremented in a block of its own, which
+    /// a `continue` branches to.
+    fn codegen_count<S, F>(&mut self, block_name: S, llvm_start: LLVMValueRef, llvm_end: LLVMValueRef, mut codegen_body: F)
+        where S: Into<String>,
+              F: FnMut(&mut LLVMBuilder, LLVMValueRef)
+    {
+        let block_name = block_name.into();
+        unsafe {
+            let llvm_name = llvm_string("");
+            let llvm_entry_block = LLVMGetInsertBlock(self.llvm_builder);
+            let llvm_cond_block = self.llvm_append_block(format!("{}.cond", block_name));
+            let llvm_body_block = self.llvm_append_block(format!("{}.body", block_name));
+            let llvm_next_block = self.llvm_append_block(format!("{}.next", block_name));
+            let llvm_end_block = self.llvm_append_block(format!("{}.end", block_name));
+            LLVMBuildBr(self.llvm_builder, llvm_cond_block);
+
+            LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_cond_block);
+            let llvm_count = LLVMBuildPhi(self.llvm_builder, LLVMTypeOf(llvm_start), llvm_name.as_ptr());
+            let llvm_is_less = LLVMBuildICmp(self.llvm_builder,
+                                             LLVMIntPredicate::LLVMIntSLT,
+                                             llvm_count,
+                                             llvm_end,
+                                             llvm_name.as_ptr());
+            LLVMBuildCondBr(self.llvm_builder, llvm_is_less, llvm_body_block, llvm_end_block);
+
+            LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_body_block);
+            self.llvm_loops.push(LLVMLoop::new(llvm_next_block, llvm_end_block, self.llvm_processes.len()));
+            codegen_body(self, llvm_count);
+            self.llvm_loops.pop();
+            self.codegen_br(llvm_next_block);
+
+            LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_next_block);
+            let llvm_next = LLVMBuildAdd(self.llvm_builder,
+                                         llvm_count,
+                                         LLVMConstInt(LLVMTypeOf(llvm_start), 1, 0),
+                                         llvm_name.as_ptr());
+            LLVMBuildBr(self.llvm_builder, llvm_cond_block);
+
+            let mut llvm_values = vec![llvm_start, llvm_next];
+            let mut llvm_blocks = vec![llvm_entry_block, llvm_next_block];
+            LLVMAddIncoming(llvm_count, llvm_values.as_mut_ptr(), llvm_blocks.as_mut_ptr(), 2);
+            LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_end_block);
+        }
+    }
+
+    /// Branch to `llvm_block`, unless the current block has already been
+    /// left by a `break` or a `continue`.
+    ///
+    /// # Return
+    /// Whether the current block reaches `llvm_block`.
+    fn codegen_br(&mut self, llvm_block: LLVMBasicBlockRef) -> bool {
+        if self.llvm_is_terminated() {
+            return false;
+        }
+        unsafe { LLVMBuildBr(self.llvm_builder, llvm_block) };
+        true
+    }
+
+    /// Cast a pointer into the `i8*` that the runtime takes for a value of
+    /// any type.
+    fn codegen_byte_ptr(&mut self, llvm_ptr: LLVMValueRef) -> LLVMValueRef {
+        unsafe {
+            let llvm_name = llvm_string("");
+            LLVMBuildBitCast(self.llvm_builder,
+                             llvm_ptr,
+                             LLVMPointerType(LLVMInt8Type(), 0),
+                             llvm_name.as_ptr())
+        }
+    }
+
+    /// Get a pointer to an element of a list, and load the element.
+    fn codegen_list_index(&mut self,
+                          llvm_list: LLVMValueRef,
+                          llvm_index: LLVMValueRef,
+                          llvm_element_type: LLVMTypeRef)
+                          -> LLVMValueRef {
+        let list_index_fn = self.context.runtime.list_index_fn.clone();
+        let llvm_element_ptr = self.codegen_runtime_call(&list_index_fn, vec![llvm_list, llvm_index]);
+        unsafe {
+            let llvm_name = llvm_string("");
+            let llvm_element_ptr = LLVMBuildBitCast(self.llvm_builder,
+                                                    llvm_element_ptr,
+                                                    LLVMPointerType(llvm_element_type, 0),
+                                                    llvm_name.as_ptr());
+            LLVMBuildLoad(self.llvm_builder, llvm_element_ptr, llvm_name.as_ptr())
+        }
+    }
+
+    /// Create an empty list for elements of an LLVM type.
+    fn codegen_list_new(&mut self, llvm_element_type: LLVMTypeRef) -> LLVMValueRef {
+        let list_new_fn = self.context.runtime.list_new_fn.clone();
+        let llvm_size = unsafe { LLVMSizeOf(llvm_element_type) };
+        self.codegen_runtime_call(&list_new_fn, vec![llvm_size])
+    }
+
+    /// Pop the last element of a list into an optional. The runtime writes
+    /// the element straight into the `Some` variant, and reports whether
+    /// there was an element to pop.
+    fn codegen_list_pop(&mut self, llvm_list: LLVMValueRef, optional_type: &EnumType) -> LLVMValueRef {
+        let llvm_enum_type = self.codegen_enum_type(optional_type);
+        let (some, nil) = optional_type.optional_variants();
+        let llvm_some_tag = self.llvm_element_position(&some);
+        let llvm_nil_tag = self.llvm_element_position(&nil);
+        let llvm_variant_type = self.codegen_variant_type(&some);
+        let llvm_enum = self.codegen_alloca(llvm_enum_type);
+        let llvm_variant_ptr = self.codegen_variant_ptr(llvm_enum, llvm_variant_type);
+        let list_pop_fn = self.context.runtime.list_pop_fn.clone();
+        unsafe {
+            let llvm_name = llvm_string("");
+            let llvm_value_ptr = LLVMBuildStructGEP(self.llvm_builder, llvm_variant_ptr, 0, llvm_name.as_ptr());
+            let llvm_value_ptr = self.codegen_byte_ptr(llvm_value_ptr);
+            let llvm_is_some = self.codegen_runtime_call(&list_pop_fn, vec![llvm_list, llvm_value_ptr]);
+            let llvm_tag = LLVMBuildSelect(self.llvm_builder,
+                                           llvm_is_some,
+                                           LLVMConstInt(LLVMInt32Type(), llvm_some_tag as u64, 0),
+                                           LLVMConstInt(LLVMInt32Type(), llvm_nil_tag as u64, 0),
+                                           llvm_name.as_ptr());
+            let llvm_tag_ptr = LLVMBuildStructGEP(self.llvm_builder, llvm_enum, 0, llvm_name.as_ptr());
+            LLVMBuildStore(self.llvm_builder, llvm_tag, llvm_tag_ptr);
+            LLVMBuildLoad(self.llvm_builder, llvm_enum, llvm_name.as_ptr())
+        }
+    }
+
+    /// Push a copy of an element onto the end of a list.
+    fn codegen_list_push(&mut self, llvm_list: LLVMValueRef, llvm_element: LLVMValueRef) {
+        let list_push_fn = self.context.runtime.list_push_fn.clone();
+        let llvm_element_ptr = self.codegen_alloca(unsafe { LLVMTypeOf(llvm_element) });
+        unsafe { LLVMBuildStore(self.llvm_builder, llvm_element, llvm_element_ptr) };
+        let llvm_element_ptr = self.codegen_byte_ptr(llvm_element_ptr);
+        self.codegen_runtime_call(&list_push_fn, vec![llvm_list, llvm_element_ptr]);
+    }
+
+    /// Merge the values of the branches that meet at the current block. There
+    /// is only a value when every branch has one.
+    fn codegen_merge(&mut self, llvm_incoming: Vec<(Option<LLVMValueRef>, LLVMBasicBlockRef)>) -> Option<LLVMValueRef> {
+        if llvm_incoming.is_empty() {
+            // every branch has left by a `break` or a `continue`
+            unsafe { LLVMBuildUnreachable(self.llvm_builder) };
+            return None;
+        }
+        let mut llvm_values = Vec::new();
+        let mut llvm_blocks = Vec::new();
+        for (llvm_value, llvm_block) in llvm_incoming {
+            match llvm_value {
+                Some(llvm_value) if !llvm_is_void(llvm_value) => {
+                    llvm_values.push(llvm_value);
+                    llvm_blocks.push(llvm_block);
+                }
+                _ => return None,
+            }
+        }
+        if llvm_values.is_empty() {
+            return None;
+        }
+        unsafe {
+            let llvm_name = llvm_string("");
+            let llvm_phi = LLVMBuildPhi(self.llvm_builder, LLVMTypeOf(llvm_values[0]), llvm_name.as_ptr());
+            LLVMAddIncoming(llvm_phi,
+                            llvm_values.as_mut_ptr(),
+                            llvm_blocks.as_mut_ptr(),
+                            llvm_values.len() as u32);
+            Some(llvm_phi)
+        }
+    }
+
+    /// Join the processes that have been started, and not yet joined, by the
+    /// blocks from `depth` onwards, which are left before their epilogues.
+    fn codegen_process_joins(&mut self, depth: usize) {
+        let llvm_processes = self.llvm_processes[depth..]
+            .iter()
+            .flat_map(|llvm_processes| llvm_processes.iter().cloned())
+            .collect::<Vec<_>>();
+        let process_join_fn = self.context.runtime.process_join_fn.clone();
+        for llvm_process in llvm_processes {
+            self.codegen_runtime_call(&process_join_fn, vec![llvm_process]);
+        }
+    }
+
+    /// Build a binary operator of the prelude as an instruction. Integers are
+    /// divided, and ordered, as signed or unsigned by their type, and their
+    /// arithmetic does what the operator says when it overflows.
+    fn codegen_prelude_op(&mut self,
+                          call_expr: &CallExpr,
+                          function: &Function,
+                          op: PreludeOp,
+                          overflow: Overflow,
+                          ty: &PrimitiveType,
+                          llvm_lhs: LLVMValueRef,
+                          llvm_rhs: LLVMValueRef)
+                          -> LLVMValueRef {
+        let is_float = match *ty {
+            PrimitiveType::F32 | PrimitiveType::F64 => true,
+            _ => false,
+        };
+        let is_signed = match *ty {
+            PrimitiveType::I8 | PrimitiveType::I16 | PrimitiveType::I32 | PrimitiveType::I64 => true,
+            _ => false,
+        };
+        let llvm_name = llvm_string("");
+        let (llvm_builder, llvm_name) = (self.llvm_builder, llvm_name.as_ptr());
+        unsafe {
+            if is_float {
+                let llvm_predicate = match op {
+                    PreludeOp::Add => return LLVMBuildFAdd(llvm_builder, llvm_lhs, llvm_rhs, llvm_name),
+                    PreludeOp::Div => return LLVMBuildFDiv(llvm_builder, llvm_lhs, llvm_rhs, llvm_name),
+                    PreludeOp::Mul => return LLVMBuildFMul(llvm_builder, llvm_lhs, llvm_rhs, llvm_name),
+                    PreludeOp::Sub => return LLVMBuildFSub(llvm_builder, llvm_lhs, llvm_rhs, llvm_name),
+                    PreludeOp::Eq => LLVMRealPredicate::LLVMRealOEQ,
+                    PreludeOp::Ge => LLVMRealPredicate::LLVMRealOGE,
+                    PreludeOp::Gt => LLVMRealPredicate::LLVMRealOGT,
+                    PreludeOp::Le => LLVMRealPredicate::LLVMRealOLE,
+                    PreludeOp::Lt => LLVMRealPredicate::LLVMRealOLT,
+                    PreludeOp::Ne => LLVMRealPredicate::LLVMRealUNE,
+                };
+                return LLVMBuildFCmp(llvm_builder, llvm_predicate, llvm_lhs, llvm_rhs, llvm_name);
+            }
+            let llvm_predicate = match (op, is_signed) {
+                (PreludeOp::Add, _) | (PreludeOp::Div, _) | (PreludeOp::Mul, _) | (PreludeOp::Sub, _) => {
+                    return self.codegen_int_arithmetic(call_expr, function, op, overflow, is_signed, llvm_lhs, llvm_rhs)
+                }
+                (PreludeOp::Eq, _) => LLVMIntPredicate::LLVMIntEQ,
+                (PreludeOp::Ne, _) => LLVMIntPredicate::LLVMIntNE,
+                (PreludeOp::Ge, true) => LLVMIntPredicate::LLVMIntSGE,
+                (PreludeOp::Ge, false) => LLVMIntPredicate::LLVMIntUGE,
+                (PreludeOp::Gt, true) => LLVMIntPredicate::LLVMIntSGT,
+                (PreludeOp::Gt, false) => LLVMIntPredicate::LLVMIntUGT,
+                (PreludeOp::Le, true) => LLVMIntPredicate::LLVMIntSLE,
+                (PreludeOp::Le, false) => LLVMIntPredicate::LLVMIntULE,
+                (PreludeOp::Lt, true) => LLVMIntPredicate::LLVMIntSLT,
+                (PreludeOp::Lt, false) => LLVMIntPredicate::LLVMIntULT,
+            };
+            LLVMBuildICmp(llvm_builder, llvm_predicate, llvm_lhs, llvm_rhs, llvm_name)
+        }
+    }
+
+    /// Build an assignment operator of the prelude, such as `addeq`, which
+    /// applies its operator to the referenced value and stores the result in
+    /// its place.
+    fn codegen_prelude_assign_op(&mut self,
+                                 call_expr: &CallExpr,
+                                 function: &Function,
+                                 op: PreludeOp,
+                                 ty: &PrimitiveType)
+                                 -> LLVMValueRef {
+        let llvm_ptr = self.codegen_expr(&call_expr.arguments[0]).expect("expected a value");
+        let llvm_rhs = self.codegen_expr(&call_expr.arguments[1]).expect("expected a value");
+        let llvm_name = llvm_string("");
+        let llvm_lhs = unsafe { LLVMBuildLoad(self.llvm_builder, llvm_ptr, llvm_name.as_ptr()) };
+        let llvm_value = self.codegen_prelude_op(call_expr, function, op, Overflow::Trap, ty, llvm_lhs, llvm_rhs);
+        unsafe { LLVMBuildStore(self.llvm_builder, llvm_value, llvm_ptr) }
+    }
+
+    /// Build integer arithmetic. A result that overflows is a runtime error,
+    /// unless the operator is a wrapping, saturating or checked one, which
+    /// wraps it, clamps it, or returns nil instead.
+    fn codegen_int_arithmetic(&mut self,
+                              call_expr: &CallExpr,
+                              function: &Function,
+                              op: PreludeOp,
+                              overflow: Overflow,
+                              is_signed: bool,
+                              llvm_lhs: LLVMValueRef,
+                              llvm_rhs: LLVMValueRef)
+                              -> LLVMValueRef {
+        let identifier = call_expr.identify();
+        let llvm_name = llvm_string("");
+        let (llvm_builder, llvm_name) = (self.llvm_builder, llvm_name.as_ptr());
+        let (llvm_value, llvm_overflow) = match (op, overflow) {
+            (PreludeOp::Div, _) => self.codegen_int_div(&identifier, overflow, is_signed, llvm_lhs, llvm_rhs),
+            (PreludeOp::Add, Overflow::Wrap) => return unsafe { LLVMBuildAdd(llvm_builder, llvm_lhs, llvm_rhs, llvm_name) },
+            (PreludeOp::Mul, Overflow::Wrap) => return unsafe { LLVMBuildMul(llvm_builder, llvm_lhs, llvm_rhs, llvm_name) },
+            (PreludeOp::Sub, Overflow::Wrap) => return unsafe { LLVMBuildSub(llvm_builder, llvm_lhs, llvm_rhs, llvm_name) },
+            _ => self.codegen_overflow_intrinsic(op, is_signed, llvm_lhs, llvm_rhs),
+        };
+        let llvm_overflow = match llvm_overflow {
+            Some(llvm_overflow) => llvm_overflow,
+            None => return llvm_value,
+        };
+        match overflow {
+            Overflow::Check => {
+                let optional_type = match function.ret {
+                    Type::Enum(ref ty) => ty.as_ref().clone(),
+                    _ => panic!("expected a checked operator to return an optional"),
+                };
+                let (some, nil) = optional_type.optional_variants();
+                let llvm_some = self.codegen_enum_value(&optional_type, &some, vec![(0, llvm_value)]);
+                let llvm_nil = self.codegen_enum_value(&optional_type, &nil, Vec::new());
+                unsafe { LLVMBuildSelect(llvm_builder, llvm_overflow, llvm_nil, llvm_some, llvm_name) }
+            }
+            Overflow::Saturate => {
+                let llvm_bound = self.codegen_saturation_bound(op, is_signed, llvm_lhs, llvm_rhs);
+                unsafe { LLVMBuildSelect(llvm_builder, llvm_overflow, llvm_bound, llvm_value, llvm_name) }
+            }
+            Overflow::Trap => {
+                let verb = match op {
+                    PreludeOp::Add => "add",
+                    PreludeOp::Mul => "multiply",
+                    _ => "subtract",
+                };
+                self.codegen_trap_if(&identifier, llvm_overflow, &format!("attempt to {} with overflow", verb));
+                llvm_value
+            }
+            Overflow::Wrap => llvm_value,
+        }
+    }
+
+    /// Build an integer division, which is never left to divide by zero, or
+    /// to overflow, since both are undefined in LLVM. Dividing by zero is a
+    /// runtime error, unless the operator is checked.
+    ///
+    /// # Return
+    /// The quotient, and whether it overflowed, unless that can not happen or
+    /// has already been dealt with.
+    fn codegen_int_div(&mut self,
+                       identifier: &Identifier,
+                       overflow: Overflow,
+                       is_signed: bool,
+                       llvm_lhs: LLVMValueRef,
+                       llvm_rhs: LLVMValueRef)
+                       -> (LLVMValueRef, Option<LLVMValueRef>) {
+        let llvm_name = llvm_string("");
+        let (llvm_builder, llvm_name) = (self.llvm_builder, llvm_name.as_ptr());
+        unsafe {
+            let llvm_type = LLVMTypeOf(llvm_lhs);
+            let llvm_is_zero = LLVMBuildICmp(llvm_builder,
+                                             LLVMIntPredicate::LLVMIntEQ,
+                                             llvm_rhs,
+                                             LLVMConstInt(llvm_type, 0, 0),
+                                             llvm_name);
+            // only the minimum of a signed type divided by -1 overflows
+            let mut llvm_overflow = if is_signed {
+                let llvm_min = LLVMConstInt(llvm_type, 1 << (LLVMGetIntTypeWidth(llvm_type) - 1), 0);
+                let llvm_is_min = LLVMBuildICmp(llvm_builder, LLVMIntPredicate::LLVMIntEQ, llvm_lhs, llvm_min, llvm_name);
+                let llvm_is_minus_one = LLVMBuildICmp(llvm_builder,
+                                                      LLVMIntPredicate::LLVMIntEQ,
+                                                      llvm_rhs,
+                                                      LLVMConstAllOnes(llvm_type),
+                                                      llvm_name);
+                Some(LLVMBuildAnd(llvm_builder, llvm_is_min, llvm_is_minus_one, llvm_name))
+            } else {
+                None
+            };
+            if overflow == Overflow::Check {
+                llvm_overflow = Some(match llvm_overflow {
+                    Some(llvm_overflow) => LLVMBuildOr(llvm_builder, llvm_is_zero, llvm_overflow, llvm_name),
+                    None => llvm_is_zero,
+                });
+            } else {
+                self.codegen_trap_if(identifier, llvm_is_zero, "attempt to divide by zero");
+            }
+            if overflow == Overflow::Trap {
+                if let Some(llvm_overflow) = llvm_overflow.take() {
+                    self.codegen_trap_if(identifier, llvm_overflow, "attempt to divide with overflow");
+                }
+            }
+            // any division that is left undefined is by one instead, which
+            // gives the minimum for a wrapping division
+            let llvm_rhs = match llvm_overflow {
+                Some(llvm_overflow) => {
+                    LLVMBuildSelect(llvm_builder, llvm_overflow, LLVMConstInt(llvm_type, 1, 0), llvm_rhs, llvm_name)
+                }
+                None => llvm_rhs,
+            };
+            let llvm_value = if is_signed {
+                LLVMBuildSDiv(llvm_builder, llvm_lhs, llvm_rhs, llvm_name)
+            } else {
+                LLVMBuildUDiv(llvm_builder, llvm_lhs, llvm_rhs, llvm_name)
+            };
+            (llvm_value, llvm_overflow)
+        }
+    }
+
+    /// Add, subtract or multiply integers with the LLVM intrinsic that also
+    /// tells whether the result overflowed.
+    ///
+    /// # Return
+    /// The wrapped result, and whether it overflowed.
+    fn codegen_overflow_intrinsic(&mut self,
+                                  op: PreludeOp,
+                                  is_signed: bool,
+                                  llvm_lhs: LLVMValueRef,
+                                  llvm_rhs: LLVMValueRef)
+                                  -> (LLVMValueRef, Option<LLVMValueRef>) {
+        unsafe {
+            let llvm_type = LLVMTypeOf(llvm_lhs);
+            let llvm_fn_name = llvm_string(format!("llvm.{}{}.with.overflow.i{}",
+                                                   if is_signed { "s" } else { "u" },
+                                                   match op {
+                                                       PreludeOp::Add => "add",
+                                                       PreludeOp::Mul => "mul",
+                                                       _ => "sub",
+                                                   },
+                                                   LLVMGetIntTypeWidth(llvm_type)));
+            let mut llvm_fn = LLVMGetNamedFunction(self.llvm_module, llvm_fn_name.as_ptr());
+            if llvm_fn.is_null() {
+                let mut llvm_ret_elements = vec![llvm_type, LLVMInt1Type()];
+                let llvm_ret_type = LLVMStructType(llvm_ret_elements.as_mut_ptr(), 2, 0);
+                let mut llvm_formals = vec![llvm_type, llvm_type];
+                let llvm_fn_type = LLVMFunctionType(llvm_ret_type, llvm_formals.as_mut_ptr(), 2, 0);
+                llvm_fn = LLVMAddFunction(self.llvm_module, llvm_fn_name.as_ptr(), llvm_fn_type);
+            }
+            let llvm_name = llvm_string("");
+            let mut llvm_arguments = vec![llvm_lhs, llvm_rhs];
+            let llvm_result = LLVMBuildCall(self.llvm_builder,
+                                            llvm_fn,
+                                            llvm_arguments.as_mut_ptr(),
+                                            2,
+                                            llvm_name.as_ptr());
+            (LLVMBuildExtractValue(self.llvm_builder, llvm_result, 0, llvm_name.as_ptr()),
+             Some(LLVMBuildExtractValue(self.llvm_builder, llvm_result, 1, llvm_name.as_ptr())))
+        }
+    }
+
+    /// Build the bound of its type that the result of a saturating integer
+    /// operator is clamped to when it overflows.
+    fn codegen_saturation_bound(&mut self,
+                                op: PreludeOp,
+                                is_signed: bool,
+                                llvm_lhs: LLVMValueRef,
+                                llvm_rhs: LLVMValueRef)
+                                -> LLVMValueRef {
+        unsafe {
+            let llvm_type = LLVMTypeOf(llvm_lhs);
+            let llvm_zero = LLVMConstInt(llvm_type, 0, 0);
+            if !is_signed {
+                // only a subtraction overflows below zero
+                return match op {
+                    PreludeOp::Sub => llvm_zero,
+                    _ => LLVMConstAllOnes(llvm_type),
+                };
+            }
+            let llvm_width = LLVMGetIntTypeWidth(llvm_type);
+            let llvm_min = LLVMConstInt(llvm_type, 1 << (llvm_width - 1), 0);
+            let llvm_max = LLVMConstInt(llvm_type, (1 << (llvm_width - 1)) - 1, 0);
+            let llvm_name = llvm_string("");
+            let llvm_name = llvm_name.as_ptr();
+            // a result overflows below the minimum when a negative number is
+            // added, a positive number is subtracted, or numbers of different
+            // signs are multiplied
+            let llvm_is_below = match op {
+                PreludeOp::Add => LLVMBuildICmp(self.llvm_builder, LLVMIntPredicate::LLVMIntSLT, llvm_rhs, llvm_zero, llvm_name),
+                PreludeOp::Sub => LLVMBuildICmp(self.llvm_builder, LLVMIntPredicate::LLVMIntSGT, llvm_rhs, llvm_zero, llvm_name),
+                PreludeOp::Mul => {
+                    let llvm_signs = LLVMBuildXor(self.llvm_builder, llvm_lhs, llvm_rhs, llvm_name);
+                    LLVMBuildICmp(self.llvm_builder, LLVMIntPredicate::LLVMIntSLT, llvm_signs, llvm_zero, llvm_name)
+                }
+                _ => return llvm_max,
+            };
+            LLVMBuildSelect(self.llvm_builder, llvm_is_below, llvm_min, llvm_max, llvm_name)
+        }
+    }
+
+    /// Stop the program with a runtime error when a condition holds. The
+    /// message is located at the expression that the error is raised for,
+    /// when its span is known.
+    fn codegen_trap_if(&mut self, identifier: &Identifier, llvm_condition: LLVMValueRef, message: &str) {
+        let message = match self.context.span_table.get(identifier) {
+            Some(span) => format!("{}: {}", span.begin(), message),
+            None => message.to_string(),
+        };
+        let arithmetic_error_fn = self.context.runtime.arithmetic_error_fn.clone();
+        let llvm_trap_block = self.llvm_append_block("arith.trap");
+        let llvm_ok_block = self.llvm_append_block("arith.ok");
+        unsafe {
+            LLVMBuildCondBr(self.llvm_builder, llvm_condition, llvm_trap_block, llvm_ok_block);
+
+            LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_trap_block);
+            let llvm_message = llvm_string(message);
+            let llvm_name = llvm_string("");
+            let llvm_message = LLVMBuildGlobalStringPtr(self.llvm_builder, llvm_message.as_ptr(), llvm_name.as_ptr());
+            self.codegen_runtime_call(&arithmetic_error_fn, vec![llvm_message]);
+            LLVMBuildUnreachable(self.llvm_builder);
+
+            LLVMPositionBuilderAtEnd(self.llvm_builder, llvm_ok_block);
+        }
+    }
+
+    /// Return the value of the body of a function, which must have the return
+    /// type of the function. A void function returns nothing, whatever the
+    /// value of its body.
+    fn codegen_ret(&mut self, function: &Function, llvm_ret: Option<LLVMValueRef>) {
+        if function.is_void() {
+            unsafe { LLVMBuildRetVoid(self.llvm_builder) };
+            return;
+        }
+        let llvm_ret_type = self.codegen_type(&function.ret);
+        match llvm_ret {
+            Some(llvm_ret) if unsafe { LLVMTypeOf(llvm_ret) } == llvm_ret_type => unsafe {
+                LLVMBuildRet(self.llvm_builder, llvm_ret);
+            },
+            Some(llvm_ret) => {
+                panic!("error: mismatched return type in function '{}': expected `{}`, found `{}`",
+                       function.symbolise().name(),
+                       llvm_type_to_string(llvm_ret_type),
+                       llvm_type_to_string(unsafe { LLVMTypeOf(llvm_ret) }))
+            }
+            None => {
+                panic!("error: function '{}' has no value to return, expected `{}`",
+                       function.symbolise().name(),
+                       llvm_type_to_string(llvm_ret_type))
+            }
+        }
+    }
+
+    /// Build a call to a function of the runtime, declaring the function if
+    /// it has not been declared yet.
+    fn codegen_runtime_call(&mut self, function: &Function, mut llvm_arguments: Vec<LLVMValueRef>) -> LLVMValueRef {
+        let llvm_fn = self.add_or_get_function_profile(function);
+        unsafe {
+            let llvm_name = llvm_string("");
+            LLVMBuildCall(self.llvm_builder,
+                          llvm_fn,
                           llvm_arguments.as_mut_ptr(),
                           llvm_arguments.len() as u32,
                           llvm_name.as_ptr())
         }
     }
 
-    pub fn codegen_ptr_type(&mut self, ptr_type: &PtrType) -> LLVMTypeRef {
-        unsafe { LLVMPointerType(self.codegen_type(&ptr_type.inner)    , 0) }
+    /// Bind a variable to a value. A variable that is assigned to, or
+    /// referenced, lives in a stack allocation that the value is stored in.
+    /// Any other variable is the value itself.
+    fn codegen_bind(&mut self, variable: &Variable, llvm_value: LLVMValueRef) {
+        if !self.written_variables.contains(&variable.identify()) {
+            self.llvm_values.insert(variable.identify(), llvm_value);
+            return;
+        }
+        let llvm_variable = self.codegen_variable_alloca(variable);
+        unsafe {
+            LLVMBuildStore(self.llvm_builder, llvm_value, llvm_variable);
+        }
     }
 
-    pub fn codegen_ref_type(&mut self, ref_type: &RefType) -> LLVMTypeRef {
-        unsafe { LLVMPointerType(self.codegen_type(&ref_type.inner)    , 0) }
+    /// Get a pointer to the place that an expression refers to. A value that
+    /// has no place of its own, such as the result of a call, is stored in a
+    /// temporary stack allocation.
+    fn codegen_place(&mut self, expr: &Expr) -> LLVMValueRef {
+        let llvm_name = llvm_string("");
+        match *expr {
+            Expr::Deref(ref deref_expr) => {
+                return self.codegen_expr(&deref_expr.inner).expect("expected a reference");
+            }
+            Expr::Variable(ref variable_expr) => {
+                match variable_expr.parent.first() {
+                    Some(parent) => {
+                        let llvm_position = self.llvm_element_position(&variable_expr.variable);
+                        let llvm_parent = match *parent {
+                            Expr::Variable(ref parent_expr) if is_ref(&parent_expr.variable.ty) => {
+                                self.codegen_expr(parent).expect("expected a reference")
+                            }
+                            _ => self.codegen_place(parent),
+                        };
+                        return unsafe {
+                            LLVMBuildStructGEP(self.llvm_builder, llvm_parent, llvm_position, llvm_name.as_ptr())
+                        };
+                    }
+                    None => {
+                        if let Some(&llvm_variable) = self.llvm_variables.get(&variable_expr.variable.identify()) {
+                            return llvm_variable;
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+        let llvm_value = self.codegen_expr(expr).expect("expected a value");
+        unsafe {
+            let llvm_ptr = self.codegen_alloca(LLVMTypeOf(llvm_value));
+            LLVMBuildStore(self.llvm_builder, llvm_value, llvm_ptr);
+            llvm_ptr
+        }
     }
 
+    /// Allocate a variable on the stack of the current function.
+    fn codegen_variable_alloca(&mut self, variable: &Variable) -> LLVMValueRef {
+        let llvm_type = self.codegen_type(&variable.ty);
+        let llvm_variable = self.codegen_alloca(llvm_type);
+        unsafe {
+            let llvm_name = llvm_string(variable.symbolise().name());
+            LLVMSetValueName(llvm_variable, llvm_name.as_ptr());
+        }
+        self.llvm_variables.insert(variable.identify(), llvm_variable);
+        llvm_variable
+    }
 
-    pub fn codegen_void_expr(&mut self, void_expr: &VoidExpr) -> Option<LLVMValueRef> {
+    /// Allocate memory on the stack of the current function. Allocations are
+    /// placed at the start of the entry block, so that they happen once.
+    fn codegen_alloca(&mut self, llvm_type: LLVMTypeRef) -> LLVMValueRef {
+        unsafe {
+            let llvm_fn = self.llvm_current_function.expect("expected Some(LLVMValueRef) for None");
+            let llvm_entry_block = LLVMGetEntryBasicBlock(llvm_fn);
+            let llvm_builder = LLVMCreateBuilderInContext(LLVMGetModuleContext(self.llvm_module));
+            let llvm_first = LLVMGetFirstInstruction(llvm_entry_block);
+            if llvm_first == ptr::null_mut() {
+                LLVMPositionBuilderAtEnd(llvm_builder, llvm_entry_block);
+            } else {
+                LLVMPositionBuilderBefore(llvm_builder, llvm_first);
+            }
+            let llvm_name = llvm_string("");
+            let llvm_alloca = LLVMBuildAlloca(llvm_builder, llvm_type, llvm_name.as_ptr());
+            LLVMDisposeBuilder(llvm_builder);
+            llvm_alloca
+        }
+    }
+
+    pub fn codegen_void_expr(&mut self, _: &VoidExpr) -> Option<LLVMValueRef> {
         None
     }
 
@@ -366,14 +1888,53 @@ impl LLVMBuilder {
 
     pub fn codegen_expr(&mut self, expr: &Expr) -> Option<LLVMValueRef> {
         match *expr {
-            Expr::Block(ref expr) => self.codegen_block_expr("", expr),
+            Expr::Assign(ref expr) => {
+                self.codegen_assign_expr(expr);
+                None
+            }
+            Expr::Block(ref expr) => self.codegen_block_expr(expr),
+            Expr::Break(ref expr) => {
+                self.codegen_break_expr(expr);
+                None
+            }
             Expr::Call(ref expr) => Some(self.codegen_call_expr(expr)),
+            Expr::Channel(ref expr) => Some(self.codegen_channel_expr(expr)),
+            Expr::ChannelOp(ref expr) => self.codegen_channel_op_expr(expr),
+            Expr::Continue(ref expr) => {
+                self.codegen_continue_expr(expr);
+                None
+            }
+            Expr::Deref(ref expr) => Some(self.codegen_deref_expr(expr)),
+            Expr::Enum(ref expr) => Some(self.codegen_enum_expr(expr)),
+            Expr::For(ref expr) => {
+                self.codegen_for_expr(expr);
+                None
+            }
+            Expr::If(ref expr) => self.codegen_if_expr(expr),
             Expr::Process(ref expr) => Some(self.codegen_process_expr(expr)),
             Expr::ProcessJoin(ref expr) => Some(self.codegen_process_join_expr(expr)),
             Expr::Item(ref expr) => Some(self.codegen_item_expr(expr)),
+            Expr::Lambda(ref expr) => Some(self.codegen_lambda_expr(expr)),
+            Expr::Let(ref expr) => {
+                self.codegen_let_expr(expr);
+                None
+            }
+            Expr::LetTuple(ref expr) => {
+                self.codegen_let_tuple_expr(expr);
+                None
+            }
+            Expr::List(ref expr) => Some(self.codegen_list_expr(expr)),
+            Expr::ListOp(ref expr) => self.codegen_list_op_expr(expr),
             Expr::Literal(ref expr) => Some(self.codegen_literal_expr(expr)),
+            Expr::Match(ref expr) => self.codegen_match_expr(expr),
+            Expr::Range(ref expr) => Some(self.codegen_range_expr(expr)),
+            Expr::Ref(ref expr) => Some(self.codegen_ref_expr(expr)),
+            Expr::Select(ref expr) => self.codegen_select_expr(expr),
+            Expr::Struct(ref expr) => Some(self.codegen_struct_expr(expr)),
+            Expr::Tuple(ref expr) => Some(self.codegen_tuple_expr(expr)),
+            Expr::Unwrap(ref expr) => Some(self.codegen_unwrap_expr(expr)),
+            Expr::Variable(ref expr) => Some(self.codegen_variable_expr(expr)),
             Expr::Void(ref expr) => self.codegen_void_expr(expr),
-            _ => unimplemented!(),
         }
     }
 
@@ -386,10 +1947,16 @@ impl LLVMBuilder {
 
     pub fn codegen_type(&mut self, ty: &Type) -> LLVMTypeRef {
         match *ty {
+            Type::Channel(ref ty) => self.codegen_channel_type(ty),
+            Type::Closure(ref ty) => self.codegen_closure_type(ty),
+            Type::Enum(ref ty) => self.codegen_enum_type(ty),
             Type::Lambda(ref ty) => self.codegen_lambda_type(ty),
+            Type::List(ref ty) => self.codegen_list_type(ty),
             Type::Primitive(ref ty) => self.codegen_primitive_type(ty),
             Type::Ptr(ref ty) => self.codegen_ptr_type(ty),
             Type::Ref(ref ty) => self.codegen_ref_type(ty),
+            Type::Struct(ref ty) => self.codegen_struct_type(ty),
+            Type::Tuple(ref ty) => self.codegen_tuple_type(ty),
             _ => unimplemented!(),
         }
     }
@@ -410,6 +1977,31 @@ impl LLVMBuilder {
         }
     }
 
+    fn llvm_append_block<S: Into<String>>(&self, block_name: S) -> LLVMBasicBlockRef {
+        let llvm_name = llvm_string(block_name);
+        unsafe {
+            LLVMAppendBasicBlockInContext(LLVMGetModuleContext(self.llvm_module),
+                                          self.llvm_current_function.expect("expected Some(LLVMValueRef) for None"),
+                                          llvm_name.as_ptr())
+        }
+    }
+
+    /// Whether the current block already ends in a terminator, such as the
+    /// branch of a `break`.
+    fn llvm_is_terminated(&self) -> bool {
+        unsafe {
+            let llvm_block = LLVMGetInsertBlock(self.llvm_builder);
+            llvm_block != ptr::null_mut() && LLVMGetBasicBlockTerminator(llvm_block) != ptr::null_mut()
+        }
+    }
+
+    fn llvm_element_position(&self, element: &Variable) -> u32 {
+        self.llvm_element_positions
+            .get(&element.identify())
+            .expect("use of undefined struct element")
+            .clone()
+    }
+
     pub fn dump(&self) {
         llvm_dump_module(self.llvm_module);
     }
@@ -430,11 +2022,11 @@ impl LLVMBuilder {
                   .stdin(Stdio::piped())
                   .spawn() {
             Ok(ld) => ld,
-            Err(err) => panic!(err.to_string()),
+            Err(err) => panic!("{}", err),
         };
         match ld.stdin.unwrap().write_all(bytecode.as_bytes()) {
             Ok(_) => (),
-            Err(err) => panic!(err.to_string()),
+            Err(err) => panic!("{}", err),
         };
 
         // compile using clang
@@ -456,6 +2048,34 @@ impl LLVMBuilder {
     }
 }
 
+impl Drop for LLVMBuilder {
+    fn drop(&mut self) {
+        unsafe {
+            LLVMDisposeBuilder(self.llvm_builder);
+            LLVMContextDispose(self.llvm_context);
+        }
+    }
+}
+
+/// The blocks that a `continue`, and a `break`, of a loop branch to, and the
+/// number of blocks that enclosed the loop when it was entered.
+#[derive(Clone, Copy)]
+struct LLVMLoop {
+    llvm_continue_block: LLVMBasicBlockRef,
+    llvm_break_block: LLVMBasicBlockRef,
+    processes: usize,
+}
+
+impl LLVMLoop {
+    fn new(llvm_continue_block: LLVMBasicBlockRef, llvm_break_block: LLVMBasicBlockRef, processes: usize) -> LLVMLoop {
+        LLVMLoop {
+            llvm_continue_block: llvm_continue_block,
+            llvm_break_block: llvm_break_block,
+            processes: processes,
+        }
+    }
+}
+
 ///
 #[derive(Clone)]
 pub enum LLVMNode {
@@ -502,10 +2122,202 @@ impl From<LLVMNode> for LLVMValueRef {
     }
 }
 
+fn is_ref(ty: &Type) -> bool {
+    if let Type::Ref(..) = *ty { true } else { false }
+}
+
+/// Find the variables that a block assigns to, or takes a reference of, and
+/// which must therefore live in memory.
+fn written_variables(block_expr: &BlockExpr) -> HashSet<Identifier> {
+    let mut variables = HashSet::new();
+    collect_written_block(block_expr, &mut variables);
+    variables
+}
+
+fn collect_written_block(block_expr: &BlockExpr, variables: &mut HashSet<Identifier>) {
+    for expr in block_expr.prelude.iter().chain(block_expr.body.iter()).chain(block_expr.epilogue.iter()) {
+        collect_written(expr, variables);
+    }
+    collect_written(&block_expr.ret, variables);
+}
+
+/// Collect the variable that owns the place of an expression. An element of
+/// a struct is owned by the variable that owns the struct, unless it is
+/// reached through a reference.
+fn collect_written_place(expr: &Expr, variables: &mut HashSet<Identifier>) {
+    if let Expr::Variable(ref variable_expr) = *expr {
+        match variable_expr.parent.first() {
+            Some(&Expr::Variable(ref parent_expr)) if is_ref(&parent_expr.variable.ty) => (),
+            Some(parent) => collect_written_place(parent, variables),
+            None => {
+                variables.insert(variable_expr.variable.identify());
+            }
+        }
+    }
+}
+
+fn collect_written(expr: &Expr, variables: &mut HashSet<Identifier>) {
+    match *expr {
+        Expr::Assign(ref expr) => {
+            collect_written_place(&expr.lhs, variables);
+            collect_written(&expr.lhs, variables);
+            collect_written(&expr.rhs, variables);
+        }
+        Expr::Block(ref expr) => collect_written_block(expr, variables),
+        Expr::Break(..) | Expr::Continue(..) => (),
+        Expr::Call(ref expr) => {
+            collect_written(&expr.target, variables);
+            for argument in expr.arguments.iter() {
+                collect_written(argument, variables);
+            }
+        }
+        Expr::Channel(ref expr) => {
+            if let Some(ref capacity) = expr.capacity {
+                collect_written(capacity, variables);
+            }
+        }
+        Expr::ChannelOp(ref expr) => {
+            match expr.op {
+                ChannelOp::Close(ref channel) |
+                ChannelOp::Recv(ref channel) => collect_written(channel, variables),
+                ChannelOp::Send(ref channel, ref value) => {
+                    collect_written(channel, variables);
+                    collect_written(value, variables);
+                }
+            }
+        }
+        Expr::Deref(ref expr) => collect_written(&expr.inner, variables),
+        Expr::Enum(ref expr) => {
+            for &(_, ref element) in expr.elements.iter() {
+                collect_written(element, variables);
+            }
+        }
+        Expr::For(ref expr) => {
+            collect_written(&expr.iterator, variables);
+            collect_written_block(&expr.iteration, variables);
+        }
+        Expr::If(ref expr) => {
+            collect_written(&expr.condition, variables);
+            collect_written_block(&expr.then_block, variables);
+            collect_written_block(&expr.else_block, variables);
+        }
+        Expr::Lambda(ref expr) => collect_written_block(&expr.body, variables),
+        Expr::Let(ref expr) => collect_written(&expr.definition.rhs, variables),
+        Expr::LetTuple(ref expr) => collect_written(&expr.definition, variables),
+        Expr::List(ref expr) => {
+            for element in expr.elements.iter() {
+                collect_written(element, variables);
+            }
+        }
+        Expr::ListOp(ref expr) => {
+            match expr.op {
+                ListOp::Index(ref list, ref operand) |
+                ListOp::Push(ref list, ref operand) => {
+                    collect_written(list, variables);
+                    collect_written(operand, variables);
+                }
+                ListOp::Len(ref list) |
+                ListOp::Pop(ref list, _) => collect_written(list, variables),
+                ListOp::Slice(ref list, ref start, ref end) => {
+                    collect_written(list, variables);
+                    collect_written(start, variables);
+                    collect_written(end, variables);
+                }
+            }
+        }
+        Expr::Match(ref expr) => {
+            collect_written(&expr.subject, variables);
+            for arm in expr.arms.iter() {
+                collect_written_block(&arm.body, variables);
+            }
+        }
+        Expr::Process(ref expr) => collect_written_block(&expr.body, variables),
+        Expr::Range(ref expr) => {
+            collect_written(&expr.start, variables);
+            collect_written(&expr.end, variables);
+        }
+        Expr::Ref(ref expr) => {
+            collect_written_place(&expr.inner, variables);
+            collect_written(&expr.inner, variables);
+        }
+        Expr::Select(ref expr) => {
+            for guard in expr.guards.iter() {
+                match guard.op {
+                    SelectOp::Recv(ref channel, _) => collect_written(channel, variables),
+                    SelectOp::Send(ref channel, ref value) => {
+                        collect_written(channel, variables);
+                        collect_written(value, variables);
+                    }
+                }
+                collect_written_block(&guard.body, variables);
+            }
+            if let Some(ref else_body) = expr.else_body {
+                collect_written_block(else_body, variables);
+            }
+        }
+        Expr::Struct(ref expr) => {
+            for &(_, ref element) in expr.elements.iter() {
+                collect_written(element, variables);
+            }
+        }
+        Expr::Tuple(ref expr) => {
+            for element in expr.elements.iter() {
+                collect_written(element, variables);
+            }
+        }
+        Expr::Unwrap(ref expr) => collect_written(&expr.inner, variables),
+        Expr::Variable(ref expr) => {
+            for parent in expr.parent.iter() {
+                collect_written(parent, variables);
+            }
+        }
+        Expr::Item(..) |
+        Expr::Literal(..) |
+        Expr::ProcessJoin(..) |
+        Expr::Void(..) => (),
+    }
+}
+
 fn llvm_string<S: Into<String>>(string: S) -> CString {
     CString::new(string.into()).unwrap()
 }
 
+fn llvm_is_pointer(value: LLVMValueRef) -> bool {
+    unsafe {
+        match LLVMGetTypeKind(LLVMTypeOf(value)) {
+            LLVMTypeKind::LLVMPointerTypeKind => true,
+            _ => false,
+        }
+    }
+}
+
+fn llvm_is_struct(value: LLVMValueRef) -> bool {
+    unsafe {
+        match LLVMGetTypeKind(LLVMTypeOf(value)) {
+            LLVMTypeKind::LLVMStructTypeKind => true,
+            _ => false,
+        }
+    }
+}
+
+fn llvm_is_void(value: LLVMValueRef) -> bool {
+    unsafe {
+        match LLVMGetTypeKind(LLVMTypeOf(value)) {
+            LLVMTypeKind::LLVMVoidTypeKind => true,
+            _ => false,
+        }
+    }
+}
+
+fn llvm_type_to_string(ty: LLVMTypeRef) -> String {
+    unsafe {
+        let llvm_string = LLVMPrintTypeToString(ty);
+        let string = CStr::from_ptr(llvm_string).to_string_lossy().into_owned();
+        LLVMDisposeMessage(llvm_string);
+        string
+    }
+}
+
 fn llvm_dump_module(module: LLVMModuleRef) {
     unsafe {
         println!("{}",
@@ -1,5 +1,6 @@
 use super::llvm::*;
-use super::super::identifier::Symbol;
+use super::super::identifier::{Identifier, Symbol};
+use super::super::lexer::Span;
 use super::super::noir::*;
 use super::super::noir::context::*;
 
@@ -42,6 +43,660 @@ fn hello_world() {
     builder.build();
 }
 
+#[test]
+fn struct_layout() {
+    let context = Context::new();
+    let mut builder = LLVMBuilder::new("test", context.clone());
+
+    let x = Variable::new(Symbol::new("x"), PrimitiveType::F64);
+    let y = Variable::new(Symbol::new("y"), PrimitiveType::F64);
+    let point = StructType::new(Symbol::new("Point"), vec![x.clone(), y.clone()]);
+    let p = Variable::new(Symbol::new("p"), RefType::new(point.clone()));
+
+    // move the point along the x axis, writing through the reference
+    let move_x_fn = Function::new(
+        Symbol::new("move_x"),
+        vec![p.clone()],
+        PrimitiveType::Void,
+        BlockExpr::new(
+            Exprs::new(), // prelude
+            Exprs::new(), // epilogue
+            vec![
+                AssignExpr::new(
+                    VariableExpr::new_with_parent(Identifier::id(), VariableExpr::new(p.clone()), x.clone()),
+                    VariableExpr::new_with_parent(Identifier::id(), VariableExpr::new(p.clone()), y.clone()),
+                ),
+            ], // body
+            VoidExpr::new(), // ret
+            FunctionTable::new(), // functions
+            ModuleTable::new(), // modules
+            TypeTable::new(), // types
+        )
+    );
+    builder.codegen_function(&move_x_fn);
+
+    // elements are laid out in the order that they are declared
+    let ir = builder.dump_to_string();
+    assert!(ir.contains("%Point = type { double, double }"));
+    assert!(ir.contains("getelementptr inbounds %Point, %Point* %p, i32 0, i32 1"));
+    assert!(ir.contains("getelementptr inbounds %Point, %Point* %p, i32 0, i32 0"));
+}
+
+#[test]
+fn tuple_destructuring() {
+    let context = Context::new();
+    let mut builder = LLVMBuilder::new("test", context.clone());
+
+    let t = Variable::new(Symbol::new("t"), TupleType::new(vec![PrimitiveType::I64, PrimitiveType::Bool]));
+    let a = Variable::new(Symbol::new("a"), PrimitiveType::I64);
+    let b = Variable::new(Symbol::new("b"), PrimitiveType::Bool);
+
+    // destructure a tuple and build another with its elements swapped
+    let swap_fn = Function::new(
+        Symbol::new("swap"),
+        vec![t.clone()],
+        PrimitiveType::Void,
+        BlockExpr::new(
+            Exprs::new(), // prelude
+            Exprs::new(), // epilogue
+            vec![
+                Expr::from(LetTupleExpr::new(vec![a.clone(), b.clone()], VariableExpr::new(t.clone()))),
+                Expr::from(TupleExpr::new(vec![VariableExpr::new(b.clone()), VariableExpr::new(a.clone())])),
+            ], // body
+            VoidExpr::new(), // ret
+            FunctionTable::new(), // functions
+            ModuleTable::new(), // modules
+            TypeTable::new(), // types
+        )
+    );
+    builder.codegen_function(&swap_fn);
+
+    let ir = builder.dump_to_string();
+    assert!(ir.contains("%a = extractvalue { i64, i1 } %t, 0"));
+    assert!(ir.contains("%b = extractvalue { i64, i1 } %t, 1"));
+    assert!(ir.contains("insertvalue { i1, i64 } undef, i1 %b, 0"));
+}
+
+#[test]
+fn locals_and_references() {
+    let context = Context::new();
+    let mut builder = LLVMBuilder::new("test", context.clone());
+
+    let x = Variable::new(Symbol::new("x"), PrimitiveType::I64);
+    let y = Variable::new(Symbol::new("y"), PrimitiveType::I64);
+    let r = Variable::new(Symbol::new("r"), RefType::new(PrimitiveType::I64));
+
+    // write to a formal, and to a local through a reference
+    let bump_fn = Function::new(
+        Symbol::new("bump"),
+        vec![x.clone()],
+        PrimitiveType::I64,
+        BlockExpr::new(
+            Exprs::new(), // prelude
+            Exprs::new(), // epilogue
+            vec![
+                Expr::from(LetExpr::new(y.clone(), VariableExpr::new(x.clone()))),
+                Expr::from(AssignExpr::new(VariableExpr::new(x.clone()), LiteralExpr::new(Literal::I64(1)))),
+                Expr::from(LetExpr::new(r.clone(), RefExpr::new(VariableExpr::new(y.clone())))),
+                Expr::from(AssignExpr::new(DerefExpr::new(VariableExpr::new(r.clone())), VariableExpr::new(x.clone()))),
+            ], // body
+            DerefExpr::new(VariableExpr::new(r.clone())), // ret
+            FunctionTable::new(), // functions
+            ModuleTable::new(), // modules
+            TypeTable::new(), // types
+        )
+    );
+    builder.codegen_function(&bump_fn);
+
+    let ir = builder.dump_to_string();
+    assert!(ir.contains("%x1 = alloca i64"));
+    assert!(ir.contains("%y = alloca i64"));
+    assert!(ir.contains("%r = alloca i64*"));
+    assert!(ir.contains("store i64 %x, i64* %x1"));
+    assert!(ir.contains("store i64 1, i64* %x1"));
+    assert!(ir.contains("store i64* %y, i64** %r"));
+    assert!(ir.contains("load i64*, i64** %r"));
+    assert!(ir.contains("ret i64 %"));
+}
+
+#[test]
+fn list_iteration() {
+    let context = Context::new();
+    let mut builder = LLVMBuilder::new("test", context.clone());
+
+    let xs = Variable::new(Symbol::new("xs"), ListType::new(PrimitiveType::I64));
+    let x = Variable::new(Symbol::new("x"), PrimitiveType::I64);
+    let i = Variable::new(Symbol::new("i"), PrimitiveType::I64);
+    let n = Variable::new(Symbol::new("n"), PrimitiveType::I64);
+    let push = |value: Variable| -> Expr {
+        ListOpExpr::new(ListOp::Push(VariableExpr::new(xs.clone()).into(), VariableExpr::new(value).into()),
+                        PrimitiveType::I64)
+            .into()
+    };
+    let block = |body: Vec<Expr>| -> BlockExpr {
+        BlockExpr::new(
+            Exprs::new(), // prelude
+            Exprs::new(), // epilogue
+            body, // body
+            VoidExpr::new(), // ret
+            FunctionTable::new(), // functions
+            ModuleTable::new(), // modules
+            TypeTable::new(), // types
+        )
+    };
+
+    // count up to n, and then push every element of the list again
+    let repeat_fn = Function::new(
+        Symbol::new("repeat"),
+        vec![xs.clone(), n.clone()],
+        PrimitiveType::Void,
+        block(vec![
+            ForExpr::new(false,
+                         i.clone(),
+                         RangeExpr::new(LiteralExpr::new(Literal::I64(0)), VariableExpr::new(n.clone())),
+                         block(vec![push(i.clone())]),
+                         ListType::new(PrimitiveType::I64))
+                .into(),
+            ForExpr::new(false, x.clone(), VariableExpr::new(xs.clone()), block(vec![push(x.clone())]), xs.ty.clone()).into(),
+        ]),
+    );
+    builder.codegen_function(&repeat_fn);
+
+    let ir = builder.dump_to_string();
+    assert!(ir.contains("%i = phi i64 [ 0, %entry ]"));
+    assert!(ir.contains("icmp slt i64 %i, %n"));
+    assert!(ir.contains("call i64 @__libruntime__list_len(i8* %xs)"));
+    assert!(ir.contains("call i8* @__libruntime__list_index(i8* %xs"));
+    assert!(ir.contains("call void @__libruntime__list_push(i8* %xs"));
+}
+
+#[test]
+fn channel_ops() {
+    let context = Context::new();
+    let mut builder = LLVMBuilder::new("test", context.clone());
+
+    let ch = Variable::new(Symbol::new("ch"), ChannelType::new(PrimitiveType::I64));
+    let x = Variable::new(Symbol::new("x"), PrimitiveType::I64);
+    let block = |body: Vec<Expr>| -> BlockExpr {
+        BlockExpr::new(
+            Exprs::new(), // prelude
+            Exprs::new(), // epilogue
+            body, // body
+            VoidExpr::new(), // ret
+            FunctionTable::new(), // functions
+            ModuleTable::new(), // modules
+            TypeTable::new(), // types
+        )
+    };
+
+    // echo every value received back into the channel, and then close it
+    let echo_fn = Function::new(
+        Symbol::new("echo"),
+        vec![ch.clone()],
+        PrimitiveType::Void,
+        block(vec![
+            ForExpr::new(false,
+                         x.clone(),
+                         VariableExpr::new(ch.clone()),
+                         block(vec![
+                             ChannelOpExpr::new(ChannelOp::Send(VariableExpr::new(ch.clone()).into(),
+                                                                VariableExpr::new(x.clone()).into()),
+                                                PrimitiveType::I64)
+                                 .into(),
+                         ]),
+                         ch.ty.clone())
+                .into(),
+            ChannelOpExpr::new(ChannelOp::Close(VariableExpr::new(ch.clone()).into()), PrimitiveType::I64).into(),
+        ]),
+    );
+    builder.codegen_function(&echo_fn);
+
+    let ir = builder.dump_to_string();
+    assert!(ir.contains("call i1 @__libruntime__channel_next(i8* %ch"));
+    assert!(ir.contains("call void @__libruntime__channel_send(i8* %ch"));
+    assert!(ir.contains("call void @__libruntime__channel_close(i8* %ch)"));
+}
+
+#[test]
+fn forall_processes() {
+    let context = Context::new();
+    let mut builder = LLVMBuilder::new("test", context.clone());
+
+    let chs = Variable::new(Symbol::new("chs"), ListType::new(ChannelType::new(PrimitiveType::I64)));
+    let ch = Variable::new(Symbol::new("ch"), ChannelType::new(PrimitiveType::I64));
+    let block = |body: Vec<Expr>| -> BlockExpr {
+        BlockExpr::new(
+            Exprs::new(), // prelude
+            Exprs::new(), // epilogue
+            body, // body
+            VoidExpr::new(), // ret
+            FunctionTable::new(), // functions
+            ModuleTable::new(), // modules
+            TypeTable::new(), // types
+        )
+    };
+
+    // close every channel of the list from a process of its own
+    let close_all_fn = Function::new(
+        Symbol::new("close_all"),
+        vec![chs.clone()],
+        PrimitiveType::Void,
+        block(vec![
+            ForExpr::new(true,
+                         ch.clone(),
+                         VariableExpr::new(chs.clone()),
+                         block(vec![
+                             ChannelOpExpr::new(ChannelOp::Close(VariableExpr::new(ch.clone()).into()),
+                                                PrimitiveType::I64)
+                                 .into(),
+                         ]),
+                         chs.ty.clone())
+                .into(),
+        ]),
+    );
+    builder.codegen_function(&close_all_fn);
+
+    let ir = builder.dump_to_string();
+    assert!(ir.contains("define void @__libruntime__forall_"));
+    assert!(ir.contains("call i8* @__libruntime__process_group_new()"));
+    assert!(ir.contains("call void @__libruntime__process_group_spawn(i8* %"));
+    assert!(ir.contains("call void @__libruntime__process_group_join(i8* %"));
+    assert!(ir.contains("call void @__libruntime__channel_close(i8* %ch)"));
+}
+
+#[test]
+fn process_captures() {
+    let context = Context::new();
+    let mut builder = LLVMBuilder::new("test", context.clone());
+
+    let ch = Variable::new(Symbol::new("ch"), ChannelType::new(PrimitiveType::I64));
+
+    // close the channel from a process, which captures it
+    let (mut process, process_join) = simple_process(vec![
+        ChannelOpExpr::new(ChannelOp::Close(VariableExpr::new(ch.clone()).into()), PrimitiveType::I64),
+    ]);
+    process.captures = vec![ch.clone()];
+    let close_later_fn = Function::new(
+        Symbol::new("close_later"),
+        vec![ch.clone()],
+        PrimitiveType::Void,
+        BlockExpr::new(
+            Exprs::new(), // prelude
+            vec![process_join], // epilogue
+            vec![process], // body
+            VoidExpr::new(), // ret
+            FunctionTable::new(), // functions
+            ModuleTable::new(), // modules
+            TypeTable::new(), // types
+        ),
+    );
+    builder.codegen_function(&close_later_fn);
+
+    let ir = builder.dump_to_string();
+    assert!(ir.contains("define void @__libruntime__process_"));
+    assert!(ir.contains("%ch = load i8*, i8** %"));
+    assert!(ir.contains("call i8* @__libruntime__process(void (i8*)* @__libruntime__process_"));
+    assert!(ir.contains("call void @__libruntime__channel_close(i8* %ch)"));
+}
+
+#[test]
+fn lambda_captures() {
+    let context = Context::new();
+    let mut builder = LLVMBuilder::new("test", context.clone());
+
+    let n = Variable::new(Symbol::new("n"), PrimitiveType::I64);
+    let x = Variable::new(Symbol::new("x"), PrimitiveType::I64);
+    let block = |ret: Expr| -> BlockExpr {
+        BlockExpr::new(
+            Exprs::new(), // prelude
+            Exprs::new(), // epilogue
+            Exprs::new(), // body
+            ret, // ret
+            FunctionTable::new(), // functions
+            ModuleTable::new(), // modules
+            TypeTable::new(), // types
+        )
+    };
+
+    // call a lambda that captures `n`
+    let mut lambda = LambdaExpr::new(vec![x.clone()], PrimitiveType::I64, block(VariableExpr::new(n.clone()).into()));
+    lambda.captures = vec![n.clone()];
+    let capture_fn = Function::new(
+        Symbol::new("capture"),
+        vec![n.clone()],
+        PrimitiveType::I64,
+        block(CallExpr::new(lambda, vec![LiteralExpr::new(Literal::I64(1))]).into()),
+    );
+    builder.codegen_function(&capture_fn);
+
+    let ir = builder.dump_to_string();
+    assert!(ir.contains("define i64 @__libruntime__lambda_"));
+    assert!(ir.contains("i64 %x)"));
+    assert!(ir.contains("%n = load i64, i64* %"));
+    assert!(ir.contains("call i8* @__libruntime__environment_new(i8* %"));
+    assert!(ir.contains("i64 (i8*, i64)* @__libruntime__lambda_"));
+    assert!(ir.contains("insertvalue { i64 (i8*, i64)*, i8* }"));
+    assert!(ir.contains("extractvalue { i64 (i8*, i64)*, i8* } %"));
+    assert!(ir.contains("call i64 %"));
+}
+
+#[test]
+fn channel_capacity() {
+    let context = Context::new();
+    let mut builder = LLVMBuilder::new("test", context.clone());
+
+    // an unbounded channel, and a rendezvous channel
+    let channels_fn = Function::new(
+        Symbol::new("channels"),
+        Variables::new(),
+        PrimitiveType::Void,
+        BlockExpr::new(
+            Exprs::new(), // prelude
+            Exprs::new(), // epilogue
+            vec![
+                ChannelExpr::new(None, PrimitiveType::I64),
+                ChannelExpr::new(Some(LiteralExpr::new(Literal::I64(0)).into()), PrimitiveType::I64),
+            ], // body
+            VoidExpr::new(), // ret
+            FunctionTable::new(), // functions
+            ModuleTable::new(), // modules
+            TypeTable::new(), // types
+        ),
+    );
+    builder.codegen_function(&channels_fn);
+
+    let ir = builder.dump_to_string();
+    let channel_news = ir.lines().filter(|line| line.contains("call i8* @__libruntime__channel_new(")).collect::<Vec<_>>();
+    assert_eq!(channel_news.len(), 2);
+    assert!(channel_news[0].ends_with(", i64 -1)"));
+    assert!(channel_news[1].ends_with(", i64 0)"));
+}
+
+#[test]
+fn select_guards() {
+    let context = Context::new();
+    let mut builder = LLVMBuilder::new("test", context.clone());
+
+    let xs = Variable::new(Symbol::new("xs"), ChannelType::new(PrimitiveType::I64));
+    let ys = Variable::new(Symbol::new("ys"), ChannelType::new(PrimitiveType::I64));
+    let x = Variable::new(Symbol::new("x"), PrimitiveType::I64);
+    let block = |ret: Expr| -> BlockExpr {
+        BlockExpr::new(
+            Exprs::new(), // prelude
+            Exprs::new(), // epilogue
+            Exprs::new(), // body
+            ret, // ret
+            FunctionTable::new(), // functions
+            ModuleTable::new(), // modules
+            TypeTable::new(), // types
+        )
+    };
+
+    // forward a value from one channel to the other, or give up
+    let forward_fn = Function::new(
+        Symbol::new("forward"),
+        vec![xs.clone(), ys.clone()],
+        PrimitiveType::I64,
+        block(SelectExpr::new(
+            vec![
+                SelectGuard::new(SelectOp::Recv(VariableExpr::new(xs.clone()).into(), Some(x.clone())),
+                                 block(VariableExpr::new(x.clone()).into()),
+                                 PrimitiveType::I64),
+                SelectGuard::new(SelectOp::Send(VariableExpr::new(ys.clone()).into(),
+                                                LiteralExpr::new(Literal::I64(1)).into()),
+                                 block(LiteralExpr::new(Literal::I64(1)).into()),
+                                 PrimitiveType::I64),
+            ],
+            Some(block(LiteralExpr::new(Literal::I64(0)).into())),
+        ).into()),
+    );
+    builder.codegen_function(&forward_fn);
+
+    let ir = builder.dump_to_string();
+    assert!(ir.contains("call i8* @__libruntime__select_new()"));
+    assert!(ir.contains("call void @__libruntime__select_recv(i8* %"));
+    assert!(ir.contains("call void @__libruntime__select_send(i8* %"));
+    assert!(ir.contains("call i64 @__libruntime__select_wait(i8* %"));
+    assert!(ir.contains("i1 false)"));
+    assert!(ir.contains("%x = load i64, i64* %"));
+    assert!(ir.contains("phi i64 [ %x, %select.guard ], [ 1, %select.guard1 ], [ 0, %select.else ]"));
+}
+
+#[test]
+fn if_and_loop_control() {
+    let context = Context::new();
+    let mut builder = LLVMBuilder::new("test", context.clone());
+
+    let b = Variable::new(Symbol::new("b"), PrimitiveType::Bool);
+    let x = Variable::new(Symbol::new("x"), PrimitiveType::I64);
+    let i = Variable::new(Symbol::new("i"), PrimitiveType::I64);
+    let n = Variable::new(Symbol::new("n"), PrimitiveType::I64);
+    let block = |body: Vec<Expr>, ret: Expr| -> BlockExpr {
+        BlockExpr::new(
+            Exprs::new(), // prelude
+            Exprs::new(), // epilogue
+            body, // body
+            ret, // ret
+            FunctionTable::new(), // functions
+            ModuleTable::new(), // modules
+            TypeTable::new(), // types
+        )
+    };
+
+    // an `if` that is used as a value, in a nested block
+    let pick_fn = Function::new(
+        Symbol::new("pick"),
+        vec![b.clone(), x.clone()],
+        PrimitiveType::I64,
+        block(vec![], block(vec![], IfExpr::new(VariableExpr::new(b.clone()),
+                                                block(vec![], VariableExpr::new(x.clone()).into()),
+                                                block(vec![], LiteralExpr::new(Literal::I64(0)).into()))
+                                         .into())
+                  .into()),
+    );
+    builder.codegen_function(&pick_fn);
+
+    // a loop that either skips to its next iteration, or ends
+    let skip_fn = Function::new(
+        Symbol::new("skip"),
+        vec![b.clone(), n.clone()],
+        PrimitiveType::Void,
+        block(vec![
+            ForExpr::new(false,
+                         i.clone(),
+                         RangeExpr::new(LiteralExpr::new(Literal::I64(0)), VariableExpr::new(n.clone())),
+                         block(vec![IfExpr::new(VariableExpr::new(b.clone()),
+                                                block(vec![ContinueExpr::new().into()], VoidExpr::new().into()),
+                                                block(vec![], BreakExpr::new().into()))
+                                        .into()],
+                               VoidExpr::new().into()),
+                         ListType::new(PrimitiveType::I64))
+                .into(),
+        ], VoidExpr::new().into()),
+    );
+    builder.codegen_function(&skip_fn);
+
+    let ir = builder.dump_to_string();
+    assert!(ir.contains("br i1 %b, label %if.then, label %if.else"));
+    assert!(ir.contains("phi i64 [ %x, %if.then ], [ 0, %if.else ]"));
+    assert!(ir.contains("ret i64 %"));
+    assert!(ir.contains("br label %for.next"));
+    assert!(ir.contains("br label %for.end"));
+    assert!(ir.contains("%for.next ]"));
+    assert!(ir.contains("unreachable"));
+}
+
+#[test]
+fn block_return_values() {
+    let context = Context::new();
+    let mut builder = LLVMBuilder::new("test", context.clone());
+
+    let x = Variable::new(Symbol::new("x"), PrimitiveType::I64);
+    let y = Variable::new(Symbol::new("y"), PrimitiveType::I64);
+
+    // fn add(x i64, y i64) i64 -> x + y
+    let add_fn = |name: &str, op: &Function| -> Function {
+        Function::new(
+            Symbol::new(name),
+            vec![x.clone(), y.clone()],
+            PrimitiveType::I64,
+            BlockExpr::new(
+                Exprs::new(), // prelude
+                Exprs::new(), // epilogue
+                Exprs::new(), // body
+                CallExpr::new(
+                    ItemExpr::new(op.clone()),
+                    vec![VariableExpr::new(x.clone()), VariableExpr::new(y.clone())],
+                ), // ret
+                FunctionTable::new(), // functions
+                ModuleTable::new(), // modules
+                TypeTable::new(), // types
+            )
+        )
+    };
+    builder.codegen_prelude();
+    builder.codegen_function(&add_fn("add", &context.prelude.add_i64_fn));
+    builder.codegen_function(&add_fn("wrapping_add", &context.prelude.wrapping_add_i64_fn));
+
+    let ir = builder.dump_to_string();
+    assert!(ir.contains("define i64 @add(i64 %x, i64 %y)"));
+    assert!(ir.contains("define i64 @wrapping_add(i64 %x, i64 %y)"));
+    // an addition is checked for overflow, unless it wraps
+    assert!(ir.contains("%0 = call { i64, i1 } @llvm.sadd.with.overflow.i64(i64 %x, i64 %y)"));
+    assert!(ir.contains("%1 = extractvalue { i64, i1 } %0, 0"));
+    assert!(ir.contains("%2 = extractvalue { i64, i1 } %0, 1"));
+    assert!(ir.contains("br i1 %2, label %arith.trap, label %arith.ok"));
+    assert!(ir.contains("ret i64 %1"));
+    assert!(ir.contains("%0 = add i64 %x, %y"));
+    assert!(ir.contains("ret i64 %0"));
+}
+
+#[test]
+#[should_panic(expected = "mismatched return type in function 'is_zero'")]
+fn block_return_type_mismatch() {
+    let context = Context::new();
+    let mut builder = LLVMBuilder::new("test", context.clone());
+
+    let is_zero_fn = Function::new(
+        Symbol::new("is_zero"),
+        Variables::new(),
+        PrimitiveType::Bool,
+        BlockExpr::new(
+            Exprs::new(), // prelude
+            Exprs::new(), // epilogue
+            Exprs::new(), // body
+            LiteralExpr::new(Literal::I64(0)), // ret
+            FunctionTable::new(), // functions
+            ModuleTable::new(), // modules
+            TypeTable::new(), // types
+        )
+    );
+    builder.codegen_function(&is_zero_fn);
+}
+
+#[test]
+fn inline_arithmetic() {
+    let context = Context::new();
+    let mut builder = LLVMBuilder::new("test", context.clone());
+
+    let op_fn = |name: &str, op: &Function, ty: PrimitiveType, ret: PrimitiveType| -> Function {
+        let x = Variable::new(Symbol::new("x"), ty.clone());
+        let y = Variable::new(Symbol::new("y"), ty);
+        Function::new(
+            Symbol::new(name),
+            vec![x.clone(), y.clone()],
+            ret,
+            BlockExpr::new(
+                Exprs::new(), // prelude
+                Exprs::new(), // epilogue
+                Exprs::new(), // body
+                CallExpr::new(ItemExpr::new(op.clone()),
+                              vec![VariableExpr::new(x.clone()), VariableExpr::new(y.clone())]), // ret
+                FunctionTable::new(), // functions
+                ModuleTable::new(), // modules
+                TypeTable::new(), // types
+            )
+        )
+    };
+
+    // integers are divided, and compared, with the signedness of their type
+    let prelude = &context.prelude;
+    builder.codegen_function(&op_fn("div_i64", &prelude.div_i64_fn, PrimitiveType::I64, PrimitiveType::I64));
+    builder.codegen_function(&op_fn("div_u32", &prelude.div_u32_fn, PrimitiveType::U32, PrimitiveType::U32));
+    builder.codegen_function(&op_fn("mul_f64", &prelude.mul_f64_fn, PrimitiveType::F64, PrimitiveType::F64));
+    builder.codegen_function(&op_fn("sub_u8", &prelude.sub_u8_fn, PrimitiveType::U8, PrimitiveType::U8));
+    builder.codegen_function(&op_fn("lt_i32", &prelude.lt_i32_fn, PrimitiveType::I32, PrimitiveType::Bool));
+    builder.codegen_function(&op_fn("lt_u16", &prelude.lt_u16_fn, PrimitiveType::U16, PrimitiveType::Bool));
+    builder.codegen_function(&op_fn("ne_f32", &prelude.ne_f32_fn, PrimitiveType::F32, PrimitiveType::Bool));
+
+    let ir = builder.dump_to_string();
+    assert!(ir.contains("sdiv i64 %x, %y"));
+    assert!(ir.contains("udiv i32 %x, %y"));
+    assert!(ir.contains("fmul double %x, %y"));
+    assert!(ir.contains("call { i8, i1 } @llvm.usub.with.overflow.i8(i8 %x, i8 %y)"));
+    assert!(ir.contains("icmp slt i32 %x, %y"));
+    assert!(ir.contains("icmp ult i16 %x, %y"));
+    assert!(ir.contains("fcmp une float %x, %y"));
+    assert!(!ir.contains("@__libprelude__"));
+}
+
+#[test]
+fn integer_overflow() {
+    let mut context = Context::new();
+
+    // a call that overflows is located in the runtime error
+    let add = Identifier::id();
+    context.span_table.insert(add.clone(), Span::new("main.rv", 3, 7, 3, 12));
+    let mut builder = LLVMBuilder::new("test", context.clone());
+
+    let op_fn = |name: &str, identifier: Identifier, op: &Function| -> Function {
+        let x = Variable::new(Symbol::new("x"), op.formals[0].ty.clone());
+        let y = Variable::new(Symbol::new("y"), op.formals[1].ty.clone());
+        Function::new(
+            Symbol::new(name),
+            vec![x.clone(), y.clone()],
+            op.ret.clone(),
+            BlockExpr::new(
+                Exprs::new(), // prelude
+                Exprs::new(), // epilogue
+                Exprs::new(), // body
+                CallExpr::new_with_id(identifier,
+                                      ItemExpr::new(op.clone()),
+                                      vec![VariableExpr::new(x.clone()), VariableExpr::new(y.clone())]), // ret
+                FunctionTable::new(), // functions
+                ModuleTable::new(), // modules
+                TypeTable::new(), // types
+            )
+        )
+    };
+
+    let prelude = &context.prelude;
+    builder.codegen_function(&op_fn("add_i64", add, &prelude.add_i64_fn));
+    builder.codegen_function(&op_fn("div_u32", Identifier::id(), &prelude.div_u32_fn));
+    builder.codegen_function(&op_fn("wrapping_mul_i32", Identifier::id(), &prelude.wrapping_mul_i32_fn));
+    builder.codegen_function(&op_fn("saturating_sub_u16", Identifier::id(), &prelude.saturating_sub_u16_fn));
+    builder.codegen_function(&op_fn("checked_div_i8", Identifier::id(), &prelude.checked_div_i8_fn));
+    builder.codegen_function(&op_fn("addeq_u64", Identifier::id(), &prelude.addeq_u64_fn));
+
+    let ir = builder.dump_to_string();
+    // arithmetic traps by default
+    assert!(ir.contains("call { i64, i1 } @llvm.sadd.with.overflow.i64(i64 %x, i64 %y)"));
+    assert!(ir.contains("c\"main.rv:3:7: attempt to add with overflow\\00\""));
+    assert!(ir.contains("c\"attempt to divide by zero\\00\""));
+    assert!(ir.contains("call void @__libruntime__arithmetic_error"));
+    assert!(ir.contains("udiv i32 %x, %y"));
+    // other operators wrap, saturate, or return nil instead
+    assert!(ir.contains("mul i32 %x, %y"));
+    assert!(ir.contains("call { i16, i1 } @llvm.usub.with.overflow.i16(i16 %x, i16 %y)"));
+    assert!(ir.contains("select i1 %2, i16 0, i16 %1"));
+    assert!(ir.contains("sdiv i8 %x, %"));
+    assert!(ir.contains("select i1 %"));
+    // assignment operators update the referenced value in place
+    assert!(ir.contains("load i64, i64* %x"));
+    assert!(ir.contains("call { i64, i1 } @llvm.uadd.with.overflow.i64(i64 %0, i64 %y)"));
+    assert!(!ir.contains("@__libprelude__"));
+}
+
 fn simple_process<E: Into<Expr>>(body: Vec<E>) -> (ProcessExpr, ProcessJoinExpr) {
     let process = ProcessExpr::new(BlockExpr::new(
             Exprs::new(), // prelude
@@ -38,17 +38,22 @@ impl Precedence {
 pub enum Token {
     // Reserved keywords
     As(Span),
+    Do(Span),
     Else(Span),
     Extern(Span),
     Expose(Span),
     For(Span),
+    Forall(Span),
     Func(Span),
     If(Span),
     Import(Span),
     In(Span),
+    Match(Span),
     Module(Span),
     Mut(Span),
+    Nil(Span),
     Ref(Span),
+    Then(Span),
     Type(Span),
 
     // Operators
@@ -70,6 +75,7 @@ pub enum Token {
     PushPop(Span),
     Sub(Span),
     SubEq(Span),
+    Unwrap(Span),
 
     // Symbols
     BraceL(Span),
@@ -85,6 +91,7 @@ pub enum Token {
     ParenR(Span),
     Pipe(Span),
     Question(Span),
+    QuestionQuestion(Span),
     SemiColon(Span),
     Whitespace(Span),
     WhitespaceNewline(Span),
@@ -125,12 +132,16 @@ impl Token {
                 // Reserved keywords
                 if capture.name("As").is_some() {
                     Token::As(span)
+                } else if capture.name("Do").is_some() {
+                    Token::Do(span)
                 } else if capture.name("Else").is_some() {
                     Token::Else(span)
                 } else if capture.name("Expose").is_some() {
                     Token::Expose(span)
                 } else if capture.name("Extern").is_some() {
                     Token::Extern(span)
+                } else if capture.name("Forall").is_some() {
+                    Token::Forall(span)
                 } else if capture.name("For").is_some() {
                     Token::For(span)
                 } else if capture.name("Func").is_some() {
@@ -141,12 +152,18 @@ impl Token {
                     Token::Import(span)
                 } else if capture.name("In").is_some() {
                     Token::In(span)
+                } else if capture.name("Match").is_some() {
+                    Token::Match(span)
                 } else if capture.name("Module").is_some() {
                     Token::Module(span)
                 } else if capture.name("Mut").is_some() {
                     Token::Mut(span)
+                } else if capture.name("Nil").is_some() {
+                    Token::Nil(span)
                 } else if capture.name("Ref").is_some() {
                     Token::Ref(span)
+                } else if capture.name("Then").is_some() {
+                    Token::Then(span)
                 } else if capture.name("Type").is_some() {
                     Token::Type(span)
                 }
@@ -178,6 +195,8 @@ impl Token {
                     Token::Pipe(span)
                 } else if capture.name("Question").is_some() {
                     Token::Question(span)
+                } else if capture.name("QuestionQuestion").is_some() {
+                    Token::QuestionQuestion(span)
                 } else if capture.name("SemiColon").is_some() {
                     Token::SemiColon(span)
                 } else if capture.name("Whitespace").is_some() {
@@ -201,6 +220,8 @@ impl Token {
                     Token::DivEq(span)
                 } else if capture.name("Equal").is_some() {
                     Token::Equal(span)
+                } else if capture.name("NotEqual").is_some() {
+                    Token::NotEqual(span)
                 } else if capture.name("GreaterThan").is_some() {
                     Token::GreaterThan(span)
                 } else if capture.name("GreaterThanEq").is_some() {
@@ -221,6 +242,8 @@ impl Token {
                     Token::Sub(span)
                 } else if capture.name("SubEq").is_some() {
                     Token::SubEq(span)
+                } else if capture.name("Unwrap").is_some() {
+                    Token::Unwrap(span)
                 }
 
                 // Literals
@@ -279,17 +302,22 @@ impl Token {
 
             // Reserved keywords
             r"(?P<As>as)|",
+            r"(?P<Do>do)|",
             r"(?P<Else>else)|",
             r"(?P<Expose>expose)|",
             r"(?P<Extern>extern)|",
+            r"(?P<Forall>forall)|",
             r"(?P<For>for)|",
             r"(?P<Func>fn)|",
             r"(?P<If>if)|",
             r"(?P<Import>import)|",
             r"(?P<In>in)|",
+            r"(?P<Match>match)|",
             r"(?P<Module>module)|",
             r"(?P<Mut>mut)|",
+            r"(?P<Nil>nil)|",
             r"(?P<Ref>ref)|",
+            r"(?P<Then>then)|",
             r"(?P<Type>type)|",
 
             // Symbols
@@ -305,6 +333,7 @@ impl Token {
             r"(?P<ParenL>\()|",
             r"(?P<ParenR>\))|",
             r"(?P<Pipe>\|)|",
+            r"(?P<QuestionQuestion>\?\?)|",
             r"(?P<Question>\?)|",
             r"(?P<SemiColon>;)|",
             r"(?P<Whitespace>( |\t))|",
@@ -319,6 +348,7 @@ impl Token {
             r"(?P<DivEq>/=)|",
             r"(?P<Equal>=)|",
             r"(?P<NotEqual>!=)|",
+            r"(?P<Unwrap>!)|",
             r"(?P<LessThan><)|",
             r"(?P<LessThanEq><=)|",
             r"(?P<GreaterThan>>)|",
@@ -352,18 +382,21 @@ impl Token {
         use self::Token::*;
 
         match *self {
-            Or(..) => Precedence(1),
-            And(..) => Precedence(2),
+            PushPop(..) => Precedence(1),
+            DotDot(..) => Precedence(2),
+            Or(..) => Precedence(3),
+            And(..) => Precedence(4),
             Equal(..) |
             NotEqual(..) |
             LessThan(..) |
             LessThanEq(..) |
             GreaterThan(..) |
-            GreaterThanEq(..) => Precedence(3),
+            GreaterThanEq(..) => Precedence(5),
+            QuestionQuestion(..) => Precedence(6),
             Add(..) |
-            Sub(..) => Precedence(4),
+            Sub(..) => Precedence(7),
             Mul(..) |
-            Div(..) => Precedence(5),
+            Div(..) => Precedence(8),
             _ => Precedence::lowest(),
         }
     }
@@ -384,17 +417,22 @@ impl Spanned for Token {
 
             // Reserved keywords
             As(ref span, ..) => span,
+            Do(ref span, ..) => span,
             Else(ref span, ..) => span,
             Extern(ref span, ..) => span,
             Expose(ref span, ..) => span,
             For(ref span, ..) => span,
+            Forall(ref span, ..) => span,
             Func(ref span, ..) => span,
             If(ref span, ..) => span,
             Import(ref span, ..) => span,
             In(ref span, ..) => span,
+            Match(ref span, ..) => span,
             Module(ref span, ..) => span,
             Mut(ref span, ..) => span,
+            Nil(ref span, ..) => span,
             Ref(ref span, ..) => span,
+            Then(ref span, ..) => span,
             Type(ref span, ..) => span,
 
             // Symbols
@@ -411,6 +449,7 @@ impl Spanned for Token {
             ParenR(ref span, ..) => span,
             Pipe(ref span, ..) => span,
             Question(ref span, ..) => span,
+            QuestionQuestion(ref span, ..) => span,
             SemiColon(ref span, ..) => span,
             Whitespace(ref span, ..) => span,
             WhitespaceNewline(ref span, ..) => span,
@@ -434,6 +473,7 @@ impl Spanned for Token {
             PushPop(ref span, ..) => span,
             Sub(ref span, ..) => span,
             SubEq(ref span, ..) => span,
+            Unwrap(ref span, ..) => span,
 
             // Literals
             Char(_, ref span, ..) => span,
@@ -457,17 +497,22 @@ impl Spanned for Token {
 
             // Reserved keywords
             As(ref mut span, ..) => span,
+            Do(ref mut span, ..) => span,
             Else(ref mut span, ..) => span,
             Extern(ref mut span, ..) => span,
             Expose(ref mut span, ..) => span,
             For(ref mut span, ..) => span,
+            Forall(ref mut span, ..) => span,
             Func(ref mut span, ..) => span,
             If(ref mut span, ..) => span,
             Import(ref mut span, ..) => span,
             In(ref mut span, ..) => span,
+            Match(ref mut span, ..) => span,
             Module(ref mut span, ..) => span,
             Mut(ref mut span, ..) => span,
+            Nil(ref mut span, ..) => span,
             Ref(ref mut span, ..) => span,
+            Then(ref mut span, ..) => span,
             Type(ref mut span, ..) => span,
 
             // Symbols
@@ -484,6 +529,7 @@ impl Spanned for Token {
             ParenR(ref mut span, ..) => span,
             Pipe(ref mut span, ..) => span,
             Question(ref mut span, ..) => span,
+            QuestionQuestion(ref mut span, ..) => span,
             SemiColon(ref mut span, ..) => span,
             Whitespace(ref mut span, ..) => span,
             WhitespaceNewline(ref mut span, ..) => span,
@@ -507,6 +553,7 @@ impl Spanned for Token {
             PushPop(ref mut span, ..) => span,
             Sub(ref mut span, ..) => span,
             SubEq(ref mut span, ..) => span,
+            Unwrap(ref mut span, ..) => span,
 
             // Literals
             Char(_, ref mut span, ..) => span,
@@ -532,17 +579,22 @@ impl fmt::Display for Token {
 
             // Reserved keywords
             As(..) => write!(formatter, "as"),
+            Do(..) => write!(formatter, "do"),
             Else(..) => write!(formatter, "else"),
             Extern(..) => write!(formatter, "extern"),
             Expose(..) => write!(formatter, "expose"),
             For(..) => write!(formatter, "for"),
+            Forall(..) => write!(formatter, "forall"),
             Func(..) => write!(formatter, "fn"),
             If(..) => write!(formatter, "if"),
             Import(..) => write!(formatter, "import"),
             In(..) => write!(formatter, "in"),
+            Match(..) => write!(formatter, "match"),
             Module(..) => write!(formatter, "module"),
             Mut(..) => write!(formatter, "mut"),
+            Nil(..) => write!(formatter, "nil"),
             Ref(..) => write!(formatter, "ref"),
+            Then(..) => write!(formatter, "then"),
             Type(..) => write!(formatter, "type"),
 
             // Symbols
@@ -559,6 +611,7 @@ impl fmt::Display for Token {
             ParenR(..) => write!(formatter, ")"),
             Pipe(..) => write!(formatter, "|"),
             Question(..) => write!(formatter, "?"),
+            QuestionQuestion(..) => write!(formatter, "??"),
             SemiColon(..) => write!(formatter, ";"),
             Whitespace(..) => write!(formatter, " "),
             WhitespaceNewline(..) => write!(formatter, "\n"),
@@ -582,6 +635,7 @@ impl fmt::Display for Token {
             PushPop(..) => write!(formatter, "<-"),
             Sub(..) => write!(formatter, "-"),
             SubEq(..) => write!(formatter, "-="),
+            Unwrap(..) => write!(formatter, "!"),
 
             // Literals
             Char(ref character, ..) => write!(formatter, "{}", character),
@@ -169,4 +169,67 @@ fn tokenise_writeln() {
             Token::ParenR(Span::new("", 1, 27, 1, 27))
         ]
     );
-}
\ No newline at end of file
+}
+
+#[test]
+fn tokenise_match() {
+    assert_eq!(
+        Token::tokenise("", "match x {\n  Some a -> a\n  Nil -> 0\n}"),
+        vec![
+            Token::Match(Span::new("", 1, 1, 1, 5)),
+            Token::Ident("x".to_string(), Span::new("", 1, 7, 1, 7)),
+            Token::BraceL(Span::new("", 1, 9, 1, 9)),
+            Token::WhitespaceNewline(Span::new("", 1, 10, 1, 10)),
+            Token::Ident("Some".to_string(), Span::new("", 2, 3, 2, 6)),
+            Token::Ident("a".to_string(), Span::new("", 2, 8, 2, 8)),
+            Token::LambdaR(Span::new("", 2, 10, 2, 11)),
+            Token::Ident("a".to_string(), Span::new("", 2, 13, 2, 13)),
+            Token::WhitespaceNewline(Span::new("", 2, 14, 2, 14)),
+            Token::Ident("Nil".to_string(), Span::new("", 3, 3, 3, 5)),
+            Token::LambdaR(Span::new("", 3, 7, 3, 8)),
+            Token::Int(0, Span::new("", 3, 10, 3, 10)),
+            Token::WhitespaceNewline(Span::new("", 3, 11, 3, 11)),
+            Token::BraceR(Span::new("", 4, 1, 4, 1))
+        ]
+    );
+}
+
+#[test]
+fn tokenise_optional() {
+    assert_eq!(
+        Token::tokenise("", "x! ?? nil != y?"),
+        vec![
+            Token::Ident("x".to_string(), Span::new("", 1, 1, 1, 1)),
+            Token::Unwrap(Span::new("", 1, 2, 1, 2)),
+            Token::QuestionQuestion(Span::new("", 1, 4, 1, 5)),
+            Token::Nil(Span::new("", 1, 7, 1, 9)),
+            Token::NotEqual(Span::new("", 1, 11, 1, 12)),
+            Token::Ident("y".to_string(), Span::new("", 1, 14, 1, 14)),
+            Token::Question(Span::new("", 1, 15, 1, 15))
+        ]
+    );
+}
+#[test]
+fn tokenise_do_then() {
+    assert_eq!(
+        Token::tokenise("", "do 1 then 2"),
+        vec![
+            Token::Do(Span::new("", 1, 1, 1, 2)),
+            Token::Int(1, Span::new("", 1, 4, 1, 4)),
+            Token::Then(Span::new("", 1, 6, 1, 9)),
+            Token::Int(2, Span::new("", 1, 11, 1, 11)),
+        ]
+    );
+}
+
+#[test]
+fn tokenise_for_forall() {
+    assert_eq!(
+        Token::tokenise("", "for forall in"),
+        vec![
+            Token::For(Span::new("", 1, 1, 1, 3)),
+            Token::Forall(Span::new("", 1, 5, 1, 10)),
+            Token::In(Span::new("", 1, 12, 1, 13)),
+        ]
+    );
+}
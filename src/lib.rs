@@ -7,11 +7,16 @@ pub mod identifier;
 pub mod lexer;
 pub mod parser;
 pub mod ast;
-// pub mod air;
+pub mod air;
 // pub mod resolver;
+pub mod typechecker;
+pub mod borrowchecker;
+pub mod noir;
+pub mod normaliser;
+pub mod builder;
 
 pub mod compile;
 pub use compile::compile;
 
 pub mod repl;
-pub use repl::repl;
\ No newline at end of file
+pub use repl::repl;
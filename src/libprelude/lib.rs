@@ -1,8 +1,15 @@
 //! Prelude
+//!
+//! The functions of the prelude are only called by compiled programs, which
+//! pass them valid strings. So the functions are not marked `unsafe`, and
+//! those that read their strings allow `clippy::not_unsafe_ptr_arg_deref`.
 
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::process;
+use std::ptr;
 
-/// Stdout
+// Stdout
 
 #[no_mangle]
 #[allow(non_snake_case)]
@@ -53,7 +60,7 @@ pub extern "C" fn __libprelude__write_i64(x: i64) {
 }
 
 #[no_mangle]
-#[allow(non_snake_case)]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
 pub extern "C" fn __libprelude__write_string(string: *const i8) {
     print!("{}", unsafe { CStr::from_ptr(string).to_str().unwrap() });
 }
@@ -137,7 +144,7 @@ pub extern "C" fn __libprelude__writeln_i64(x: i64) {
 }
 
 #[no_mangle]
-#[allow(non_snake_case)]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
 pub extern "C" fn __libprelude__writeln_string(string: *const i8) {
     println!("{}", unsafe { CStr::from_ptr(string).to_str().unwrap() });
 }
@@ -172,7 +179,7 @@ pub extern "C" fn __libprelude__writeln_usize(x: usize) {
     println!("{}", x);
 }
 
-/// Arithmetic
+// Arithmetic
 
 #[no_mangle]
 #[allow(non_snake_case)]
@@ -189,121 +196,325 @@ pub extern "C" fn __libprelude__add_f64(x: f64, y: f64) -> f64 {
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__add_i8(x: i8, y: i8) -> i8 {
-    x + y
+    x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__add_i16(x: i16, y: i16) -> i16 {
-    x + y
+    x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__add_i32(x: i32, y: i32) -> i32 {
-    x + y
+    x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__add_i64(x: i64, y: i64) -> i64 {
-    x + y
+    x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__add_u8(x: u8, y: u8) -> u8 {
-    x + y
+    x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__add_u16(x: u16, y: u16) -> u16 {
-    x + y
+    x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__add_u32(x: u32, y: u32) -> u32 {
-    x + y
+    x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__add_u64(x: u64, y: u64) -> u64 {
-    x + y
+    x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__add_usize(x: usize, y: usize) -> usize {
-    x + y
+    x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__addeq_f32(x: &mut f32, y: f32) {
-    *x = *x + y
+    *x += y
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__addeq_f64(x: &mut f64, y: f64) {
-    *x = *x + y
+    *x += y
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__addeq_i8(x: &mut i8, y: i8) {
-    *x = *x + y
+    *x = x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__addeq_i16(x: &mut i16, y: i16) {
-    *x = *x + y
+    *x = x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__addeq_i32(x: &mut i32, y: i32) {
-    *x = *x + y
+    *x = x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__addeq_i64(x: &mut i64, y: i64) {
-    *x = *x + y
+    *x = x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__addeq_u8(x: &mut u8, y: u8) {
-    *x = *x + y
+    *x = x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__addeq_u16(x: &mut u16, y: u16) {
-    *x = *x + y
+    *x = x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__addeq_u32(x: &mut u32, y: u32) {
-    *x = *x + y
+    *x = x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__addeq_u64(x: &mut u64, y: u64) {
-    *x = *x + y
+    *x = x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__addeq_usize(x: &mut usize, y: usize) {
-    *x = *x + y
+    *x = x.checked_add(y).unwrap_or_else(|| trap("attempt to add with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__div_f32(x: f32, y: f32) -> f32 {
+    x / y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__div_f64(x: f64, y: f64) -> f64 {
+    x / y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__div_i8(x: i8, y: i8) -> i8 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__div_i16(x: i16, y: i16) -> i16 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__div_i32(x: i32, y: i32) -> i32 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__div_i64(x: i64, y: i64) -> i64 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__div_u8(x: u8, y: u8) -> u8 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__div_u16(x: u16, y: u16) -> u16 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__div_u32(x: u32, y: u32) -> u32 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__div_u64(x: u64, y: u64) -> u64 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__div_usize(x: usize, y: usize) -> usize {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__diveq_f32(x: &mut f32, y: f32) {
+    *x /= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__diveq_f64(x: &mut f64, y: f64) {
+    *x /= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__diveq_i8(x: &mut i8, y: i8) {
+    *x = if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__diveq_i16(x: &mut i16, y: i16) {
+    *x = if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__diveq_i32(x: &mut i32, y: i32) {
+    *x = if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__diveq_i64(x: &mut i64, y: i64) {
+    *x = if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__diveq_u8(x: &mut u8, y: u8) {
+    *x = if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__diveq_u16(x: &mut u16, y: u16) {
+    *x = if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__diveq_u32(x: &mut u32, y: u32) {
+    *x = if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__diveq_u64(x: &mut u64, y: u64) {
+    *x = if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__diveq_usize(x: &mut usize, y: usize) {
+    *x = if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or_else(|| trap("attempt to divide with overflow"))
+    }
 }
 
 #[no_mangle]
@@ -321,53 +532,1512 @@ pub extern "C" fn __libprelude__mul_f64(x: f64, y: f64) -> f64 {
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__mul_i8(x: i8, y: i8) -> i8 {
-    x * y
+    x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__mul_i16(x: i16, y: i16) -> i16 {
-    x * y
+    x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__mul_i32(x: i32, y: i32) -> i32 {
-    x * y
+    x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__mul_i64(x: i64, y: i64) -> i64 {
-    x * y
+    x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__mul_u8(x: u8, y: u8) -> u8 {
-    x * y
+    x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__mul_u16(x: u16, y: u16) -> u16 {
-    x * y
+    x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__mul_u32(x: u32, y: u32) -> u32 {
-    x * y
+    x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__mul_u64(x: u64, y: u64) -> u64 {
-    x * y
+    x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn __libprelude__mul_usize(x: usize, y: usize) -> usize {
-    x * y
-}
\ No newline at end of file
+    x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__muleq_f32(x: &mut f32, y: f32) {
+    *x *= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__muleq_f64(x: &mut f64, y: f64) {
+    *x *= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__muleq_i8(x: &mut i8, y: i8) {
+    *x = x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__muleq_i16(x: &mut i16, y: i16) {
+    *x = x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__muleq_i32(x: &mut i32, y: i32) {
+    *x = x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__muleq_i64(x: &mut i64, y: i64) {
+    *x = x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__muleq_u8(x: &mut u8, y: u8) {
+    *x = x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__muleq_u16(x: &mut u16, y: u16) {
+    *x = x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__muleq_u32(x: &mut u32, y: u32) {
+    *x = x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__muleq_u64(x: &mut u64, y: u64) {
+    *x = x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__muleq_usize(x: &mut usize, y: usize) {
+    *x = x.checked_mul(y).unwrap_or_else(|| trap("attempt to multiply with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__sub_f32(x: f32, y: f32) -> f32 {
+    x - y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__sub_f64(x: f64, y: f64) -> f64 {
+    x - y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__sub_i8(x: i8, y: i8) -> i8 {
+    x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__sub_i16(x: i16, y: i16) -> i16 {
+    x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__sub_i32(x: i32, y: i32) -> i32 {
+    x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__sub_i64(x: i64, y: i64) -> i64 {
+    x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__sub_u8(x: u8, y: u8) -> u8 {
+    x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__sub_u16(x: u16, y: u16) -> u16 {
+    x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__sub_u32(x: u32, y: u32) -> u32 {
+    x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__sub_u64(x: u64, y: u64) -> u64 {
+    x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__sub_usize(x: usize, y: usize) -> usize {
+    x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__subeq_f32(x: &mut f32, y: f32) {
+    *x -= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__subeq_f64(x: &mut f64, y: f64) {
+    *x -= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__subeq_i8(x: &mut i8, y: i8) {
+    *x = x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__subeq_i16(x: &mut i16, y: i16) {
+    *x = x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__subeq_i32(x: &mut i32, y: i32) {
+    *x = x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__subeq_i64(x: &mut i64, y: i64) {
+    *x = x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__subeq_u8(x: &mut u8, y: u8) {
+    *x = x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__subeq_u16(x: &mut u16, y: u16) {
+    *x = x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__subeq_u32(x: &mut u32, y: u32) {
+    *x = x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__subeq_u64(x: &mut u64, y: u64) {
+    *x = x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__subeq_usize(x: &mut usize, y: usize) {
+    *x = x.checked_sub(y).unwrap_or_else(|| trap("attempt to subtract with overflow"))
+}
+
+// Comparison
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__eq_f32(x: f32, y: f32) -> bool {
+    x == y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__eq_f64(x: f64, y: f64) -> bool {
+    x == y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__eq_i8(x: i8, y: i8) -> bool {
+    x == y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__eq_i16(x: i16, y: i16) -> bool {
+    x == y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__eq_i32(x: i32, y: i32) -> bool {
+    x == y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__eq_i64(x: i64, y: i64) -> bool {
+    x == y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__eq_u8(x: u8, y: u8) -> bool {
+    x == y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__eq_u16(x: u16, y: u16) -> bool {
+    x == y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__eq_u32(x: u32, y: u32) -> bool {
+    x == y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__eq_u64(x: u64, y: u64) -> bool {
+    x == y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__eq_usize(x: usize, y: usize) -> bool {
+    x == y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ne_f32(x: f32, y: f32) -> bool {
+    x != y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ne_f64(x: f64, y: f64) -> bool {
+    x != y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ne_i8(x: i8, y: i8) -> bool {
+    x != y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ne_i16(x: i16, y: i16) -> bool {
+    x != y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ne_i32(x: i32, y: i32) -> bool {
+    x != y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ne_i64(x: i64, y: i64) -> bool {
+    x != y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ne_u8(x: u8, y: u8) -> bool {
+    x != y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ne_u16(x: u16, y: u16) -> bool {
+    x != y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ne_u32(x: u32, y: u32) -> bool {
+    x != y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ne_u64(x: u64, y: u64) -> bool {
+    x != y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ne_usize(x: usize, y: usize) -> bool {
+    x != y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__lt_f32(x: f32, y: f32) -> bool {
+    x < y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__lt_f64(x: f64, y: f64) -> bool {
+    x < y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__lt_i8(x: i8, y: i8) -> bool {
+    x < y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__lt_i16(x: i16, y: i16) -> bool {
+    x < y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__lt_i32(x: i32, y: i32) -> bool {
+    x < y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__lt_i64(x: i64, y: i64) -> bool {
+    x < y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__lt_u8(x: u8, y: u8) -> bool {
+    x < y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__lt_u16(x: u16, y: u16) -> bool {
+    x < y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__lt_u32(x: u32, y: u32) -> bool {
+    x < y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__lt_u64(x: u64, y: u64) -> bool {
+    x < y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__lt_usize(x: usize, y: usize) -> bool {
+    x < y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__le_f32(x: f32, y: f32) -> bool {
+    x <= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__le_f64(x: f64, y: f64) -> bool {
+    x <= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__le_i8(x: i8, y: i8) -> bool {
+    x <= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__le_i16(x: i16, y: i16) -> bool {
+    x <= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__le_i32(x: i32, y: i32) -> bool {
+    x <= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__le_i64(x: i64, y: i64) -> bool {
+    x <= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__le_u8(x: u8, y: u8) -> bool {
+    x <= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__le_u16(x: u16, y: u16) -> bool {
+    x <= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__le_u32(x: u32, y: u32) -> bool {
+    x <= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__le_u64(x: u64, y: u64) -> bool {
+    x <= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__le_usize(x: usize, y: usize) -> bool {
+    x <= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__gt_f32(x: f32, y: f32) -> bool {
+    x > y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__gt_f64(x: f64, y: f64) -> bool {
+    x > y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__gt_i8(x: i8, y: i8) -> bool {
+    x > y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__gt_i16(x: i16, y: i16) -> bool {
+    x > y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__gt_i32(x: i32, y: i32) -> bool {
+    x > y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__gt_i64(x: i64, y: i64) -> bool {
+    x > y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__gt_u8(x: u8, y: u8) -> bool {
+    x > y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__gt_u16(x: u16, y: u16) -> bool {
+    x > y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__gt_u32(x: u32, y: u32) -> bool {
+    x > y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__gt_u64(x: u64, y: u64) -> bool {
+    x > y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__gt_usize(x: usize, y: usize) -> bool {
+    x > y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ge_f32(x: f32, y: f32) -> bool {
+    x >= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ge_f64(x: f64, y: f64) -> bool {
+    x >= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ge_i8(x: i8, y: i8) -> bool {
+    x >= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ge_i16(x: i16, y: i16) -> bool {
+    x >= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ge_i32(x: i32, y: i32) -> bool {
+    x >= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ge_i64(x: i64, y: i64) -> bool {
+    x >= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ge_u8(x: u8, y: u8) -> bool {
+    x >= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ge_u16(x: u16, y: u16) -> bool {
+    x >= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ge_u32(x: u32, y: u32) -> bool {
+    x >= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ge_u64(x: u64, y: u64) -> bool {
+    x >= y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__ge_usize(x: usize, y: usize) -> bool {
+    x >= y
+}
+
+// Logical
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__and_bool(x: bool, y: bool) -> bool {
+    x && y
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__or_bool(x: bool, y: bool) -> bool {
+    x || y
+}
+
+// Strings
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__concat_string_bool(x: *const i8, y: bool) -> *const i8 {
+    concat(x, y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__concat_string_char(x: *const i8, y: char) -> *const i8 {
+    concat(x, y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__concat_string_f32(x: *const i8, y: f32) -> *const i8 {
+    concat(x, y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__concat_string_f64(x: *const i8, y: f64) -> *const i8 {
+    concat(x, y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__concat_string_i8(x: *const i8, y: i8) -> *const i8 {
+    concat(x, y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__concat_string_i16(x: *const i8, y: i16) -> *const i8 {
+    concat(x, y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__concat_string_i32(x: *const i8, y: i32) -> *const i8 {
+    concat(x, y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__concat_string_i64(x: *const i8, y: i64) -> *const i8 {
+    concat(x, y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libprelude__concat_string_string(x: *const i8, y: *const i8) -> *const i8 {
+    concat(x, unsafe { CStr::from_ptr(y).to_string_lossy() })
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__concat_string_u8(x: *const i8, y: u8) -> *const i8 {
+    concat(x, y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__concat_string_u16(x: *const i8, y: u16) -> *const i8 {
+    concat(x, y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__concat_string_u32(x: *const i8, y: u32) -> *const i8 {
+    concat(x, y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__concat_string_u64(x: *const i8, y: u64) -> *const i8 {
+    concat(x, y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__concat_string_usize(x: *const i8, y: usize) -> *const i8 {
+    concat(x, y)
+}
+
+// Wrapping arithmetic
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_add_i8(x: i8, y: i8) -> i8 {
+    x.wrapping_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_add_i16(x: i16, y: i16) -> i16 {
+    x.wrapping_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_add_i32(x: i32, y: i32) -> i32 {
+    x.wrapping_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_add_i64(x: i64, y: i64) -> i64 {
+    x.wrapping_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_add_u8(x: u8, y: u8) -> u8 {
+    x.wrapping_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_add_u16(x: u16, y: u16) -> u16 {
+    x.wrapping_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_add_u32(x: u32, y: u32) -> u32 {
+    x.wrapping_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_add_u64(x: u64, y: u64) -> u64 {
+    x.wrapping_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_add_usize(x: usize, y: usize) -> usize {
+    x.wrapping_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_sub_i8(x: i8, y: i8) -> i8 {
+    x.wrapping_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_sub_i16(x: i16, y: i16) -> i16 {
+    x.wrapping_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_sub_i32(x: i32, y: i32) -> i32 {
+    x.wrapping_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_sub_i64(x: i64, y: i64) -> i64 {
+    x.wrapping_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_sub_u8(x: u8, y: u8) -> u8 {
+    x.wrapping_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_sub_u16(x: u16, y: u16) -> u16 {
+    x.wrapping_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_sub_u32(x: u32, y: u32) -> u32 {
+    x.wrapping_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_sub_u64(x: u64, y: u64) -> u64 {
+    x.wrapping_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_sub_usize(x: usize, y: usize) -> usize {
+    x.wrapping_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_mul_i8(x: i8, y: i8) -> i8 {
+    x.wrapping_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_mul_i16(x: i16, y: i16) -> i16 {
+    x.wrapping_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_mul_i32(x: i32, y: i32) -> i32 {
+    x.wrapping_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_mul_i64(x: i64, y: i64) -> i64 {
+    x.wrapping_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_mul_u8(x: u8, y: u8) -> u8 {
+    x.wrapping_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_mul_u16(x: u16, y: u16) -> u16 {
+    x.wrapping_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_mul_u32(x: u32, y: u32) -> u32 {
+    x.wrapping_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_mul_u64(x: u64, y: u64) -> u64 {
+    x.wrapping_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_mul_usize(x: usize, y: usize) -> usize {
+    x.wrapping_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_div_i8(x: i8, y: i8) -> i8 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.wrapping_div(y)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_div_i16(x: i16, y: i16) -> i16 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.wrapping_div(y)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_div_i32(x: i32, y: i32) -> i32 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.wrapping_div(y)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_div_i64(x: i64, y: i64) -> i64 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.wrapping_div(y)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_div_u8(x: u8, y: u8) -> u8 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.wrapping_div(y)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_div_u16(x: u16, y: u16) -> u16 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.wrapping_div(y)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_div_u32(x: u32, y: u32) -> u32 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.wrapping_div(y)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_div_u64(x: u64, y: u64) -> u64 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.wrapping_div(y)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__wrapping_div_usize(x: usize, y: usize) -> usize {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.wrapping_div(y)
+    }
+}
+
+// Saturating arithmetic
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_add_i8(x: i8, y: i8) -> i8 {
+    x.saturating_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_add_i16(x: i16, y: i16) -> i16 {
+    x.saturating_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_add_i32(x: i32, y: i32) -> i32 {
+    x.saturating_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_add_i64(x: i64, y: i64) -> i64 {
+    x.saturating_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_add_u8(x: u8, y: u8) -> u8 {
+    x.saturating_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_add_u16(x: u16, y: u16) -> u16 {
+    x.saturating_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_add_u32(x: u32, y: u32) -> u32 {
+    x.saturating_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_add_u64(x: u64, y: u64) -> u64 {
+    x.saturating_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_add_usize(x: usize, y: usize) -> usize {
+    x.saturating_add(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_sub_i8(x: i8, y: i8) -> i8 {
+    x.saturating_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_sub_i16(x: i16, y: i16) -> i16 {
+    x.saturating_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_sub_i32(x: i32, y: i32) -> i32 {
+    x.saturating_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_sub_i64(x: i64, y: i64) -> i64 {
+    x.saturating_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_sub_u8(x: u8, y: u8) -> u8 {
+    x.saturating_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_sub_u16(x: u16, y: u16) -> u16 {
+    x.saturating_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_sub_u32(x: u32, y: u32) -> u32 {
+    x.saturating_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_sub_u64(x: u64, y: u64) -> u64 {
+    x.saturating_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_sub_usize(x: usize, y: usize) -> usize {
+    x.saturating_sub(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_mul_i8(x: i8, y: i8) -> i8 {
+    x.saturating_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_mul_i16(x: i16, y: i16) -> i16 {
+    x.saturating_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_mul_i32(x: i32, y: i32) -> i32 {
+    x.saturating_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_mul_i64(x: i64, y: i64) -> i64 {
+    x.saturating_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_mul_u8(x: u8, y: u8) -> u8 {
+    x.saturating_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_mul_u16(x: u16, y: u16) -> u16 {
+    x.saturating_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_mul_u32(x: u32, y: u32) -> u32 {
+    x.saturating_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_mul_u64(x: u64, y: u64) -> u64 {
+    x.saturating_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_mul_usize(x: usize, y: usize) -> usize {
+    x.saturating_mul(y)
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_div_i8(x: i8, y: i8) -> i8 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or(i8::MAX)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_div_i16(x: i16, y: i16) -> i16 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or(i16::MAX)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_div_i32(x: i32, y: i32) -> i32 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or(i32::MAX)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_div_i64(x: i64, y: i64) -> i64 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or(i64::MAX)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_div_u8(x: u8, y: u8) -> u8 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or(u8::MAX)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_div_u16(x: u16, y: u16) -> u16 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or(u16::MAX)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_div_u32(x: u32, y: u32) -> u32 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or(u32::MAX)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_div_u64(x: u64, y: u64) -> u64 {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or(u64::MAX)
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__saturating_div_usize(x: usize, y: usize) -> usize {
+    if y == 0 {
+        trap("attempt to divide by zero")
+    } else {
+        x.checked_div(y).unwrap_or(usize::MAX)
+    }
+}
+
+// Checked arithmetic
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_add_i8(x: i8, y: i8) -> Optional {
+    Optional::new(x.checked_add(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_add_i16(x: i16, y: i16) -> Optional {
+    Optional::new(x.checked_add(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_add_i32(x: i32, y: i32) -> Optional {
+    Optional::new(x.checked_add(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_add_i64(x: i64, y: i64) -> Optional {
+    Optional::new(x.checked_add(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_add_u8(x: u8, y: u8) -> Optional {
+    Optional::new(x.checked_add(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_add_u16(x: u16, y: u16) -> Optional {
+    Optional::new(x.checked_add(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_add_u32(x: u32, y: u32) -> Optional {
+    Optional::new(x.checked_add(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_add_u64(x: u64, y: u64) -> Optional {
+    Optional::new(x.checked_add(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_add_usize(x: usize, y: usize) -> Optional {
+    Optional::new(x.checked_add(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_sub_i8(x: i8, y: i8) -> Optional {
+    Optional::new(x.checked_sub(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_sub_i16(x: i16, y: i16) -> Optional {
+    Optional::new(x.checked_sub(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_sub_i32(x: i32, y: i32) -> Optional {
+    Optional::new(x.checked_sub(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_sub_i64(x: i64, y: i64) -> Optional {
+    Optional::new(x.checked_sub(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_sub_u8(x: u8, y: u8) -> Optional {
+    Optional::new(x.checked_sub(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_sub_u16(x: u16, y: u16) -> Optional {
+    Optional::new(x.checked_sub(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_sub_u32(x: u32, y: u32) -> Optional {
+    Optional::new(x.checked_sub(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_sub_u64(x: u64, y: u64) -> Optional {
+    Optional::new(x.checked_sub(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_sub_usize(x: usize, y: usize) -> Optional {
+    Optional::new(x.checked_sub(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_mul_i8(x: i8, y: i8) -> Optional {
+    Optional::new(x.checked_mul(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_mul_i16(x: i16, y: i16) -> Optional {
+    Optional::new(x.checked_mul(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_mul_i32(x: i32, y: i32) -> Optional {
+    Optional::new(x.checked_mul(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_mul_i64(x: i64, y: i64) -> Optional {
+    Optional::new(x.checked_mul(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_mul_u8(x: u8, y: u8) -> Optional {
+    Optional::new(x.checked_mul(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_mul_u16(x: u16, y: u16) -> Optional {
+    Optional::new(x.checked_mul(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_mul_u32(x: u32, y: u32) -> Optional {
+    Optional::new(x.checked_mul(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_mul_u64(x: u64, y: u64) -> Optional {
+    Optional::new(x.checked_mul(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_mul_usize(x: usize, y: usize) -> Optional {
+    Optional::new(x.checked_mul(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_div_i8(x: i8, y: i8) -> Optional {
+    Optional::new(x.checked_div(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_div_i16(x: i16, y: i16) -> Optional {
+    Optional::new(x.checked_div(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_div_i32(x: i32, y: i32) -> Optional {
+    Optional::new(x.checked_div(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_div_i64(x: i64, y: i64) -> Optional {
+    Optional::new(x.checked_div(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_div_u8(x: u8, y: u8) -> Optional {
+    Optional::new(x.checked_div(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_div_u16(x: u16, y: u16) -> Optional {
+    Optional::new(x.checked_div(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_div_u32(x: u32, y: u32) -> Optional {
+    Optional::new(x.checked_div(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_div_u64(x: u64, y: u64) -> Optional {
+    Optional::new(x.checked_div(y))
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libprelude__checked_div_usize(x: usize, y: usize) -> Optional {
+    Optional::new(x.checked_div(y))
+}
+
+/// An optional integer, laid out as the NoIR lays out an optional: a tag,
+/// which is 0 for a value and 1 for nil, and a word that holds the value.
+#[repr(C)]
+pub struct Optional {
+    tag: i32,
+    value: u64,
+}
+
+impl Optional {
+    fn new<T: Copy>(x: Option<T>) -> Optional {
+        let mut value = 0u64;
+        match x {
+            Some(x) => {
+                unsafe { ptr::write(&mut value as *mut u64 as *mut T, x) };
+                Optional { tag: 0, value }
+            }
+            None => Optional { tag: 1, value },
+        }
+    }
+}
+
+/// Concatenate a string with the text of a value into a new string. The new
+/// string is never freed, since strings have no owner that could free it.
+fn concat<T: fmt::Display>(x: *const i8, y: T) -> *const i8 {
+    let x = unsafe { CStr::from_ptr(x) }.to_string_lossy();
+    CString::new(format!("{}{}", x, y)).unwrap().into_raw()
+}
+
+/// Stop the program with a runtime error. It aborts instead of panicking,
+/// since a panic can not unwind into the program that called the prelude.
+/// The builder builds every operator that can fail as instructions, which
+/// locate their errors, so this is only reached by other callers.
+fn trap<T>(message: &str) -> T {
+    eprintln!("runtime error: {}", message);
+    process::abort()
+}
@@ -1,30 +1,548 @@
 //! Runtime
+//!
+//! The functions of the runtime are only called by compiled programs, which
+//! pass them pointers that the runtime has handed out, or that point to values
+//! of the size that the runtime is told. So the functions are not marked
+//! `unsafe`, and those that dereference their pointers allow
+//! `clippy::not_unsafe_ptr_arg_deref`.
+
+#[cfg(test)]
+mod lib_test;
 
 /// External crates
+#[macro_use]
+extern crate lazy_static;
 extern crate libc;
 
 /// Standard libraries
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::ffi::CStr;
+use std::process;
+use std::sync::{Condvar, Mutex, MutexGuard};
 use std::thread::{spawn, JoinHandle};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub struct Process {
     pub thread: JoinHandle<()>,
 }
 
+/// Copy the `size` bytes of the environment of a lambda to the heap, where
+/// it lives for as long as the program, since a lambda can outlive the
+/// function that made it.
 #[no_mangle]
-#[allow(non_snake_case)]
-pub extern "C" fn __libruntime__process(f: extern "C" fn() -> libc::c_void) -> *mut Process {
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libruntime__environment_new(environment: *const u8, size: usize) -> *mut u8 {
+    let environment = unsafe { ::std::slice::from_raw_parts(environment, size) }.to_vec();
+    Box::into_raw(environment.into_boxed_slice()) as *mut u8
+}
+
+/// Start a process that calls `f` with a copy of the `size` bytes of its
+/// environment, which the caller is free to overwrite as soon as this returns.
+#[no_mangle]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libruntime__process(f: extern "C" fn(*mut u8) -> libc::c_void,
+                                        environment: *const u8,
+                                        size: usize)
+                                        -> *mut Process {
+    let mut environment = unsafe { ::std::slice::from_raw_parts(environment, size) }.to_vec();
     let process = Process {
         thread: spawn(move || {
             // Call the function
-            f();
+            f(environment.as_mut_ptr());
         }),
     };
     Box::into_raw(Box::new(process))
 }
 
 #[no_mangle]
-#[allow(non_snake_case)]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
 pub extern "C" fn __libruntime__process_join(process: *mut Process) {
     let process = unsafe { Box::from_raw(process) };
     process.thread.join().expect("runtime error: synchronization failed");
 }
+
+/// A `ProcessGroup` holds the processes that are started by the iterations of
+/// a `forall`, so that they can all be joined at the end of the loop.
+pub struct ProcessGroup {
+    pub threads: Vec<JoinHandle<()>>,
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libruntime__process_group_new() -> *mut ProcessGroup {
+    Box::into_raw(Box::new(ProcessGroup { threads: Vec::new() }))
+}
+
+/// Start a process that calls `f` with a copy of the `size` bytes at `value`,
+/// like `__libruntime__process`.
+#[no_mangle]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libruntime__process_group_spawn(group: *mut ProcessGroup,
+                                                     f: extern "C" fn(*mut u8) -> libc::c_void,
+                                                     value: *const u8,
+                                                     size: usize) {
+    let group = unsafe { &mut *group };
+    let mut value = unsafe { ::std::slice::from_raw_parts(value, size) }.to_vec();
+    group.threads.push(spawn(move || {
+        f(value.as_mut_ptr());
+    }));
+}
+
+#[no_mangle]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libruntime__process_group_join(group: *mut ProcessGroup) {
+    let group = unsafe { Box::from_raw(group) };
+    for thread in group.threads {
+        thread.join().expect("runtime error: synchronization failed");
+    }
+}
+
+/// Stop the program with a runtime error. It aborts instead of panicking,
+/// since a panic can not unwind into the program.
+fn runtime_error(message: &str) -> ! {
+    eprintln!("runtime error: {}", message);
+    process::abort();
+}
+
+/// Stop the program with an arithmetic error, such as an overflow.
+#[no_mangle]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libruntime__arithmetic_error(message: *const i8) {
+    let message = unsafe { CStr::from_ptr(message) };
+    runtime_error(&message.to_string_lossy());
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libruntime__unwrap_nil() {
+    runtime_error("unwrapped nil");
+}
+
+lazy_static! {
+    /// Every change to a channel is counted, so that a `select` can wait for
+    /// a change to any of its channels.
+    static ref CHANNELS_CHANGED: (Mutex<u64>, Condvar) = (Mutex::new(0), Condvar::new());
+}
+
+/// A `Channel` holds the values that have been sent, but not yet received,
+/// by value. Like a `List`, the compiler knows the size of a value and passes
+/// values in and out of the channel by pointer.
+///
+/// A channel without a capacity is unbounded. A bounded channel blocks a
+/// sender while it is full, and a channel with a capacity of zero is a
+/// rendezvous, where a sender blocks until its value has been received.
+pub struct Channel {
+    pub size: usize,
+    pub capacity: Option<usize>,
+    pub state: Mutex<ChannelState>,
+    pub changed: Condvar,
+}
+
+pub struct ChannelState {
+    pub values: VecDeque<Vec<u8>>,
+    pub is_closed: bool,
+    pub sent: u64,
+    pub received: u64,
+    /// The number of blocking receives that are waiting on the channel, each
+    /// of which is committed to take the next value.
+    pub receivers: u64,
+}
+
+impl Channel {
+    fn lock<'a>(&'a self) -> MutexGuard<'a, ChannelState> {
+        self.state.lock().expect("runtime error: synchronization failed")
+    }
+
+    fn wait<'a>(&self, state: MutexGuard<'a, ChannelState>) -> MutexGuard<'a, ChannelState> {
+        self.changed.wait(state).expect("runtime error: synchronization failed")
+    }
+
+    /// Wake everything that is waiting on this channel, including any
+    /// `select` that is waiting on several channels.
+    fn notify(&self) {
+        self.changed.notify_all();
+        let (ref count, ref changed) = *CHANNELS_CHANGED;
+        *count.lock().expect("runtime error: synchronization failed") += 1;
+        changed.notify_all();
+    }
+
+    /// Check whether a sender must wait before it can add another value.
+    fn is_full(&self, state: &ChannelState) -> bool {
+        match self.capacity {
+            // A rendezvous holds at most one value, waiting for a receiver.
+            Some(0) => !state.values.is_empty(),
+            Some(capacity) => state.values.len() >= capacity,
+            None => false,
+        }
+    }
+
+    /// Check whether a value can be sent without waiting for room. Sending to
+    /// a rendezvous always waits for a receiver, and so it is only ready when
+    /// a blocking receive is already waiting to take the value. A `select`
+    /// that is waiting to receive does not count, since it may complete
+    /// another guard instead, and leave the sender waiting forever.
+    fn is_ready_to_send(&self, state: &ChannelState) -> bool {
+        match self.capacity {
+            Some(0) => state.receivers > 0 && state.values.is_empty(),
+            _ => !self.is_full(state),
+        }
+    }
+
+    /// Count a blocking receive that is waiting on the channel. Only a
+    /// rendezvous needs to wake its senders.
+    fn add_receivers(&self, state: &mut ChannelState, receivers: u64) {
+        state.receivers += receivers;
+        if self.capacity == Some(0) {
+            self.notify();
+        }
+    }
+
+    fn remove_receivers(&self, state: &mut ChannelState, receivers: u64) {
+        state.receivers -= receivers;
+    }
+
+    /// Take the next value out of the channel, if there is one.
+    fn take(&self, state: &mut ChannelState, value: *mut u8) -> bool {
+        match state.values.pop_front() {
+            Some(bytes) => {
+                unsafe {
+                    ::std::ptr::copy_nonoverlapping(bytes.as_ptr(), value, self.size);
+                }
+                state.received += 1;
+                self.notify();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Put a value into the channel. A rendezvous also waits until the value
+    /// has been received.
+    fn put(&self, mut state: MutexGuard<ChannelState>, value: *const u8) {
+        if state.is_closed {
+            runtime_error("send on a closed channel");
+        }
+        let bytes = unsafe { ::std::slice::from_raw_parts(value, self.size) };
+        state.values.push_back(bytes.to_vec());
+        state.sent += 1;
+        self.notify();
+        if self.capacity == Some(0) {
+            let sent = state.sent;
+            while !state.is_closed && state.received < sent {
+                state = self.wait(state);
+            }
+        }
+    }
+
+    /// Wait until there is a value to receive, or until the channel is closed
+    /// and there will never be one.
+    fn recv(&self, value: *mut u8) -> bool {
+        let mut state = self.lock();
+        self.add_receivers(&mut state, 1);
+        let is_received = loop {
+            if self.take(&mut state, value) {
+                break true;
+            }
+            if state.is_closed {
+                break false;
+            }
+            state = self.wait(state);
+        };
+        self.remove_receivers(&mut state, 1);
+        is_received
+    }
+
+    /// Wait until there is room for a value, and then send it.
+    fn send(&self, value: *const u8) {
+        let mut state = self.lock();
+        while !state.is_closed && self.is_full(&state) {
+            state = self.wait(state);
+        }
+        self.put(state, value);
+    }
+}
+
+/// Create a channel for values of the given size. A negative capacity makes
+/// the channel unbounded.
+///
+/// A channel is never freed. Any number of processes can hold it, and the
+/// program does not count them, so even a channel that is closed and drained
+/// may still be used by a process that has yet to find out. It lives, with
+/// any values left in it, until the program exits.
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libruntime__channel_new(size: i64, capacity: i64) -> *mut Channel {
+    let channel = Channel {
+        size: size as usize,
+        capacity: if capacity < 0 { None } else { Some(capacity as usize) },
+        state: Mutex::new(ChannelState {
+            values: VecDeque::new(),
+            is_closed: false,
+            sent: 0,
+            received: 0,
+            receivers: 0,
+        }),
+        changed: Condvar::new(),
+    };
+    Box::into_raw(Box::new(channel))
+}
+
+#[no_mangle]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libruntime__channel_close(channel: *mut Channel) {
+    let channel = unsafe { &*channel };
+    let mut state = channel.lock();
+    state.is_closed = true;
+    channel.notify();
+}
+
+/// Receive the next value from a channel, for iterating over a channel until
+/// it is closed.
+///
+/// # Return
+/// False if the channel is closed and empty, otherwise true.
+#[no_mangle]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libruntime__channel_next(channel: *mut Channel, value: *mut u8) -> bool {
+    let channel = unsafe { &*channel };
+    channel.recv(value)
+}
+
+#[no_mangle]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libruntime__channel_recv(channel: *mut Channel, value: *mut u8) {
+    let channel = unsafe { &*channel };
+    if !channel.recv(value) {
+        runtime_error("receive on a closed channel");
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libruntime__channel_send(channel: *mut Channel, value: *const u8) {
+    let channel = unsafe { &*channel };
+    channel.send(value)
+}
+
+/// A `Select` collects the guards of a `select` expression, and then waits
+/// until one of them is ready. When several guards are ready, one of them is
+/// chosen at random, so that no guard is starved.
+pub struct Select {
+    pub guards: Vec<SelectGuard>,
+}
+
+pub enum SelectGuard {
+    Recv(*mut Channel, *mut u8),
+    Send(*mut Channel, *const u8),
+}
+
+impl Select {
+    /// Try the guards in the given order, and complete the first one that is
+    /// ready.
+    ///
+    /// # Return
+    /// The index of the guard that was completed, if any.
+    fn try_guards(&self, order: &[usize]) -> Option<usize> {
+        for &index in order.iter() {
+            let is_ready = match self.guards[index] {
+                SelectGuard::Recv(channel, value) => {
+                    let channel = unsafe { &*channel };
+                    let mut state = channel.lock();
+                    if channel.take(&mut state, value) {
+                        true
+                    } else if state.is_closed {
+                        runtime_error("receive on a closed channel");
+                    } else {
+                        false
+                    }
+                }
+                SelectGuard::Send(channel, value) => {
+                    let channel = unsafe { &*channel };
+                    let state = channel.lock();
+                    if state.is_closed || channel.is_ready_to_send(&state) {
+                        channel.put(state, value);
+                        true
+                    } else {
+                        false
+                    }
+                }
+            };
+            if is_ready {
+                return Some(index);
+            }
+        }
+        None
+    }
+}
+
+thread_local! {
+    static RANDOM_STATE: Cell<u64> = Cell::new(random_seed());
+}
+
+fn random_seed() -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.subsec_nanos() as u64 ^ duration.as_secs())
+        .unwrap_or(0);
+    let local = 0u8;
+    // Mix in the address of a local, so that every thread has its own seed.
+    (nanos ^ (&local as *const u8 as u64).rotate_left(32)) | 1
+}
+
+/// Generate a pseudo-random number, using a xorshift generator.
+fn random() -> u64 {
+    RANDOM_STATE.with(|state| {
+        let mut x = state.get();
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        state.set(x);
+        x
+    })
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libruntime__select_new() -> *mut Select {
+    Box::into_raw(Box::new(Select { guards: Vec::new() }))
+}
+
+#[no_mangle]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libruntime__select_recv(select: *mut Select, channel: *mut Channel, value: *mut u8) {
+    let select = unsafe { &mut *select };
+    select.guards.push(SelectGuard::Recv(channel, value));
+}
+
+#[no_mangle]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libruntime__select_send(select: *mut Select, channel: *mut Channel, value: *const u8) {
+    let select = unsafe { &mut *select };
+    select.guards.push(SelectGuard::Send(channel, value));
+}
+
+/// Wait until one of the guards of a select is ready, and complete it. A
+/// select that does not block returns immediately when no guard is ready.
+/// The select is freed.
+///
+/// # Return
+/// The index of the guard that was completed, or -1 if no guard was ready.
+#[no_mangle]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libruntime__select_wait(select: *mut Select, is_blocking: bool) -> i64 {
+    let select = unsafe { Box::from_raw(select) };
+
+    // Shuffle the guards, so that each ready guard is as likely to be chosen.
+    let mut order = (0..select.guards.len()).collect::<Vec<_>>();
+    for i in (1..order.len()).rev() {
+        let j = (random() % (i as u64 + 1)) as usize;
+        order.swap(i, j);
+    }
+
+    let (ref count, ref changed) = *CHANNELS_CHANGED;
+    let index = loop {
+        // Read the count before trying the guards, so that no change after
+        // trying them can be missed.
+        let seen = *count.lock().expect("runtime error: synchronization failed");
+        if let Some(index) = select.try_guards(&order) {
+            break index as i64;
+        }
+        if !is_blocking {
+            break -1;
+        }
+        let mut current = count.lock().expect("runtime error: synchronization failed");
+        while *current == seen {
+            current = changed.wait(current).expect("runtime error: synchronization failed");
+        }
+    };
+    index
+}
+
+/// A `List` holds its elements by value. The compiler knows the size of an
+/// element, and passes elements in and out of the list by pointer.
+pub struct List {
+    pub size: usize,
+    pub len: usize,
+    pub bytes: Vec<u8>,
+}
+
+impl List {
+    fn element(&mut self, index: usize) -> *mut u8 {
+        unsafe { self.bytes.as_mut_ptr().add(index * self.size) }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn __libruntime__list_new(size: i64) -> *mut List {
+    let list = List {
+        size: size as usize,
+        len: 0,
+        bytes: Vec::new(),
+    };
+    Box::into_raw(Box::new(list))
+}
+
+#[no_mangle]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libruntime__list_len(list: *mut List) -> i64 {
+    let list = unsafe { &*list };
+    list.len as i64
+}
+
+#[no_mangle]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libruntime__list_push(list: *mut List, element: *const u8) {
+    let list = unsafe { &mut *list };
+    let element = unsafe { ::std::slice::from_raw_parts(element, list.size) };
+    list.bytes.extend_from_slice(element);
+    list.len += 1;
+}
+
+#[no_mangle]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libruntime__list_pop(list: *mut List, element: *mut u8) -> bool {
+    let list = unsafe { &mut *list };
+    if list.len == 0 {
+        return false;
+    }
+    list.len -= 1;
+    let len = list.len;
+    unsafe {
+        ::std::ptr::copy_nonoverlapping(list.element(len), element, list.size);
+    }
+    let size = list.len * list.size;
+    list.bytes.truncate(size);
+    true
+}
+
+#[no_mangle]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libruntime__list_index(list: *mut List, index: i64) -> *mut u8 {
+    let list = unsafe { &mut *list };
+    if index < 0 || index as usize >= list.len {
+        runtime_error(&format!("index out of bounds: the len is {} but the index is {}",
+                               list.len,
+                               index));
+    }
+    list.element(index as usize)
+}
+
+#[no_mangle]
+#[allow(non_snake_case, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn __libruntime__list_slice(list: *mut List, start: i64, end: i64) -> *mut List {
+    let list = unsafe { &*list };
+    if start < 0 || start > end || end as usize > list.len {
+        runtime_error(&format!("slice out of bounds: the len is {} but the slice is {} .. {}",
+                               list.len,
+                               start,
+                               end));
+    }
+    let slice = List {
+        size: list.size,
+        len: (end - start) as usize,
+        bytes: list.bytes[start as usize * list.size..end as usize * list.size].to_vec(),
+    };
+    Box::into_raw(Box::new(slice))
+}
@@ -0,0 +1,223 @@
+use super::*;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// Copy an element out of a list into an `i64`.
+fn element(list: *mut List, index: i64) -> i64 {
+    let mut value = 0i64;
+    unsafe {
+        ::std::ptr::copy_nonoverlapping(__libruntime__list_index(list, index), &mut value as *mut i64 as *mut u8, 8);
+    }
+    value
+}
+
+fn push(list: *mut List, value: i64) {
+    __libruntime__list_push(list, &value as *const i64 as *const u8);
+}
+
+#[test]
+fn list_push_index_pop() {
+    let list = __libruntime__list_new(8);
+    assert_eq!(__libruntime__list_len(list), 0);
+    push(list, 1);
+    push(list, 2);
+    push(list, 3);
+    assert_eq!(__libruntime__list_len(list), 3);
+    assert_eq!(element(list, 0), 1);
+    assert_eq!(element(list, 2), 3);
+
+    let mut value = 0i64;
+    assert!(__libruntime__list_pop(list, &mut value as *mut i64 as *mut u8));
+    assert_eq!(value, 3);
+    assert_eq!(__libruntime__list_len(list), 2);
+    assert!(__libruntime__list_pop(list, &mut value as *mut i64 as *mut u8));
+    assert!(__libruntime__list_pop(list, &mut value as *mut i64 as *mut u8));
+    assert_eq!(value, 1);
+    assert!(!__libruntime__list_pop(list, &mut value as *mut i64 as *mut u8));
+}
+
+#[test]
+fn list_slice() {
+    let list = __libruntime__list_new(8);
+    for value in 0..5 {
+        push(list, value);
+    }
+    let slice = __libruntime__list_slice(list, 1, 4);
+    assert_eq!(__libruntime__list_len(slice), 3);
+    assert_eq!(element(slice, 0), 1);
+    assert_eq!(element(slice, 2), 3);
+
+    // the slice is a copy
+    push(slice, 9);
+    assert_eq!(__libruntime__list_len(list), 5);
+
+    let empty = __libruntime__list_slice(list, 5, 5);
+    assert_eq!(__libruntime__list_len(empty), 0);
+}
+
+fn send(channel: *mut Channel, value: i64) {
+    __libruntime__channel_send(channel, &value as *const i64 as *const u8);
+}
+
+fn recv(channel: *mut Channel) -> i64 {
+    let mut value = 0i64;
+    __libruntime__channel_recv(channel, &mut value as *mut i64 as *mut u8);
+    value
+}
+
+/// Send a value from another thread.
+///
+/// # Return
+/// The thread, and a flag that is set once the send has returned.
+fn send_in_thread(channel: *mut Channel, value: i64) -> (thread::JoinHandle<()>, Arc<AtomicBool>) {
+    let is_sent = Arc::new(AtomicBool::new(false));
+    let channel = channel as usize;
+    let thread = {
+        let is_sent = is_sent.clone();
+        thread::spawn(move || {
+            send(channel as *mut Channel, value);
+            is_sent.store(true, Ordering::SeqCst);
+        })
+    };
+    (thread, is_sent)
+}
+
+#[test]
+fn channel_rendezvous_blocks_until_received() {
+    let channel = __libruntime__channel_new(8, 0);
+    let (thread, is_sent) = send_in_thread(channel, 42);
+    thread::sleep(Duration::from_millis(50));
+    assert!(!is_sent.load(Ordering::SeqCst));
+
+    assert_eq!(recv(channel), 42);
+    thread.join().unwrap();
+    assert!(is_sent.load(Ordering::SeqCst));
+}
+
+#[test]
+fn channel_bounded_blocks_while_full() {
+    let channel = __libruntime__channel_new(8, 2);
+    send(channel, 1);
+    send(channel, 2);
+    let (thread, is_sent) = send_in_thread(channel, 3);
+    thread::sleep(Duration::from_millis(50));
+    assert!(!is_sent.load(Ordering::SeqCst));
+
+    assert_eq!(recv(channel), 1);
+    thread.join().unwrap();
+    assert!(is_sent.load(Ordering::SeqCst));
+    assert_eq!(recv(channel), 2);
+    assert_eq!(recv(channel), 3);
+}
+
+#[test]
+fn channel_unbounded_does_not_block() {
+    let channel = __libruntime__channel_new(8, -1);
+    for value in 0..100 {
+        send(channel, value);
+    }
+    __libruntime__channel_close(channel);
+
+    let mut value = 0i64;
+    let mut count = 0;
+    while __libruntime__channel_next(channel, &mut value as *mut i64 as *mut u8) {
+        assert_eq!(value, count);
+        count += 1;
+    }
+    assert_eq!(count, 100);
+}
+
+#[test]
+fn select_chooses_fairly() {
+    let first = __libruntime__channel_new(8, -1);
+    let second = __libruntime__channel_new(8, -1);
+    for _ in 0..100 {
+        send(first, 1);
+        send(second, 2);
+    }
+
+    // both guards are always ready, and each should be chosen
+    let mut counts = [0, 0];
+    let mut value = 0i64;
+    for _ in 0..100 {
+        let select = __libruntime__select_new();
+        __libruntime__select_recv(select, first, &mut value as *mut i64 as *mut u8);
+        __libruntime__select_recv(select, second, &mut value as *mut i64 as *mut u8);
+        let index = __libruntime__select_wait(select, true);
+        assert_eq!(value, index + 1);
+        counts[index as usize] += 1;
+    }
+    assert!(counts[0] > 0);
+    assert!(counts[1] > 0);
+}
+
+#[test]
+fn select_else() {
+    let channel = __libruntime__channel_new(8, 0);
+    let mut value = 0i64;
+
+    // no guard is ready, so a select with an `else` does not wait
+    let select = __libruntime__select_new();
+    __libruntime__select_recv(select, channel, &mut value as *mut i64 as *mut u8);
+    __libruntime__select_send(select, channel, &value as *const i64 as *const u8);
+    assert_eq!(__libruntime__select_wait(select, false), -1);
+
+    let channel = __libruntime__channel_new(8, -1);
+    send(channel, 42);
+    let select = __libruntime__select_new();
+    __libruntime__select_recv(select, channel, &mut value as *mut i64 as *mut u8);
+    assert_eq!(__libruntime__select_wait(select, false), 0);
+    assert_eq!(value, 42);
+}
+
+#[test]
+fn select_waits_for_a_guard() {
+    let channel = __libruntime__channel_new(8, 0);
+    let (thread, _) = send_in_thread(channel, 42);
+    let mut value = 0i64;
+    let select = __libruntime__select_new();
+    __libruntime__select_recv(select, channel, &mut value as *mut i64 as *mut u8);
+    assert_eq!(__libruntime__select_wait(select, true), 0);
+    assert_eq!(value, 42);
+    thread.join().unwrap();
+}
+
+#[test]
+fn select_rendezvous_send_waits_for_a_committed_receiver() {
+    let rendezvous = __libruntime__channel_new(8, 0);
+    let other = __libruntime__channel_new(8, -1);
+
+    // a select that is waiting to receive from a rendezvous may take another
+    // guard, so another select can not send to it
+    let waiting = {
+        let (rendezvous, other) = (rendezvous as usize, other as usize);
+        thread::spawn(move || {
+            let mut value = 0i64;
+            let select = __libruntime__select_new();
+            __libruntime__select_recv(select, rendezvous as *mut Channel, &mut value as *mut i64 as *mut u8);
+            __libruntime__select_recv(select, other as *mut Channel, &mut value as *mut i64 as *mut u8);
+            (__libruntime__select_wait(select, true), value)
+        })
+    };
+    thread::sleep(Duration::from_millis(50));
+    let value = 42i64;
+    let select = __libruntime__select_new();
+    __libruntime__select_send(select, rendezvous, &value as *const i64 as *const u8);
+    assert_eq!(__libruntime__select_wait(select, false), -1);
+
+    send(other, 7);
+    assert_eq!(waiting.join().unwrap(), (1, 7));
+
+    // a blocking receive is committed to take the value
+    let receiving = {
+        let rendezvous = rendezvous as usize;
+        thread::spawn(move || recv(rendezvous as *mut Channel))
+    };
+    thread::sleep(Duration::from_millis(50));
+    let select = __libruntime__select_new();
+    __libruntime__select_send(select, rendezvous, &value as *const i64 as *const u8);
+    assert_eq!(__libruntime__select_wait(select, false), 0);
+    assert_eq!(receiving.join().unwrap(), 42);
+}
@@ -1,23 +1,4 @@
 
-/// Send a message to an `Item`. An immutable message cannot result in the
-/// modification of the `Item`.
-macro_rules! expr_proxy {
-    ($expr: expr => $($method: ident ( $($arg: expr ),* )).+) => (match $expr {
-        Expr::Block(ref expr) => expr.$($method($($arg),*)).+,
-        Expr::Call(ref expr) => expr.$($method($($arg),*)).+,
-        _ => unimplemented!()
-    });
-}
-
-/// Send a message to an `Item`. An immutable message cannot result in the
-/// modification of the `Item`.
-macro_rules! item_proxy {
-    ($item: expr => $($method: ident ( $($arg: expr ),* )).+) => (match $item {
-        Item::Function(ref item) => item.$($method($($arg),*)).+,
-        Item::Variable(ref item) => item.$($method($($arg),*)).+,
-    });
-}
-
 /// Create a `Function` that represents an extern binary operator.
 ///
 /// # Arguments
@@ -42,26 +23,192 @@ macro_rules! bin_fn {
     )
 }
 
+/// Create a `Function` that represents an extern assignment operator, which
+/// updates its first operand in place.
+///
+/// # Arguments
+/// * op_name - The name of the assignment operator.
+/// * type_name - The name of the type that is used by the assignment operator.
+/// * type_expr - A path to a `Type`.
+///
+/// # Return
+/// An extern `Function` with a profile that matches the assignment operator in
+/// the prelude library.
+macro_rules! assign_fn {
+    ($op_name: expr, $type_name: expr, $type_expr: expr) => (
+        Function::new(
+            Symbol::new(format!("__libprelude__{}_{}", $op_name, $type_name)),
+            vec![
+                Variable::new(Symbol::new("x"), RefType::new($type_expr)),
+                Variable::new(Symbol::new("y"), $type_expr)
+            ],
+            PrimitiveType::Void,
+            None,
+        )
+    )
+}
+
 macro_rules! add_fn {
     ($type_name: expr, $type_expr: expr) => (bin_fn!("add", $type_name, $type_expr))
 }
 
 macro_rules! addeq_fn {
-    ($type_name: expr, $type_expr: expr) => (bin_fn!("addeq", $type_name, $type_expr))
+    ($type_name: expr, $type_expr: expr) => (assign_fn!("addeq", $type_name, $type_expr))
 }
 
 macro_rules! div_fn {
     ($type_name: expr, $type_expr: expr) => (bin_fn!("div", $type_name, $type_expr))
 }
 
+macro_rules! diveq_fn {
+    ($type_name: expr, $type_expr: expr) => (assign_fn!("diveq", $type_name, $type_expr))
+}
+
 macro_rules! mul_fn {
     ($type_name: expr, $type_expr: expr) => (bin_fn!("mul", $type_name, $type_expr))
 }
 
+macro_rules! muleq_fn {
+    ($type_name: expr, $type_expr: expr) => (assign_fn!("muleq", $type_name, $type_expr))
+}
+
 macro_rules! sub_fn {
     ($type_name: expr, $type_expr: expr) => (bin_fn!("sub", $type_name, $type_expr))
 }
 
+macro_rules! subeq_fn {
+    ($type_name: expr, $type_expr: expr) => (assign_fn!("subeq", $type_name, $type_expr))
+}
+
+/// Create a `Function` that represents an extern checked binary operator,
+/// which returns nil instead of a value that overflows.
+///
+/// # Arguments
+/// * op_name - The name of the binary operator.
+/// * type_name - The name of the type that is used by the binary operator.
+/// * type_expr - A path to a `Type`.
+///
+/// # Return
+/// An extern `Function` with a profile that matches the checked binary
+/// operator in the prelude library.
+macro_rules! checked_fn {
+    ($op_name: expr, $type_name: expr, $type_expr: expr) => (
+        Function::new(
+            Symbol::new(format!("__libprelude__checked_{}_{}", $op_name, $type_name)),
+            vec![
+                Variable::new(Symbol::new("x"), $type_expr),
+                Variable::new(Symbol::new("y"), $type_expr)
+            ],
+            EnumType::new_optional(Symbol::new(format!("{}?", $type_name)), $type_expr),
+            None,
+        )
+    )
+}
+
+macro_rules! wrapping_add_fn {
+    ($type_name: expr, $type_expr: expr) => (bin_fn!("wrapping_add", $type_name, $type_expr))
+}
+
+macro_rules! wrapping_sub_fn {
+    ($type_name: expr, $type_expr: expr) => (bin_fn!("wrapping_sub", $type_name, $type_expr))
+}
+
+macro_rules! wrapping_mul_fn {
+    ($type_name: expr, $type_expr: expr) => (bin_fn!("wrapping_mul", $type_name, $type_expr))
+}
+
+macro_rules! wrapping_div_fn {
+    ($type_name: expr, $type_expr: expr) => (bin_fn!("wrapping_div", $type_name, $type_expr))
+}
+
+macro_rules! saturating_add_fn {
+    ($type_name: expr, $type_expr: expr) => (bin_fn!("saturating_add", $type_name, $type_expr))
+}
+
+macro_rules! saturating_sub_fn {
+    ($type_name: expr, $type_expr: expr) => (bin_fn!("saturating_sub", $type_name, $type_expr))
+}
+
+macro_rules! saturating_mul_fn {
+    ($type_name: expr, $type_expr: expr) => (bin_fn!("saturating_mul", $type_name, $type_expr))
+}
+
+macro_rules! saturating_div_fn {
+    ($type_name: expr, $type_expr: expr) => (bin_fn!("saturating_div", $type_name, $type_expr))
+}
+
+macro_rules! checked_add_fn {
+    ($type_name: expr, $type_expr: expr) => (checked_fn!("add", $type_name, $type_expr))
+}
+
+macro_rules! checked_sub_fn {
+    ($type_name: expr, $type_expr: expr) => (checked_fn!("sub", $type_name, $type_expr))
+}
+
+macro_rules! checked_mul_fn {
+    ($type_name: expr, $type_expr: expr) => (checked_fn!("mul", $type_name, $type_expr))
+}
+
+macro_rules! checked_div_fn {
+    ($type_name: expr, $type_expr: expr) => (checked_fn!("div", $type_name, $type_expr))
+}
+
+/// Create a `Function` that represents an extern comparison operator.
+///
+/// # Arguments
+/// * op_name - The name of the comparison operator.
+/// * type_name - The name of the type that is compared by the operator.
+/// * type_expr - A path to a `Type`.
+///
+/// # Return
+/// An extern `Function` with a profile that matches the comparison operator
+/// in the prelude library.
+macro_rules! cmp_fn {
+    ($op_name: expr, $type_name: expr, $type_expr: expr) => (
+        Function::new(
+            Symbol::new(format!("__libprelude__{}_{}", $op_name, $type_name)),
+            vec![
+                Variable::new(Symbol::new("x"), $type_expr),
+                Variable::new(Symbol::new("y"), $type_expr)
+            ],
+            PrimitiveType::Bool,
+            None,
+        )
+    )
+}
+
+macro_rules! eq_fn {
+    ($type_name: expr, $type_expr: expr) => (cmp_fn!("eq", $type_name, $type_expr))
+}
+
+macro_rules! ne_fn {
+    ($type_name: expr, $type_expr: expr) => (cmp_fn!("ne", $type_name, $type_expr))
+}
+
+macro_rules! lt_fn {
+    ($type_name: expr, $type_expr: expr) => (cmp_fn!("lt", $type_name, $type_expr))
+}
+
+macro_rules! le_fn {
+    ($type_name: expr, $type_expr: expr) => (cmp_fn!("le", $type_name, $type_expr))
+}
+
+macro_rules! gt_fn {
+    ($type_name: expr, $type_expr: expr) => (cmp_fn!("gt", $type_name, $type_expr))
+}
+
+macro_rules! ge_fn {
+    ($type_name: expr, $type_expr: expr) => (cmp_fn!("ge", $type_name, $type_expr))
+}
+
+macro_rules! and_fn {
+    ($type_name: expr, $type_expr: expr) => (bin_fn!("and", $type_name, $type_expr))
+}
+
+macro_rules! or_fn {
+    ($type_name: expr, $type_expr: expr) => (bin_fn!("or", $type_name, $type_expr))
+}
+
 /// Create a `Function` that represents an extern concat operator for strings.
 ///
 /// # Arguments
@@ -77,7 +224,10 @@ macro_rules! concat_string_fn {
             // symbol of the function
             Symbol::new(format!("__libprelude__concat_string_{}", $type_name)),
             // formals of the function
-            vec![Variable::new(Symbol::new("x"), $type_expr)],
+            vec![
+                Variable::new(Symbol::new("x"), PrimitiveType::Str),
+                Variable::new(Symbol::new("y"), $type_expr)
+            ],
             // type profile of the function
             PrimitiveType::Str,
             // definition of the function
@@ -7,6 +7,7 @@
 //! generation passes, are done using NoIR.
 
 use super::identifier::{Identifier, Id, Identify, Name, Symbol, Symbolise};
+use super::lexer::Span;
 
 use std::collections::HashMap;
 
@@ -16,18 +17,49 @@ pub mod context;
 pub mod prelude;
 pub mod runtime;
 
+#[cfg(test)]
+mod prelude_test;
+
 ///
 #[derive(Clone)]
 pub struct AliasType {
-    symbol: Symbol,
-    inner: Type,
+    pub symbol: Symbol,
+    pub inner: Type,
 }
 
-///
+/// The left hand side of an `AssignExpr` is a `VariableExpr`, possibly
+/// referring to an element of a struct, or a `DerefExpr`.
 #[derive(Clone)]
 pub struct AssignExpr {
-    lhs: RefExpr,
-    rhs: Expr,
+    pub identifier: Identifier,
+    pub lhs: Expr,
+    pub rhs: Expr,
+}
+
+impl AssignExpr {
+    pub fn new<L, R>(lhs: L, rhs: R) -> AssignExpr
+        where L: Into<Expr>,
+              R: Into<Expr>
+    {
+        AssignExpr::new_with_id(Identifier::id(), lhs, rhs)
+    }
+
+    pub fn new_with_id<L, R>(identifier: Identifier, lhs: L, rhs: R) -> AssignExpr
+        where L: Into<Expr>,
+              R: Into<Expr>
+    {
+        AssignExpr {
+            identifier: identifier,
+            lhs: lhs.into(),
+            rhs: rhs.into(),
+        }
+    }
+}
+
+impl Identify for AssignExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
 }
 
 ///
@@ -103,6 +135,28 @@ impl Identify for BlockExpr {
 ///
 pub type BlockExprs = Vec<BlockExpr>;
 
+/// A `BreakExpr` ends the innermost loop that encloses it.
+#[derive(Clone)]
+pub struct BreakExpr {
+    pub identifier: Identifier,
+}
+
+impl BreakExpr {
+    pub fn new() -> BreakExpr {
+        BreakExpr::new_with_id(Identifier::id())
+    }
+
+    pub fn new_with_id(identifier: Identifier) -> BreakExpr {
+        BreakExpr { identifier: identifier }
+    }
+}
+
+impl Identify for BreakExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
 ///
 #[derive(Clone)]
 pub struct CallExpr {
@@ -140,34 +194,328 @@ impl Identify for CallExpr {
     }
 }
 
+/// A `ChannelExpr` creates an empty channel. Its type is the type of the
+/// values that are sent through the channel. A channel without a capacity is
+/// unbounded.
+#[derive(Clone)]
+pub struct ChannelExpr {
+    pub identifier: Identifier,
+    pub capacity: Option<Expr>,
+    pub ty: Type,
+}
+
+impl ChannelExpr {
+    pub fn new<T: Into<Type>>(capacity: Option<Expr>, ty: T) -> ChannelExpr {
+        ChannelExpr::new_with_id(Identifier::id(), capacity, ty)
+    }
+
+    pub fn new_with_id<T: Into<Type>>(identifier: Identifier, capacity: Option<Expr>, ty: T) -> ChannelExpr {
+        ChannelExpr {
+            identifier: identifier,
+            capacity: capacity,
+            ty: ty.into(),
+        }
+    }
+}
+
+impl Identify for ChannelExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+///
+#[derive(Clone)]
+pub enum ChannelOp {
+    Close(Expr),
+    Recv(Expr),
+    Send(Expr, Expr),
+}
+
+/// A `ChannelOpExpr` applies a `ChannelOp` to a channel. Its type is the
+/// type of the values in the channel.
+#[derive(Clone)]
+pub struct ChannelOpExpr {
+    pub identifier: Identifier,
+    pub op: ChannelOp,
+    pub ty: Type,
+}
+
+impl ChannelOpExpr {
+    pub fn new<T: Into<Type>>(op: ChannelOp, ty: T) -> ChannelOpExpr {
+        ChannelOpExpr::new_with_id(Identifier::id(), op, ty)
+    }
+
+    pub fn new_with_id<T: Into<Type>>(identifier: Identifier, op: ChannelOp, ty: T) -> ChannelOpExpr {
+        ChannelOpExpr {
+            identifier: identifier,
+            op: op,
+            ty: ty.into(),
+        }
+    }
+}
+
+impl Identify for ChannelOpExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `ChannelType` is a pointer to a channel that is managed by the runtime.
+#[derive(Clone)]
+pub struct ChannelType {
+    pub element: Type,
+}
+
+impl ChannelType {
+    pub fn new<E: Into<Type>>(element: E) -> ChannelType {
+        ChannelType { element: element.into() }
+    }
+}
+
+/// A `ClosureType` is the type of a function value, which is a pointer to a
+/// function, and a pointer to the environment that it is called with.
+#[derive(Clone)]
+pub struct ClosureType {
+    pub formals: Types,
+    pub ret: Type,
+}
+
+impl ClosureType {
+    pub fn new<F, R>(formals: Vec<F>, ret: R) -> ClosureType
+        where F: Into<Type>,
+              R: Into<Type>
+    {
+        ClosureType {
+            formals: formals
+                .into_iter()
+                .map(|formal| formal.into())
+                .collect(),
+            ret: ret.into(),
+        }
+    }
+
+    /// The type of the function of a closure, which is given a pointer to its
+    /// environment before its formals.
+    pub fn lambda_type(&self) -> LambdaType {
+        let mut formals = vec![PtrType::new(PrimitiveType::I8).into()];
+        formals.extend(self.formals.iter().cloned());
+        LambdaType::new(formals, self.ret.clone())
+    }
+}
+
+/// A `ContinueExpr` skips to the next iteration of the innermost loop that
+/// encloses it.
+#[derive(Clone)]
+pub struct ContinueExpr {
+    pub identifier: Identifier,
+}
+
+impl ContinueExpr {
+    pub fn new() -> ContinueExpr {
+        ContinueExpr::new_with_id(Identifier::id())
+    }
+
+    pub fn new_with_id(identifier: Identifier) -> ContinueExpr {
+        ContinueExpr { identifier: identifier }
+    }
+}
+
+impl Identify for ContinueExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
 ///
 #[derive(Clone)]
 pub struct DerefExpr {
-    identifier: Identifier,
-    inner: Expr,
+    pub identifier: Identifier,
+    pub inner: Expr,
+}
+
+impl DerefExpr {
+    pub fn new<I: Into<Expr>>(inner: I) -> DerefExpr {
+        DerefExpr::new_with_id(Identifier::id(), inner)
+    }
+
+    pub fn new_with_id<I: Into<Expr>>(identifier: Identifier, inner: I) -> DerefExpr {
+        DerefExpr {
+            identifier: identifier,
+            inner: inner.into(),
+        }
+    }
+}
+
+impl Identify for DerefExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
 }
 
 ///
 #[derive(Clone)]
+pub struct EnumExpr {
+    pub identifier: Identifier,
+    pub variant: Variable,
+    pub elements: Vec<(Variable, Expr)>,
+    pub ty: EnumType,
+}
+
+impl EnumExpr {
+    pub fn new(variant: Variable, elements: Vec<(Variable, Expr)>, ty: EnumType) -> EnumExpr {
+        EnumExpr::new_with_id(Identifier::id(), variant, elements, ty)
+    }
+
+    pub fn new_with_id(identifier: Identifier,
+                       variant: Variable,
+                       elements: Vec<(Variable, Expr)>,
+                       ty: EnumType)
+                       -> EnumExpr {
+        EnumExpr {
+            identifier: identifier,
+            variant: variant,
+            elements: elements,
+            ty: ty,
+        }
+    }
+}
+
+impl Identify for EnumExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// An `EnumType` is a tagged union. The tag of a variant is its position in
+/// the variants, and the type of a variant is a `StructType` that holds its
+/// elements.
+#[derive(Clone)]
 pub struct EnumType {
-    symbol: Symbol,
-    params: Types,
-    variants: Box<StructType>,
+    pub symbol: Symbol,
+    pub params: Types,
+    pub variants: Box<StructType>,
 }
 
-///
+impl EnumType {
+    pub fn new<S, V>(symbol: S, variants: Vec<V>) -> EnumType
+        where S: Into<Symbol>,
+              V: Into<Variable>
+    {
+        let symbol = symbol.into();
+        EnumType {
+            symbol: symbol.clone(),
+            params: Types::new(),
+            variants: Box::new(StructType::new(symbol, variants)),
+        }
+    }
+
+    /// Create the `EnumType` that an optional is lowered to. The `Some`
+    /// variant holds the value of the optional, and the `Nil` variant is
+    /// empty.
+    pub fn new_optional<S, T>(symbol: S, inner: T) -> EnumType
+        where S: Into<Symbol>,
+              T: Into<Type>
+    {
+        let some = StructType::new(Symbol::new("Some"), vec![Variable::new(Symbol::new("value"), inner)]);
+        let nil = StructType::new(Symbol::new("Nil"), Variables::new());
+        EnumType::new(symbol,
+                      vec![Variable::new(Symbol::new("Some"), some),
+                           Variable::new(Symbol::new("Nil"), nil)])
+    }
+
+    /// Get the `Some` and `Nil` variants of an `EnumType` that was created by
+    /// `EnumType::new_optional`.
+    pub fn optional_variants(&self) -> (Variable, Variable) {
+        (self.variants.elements[0].clone(), self.variants.elements[1].clone())
+    }
+
+    /// Get the elements of a variant of an `EnumType`.
+    pub fn variant_elements(variant: &Variable) -> Variables {
+        match variant.ty {
+            Type::Struct(ref ty) => ty.elements.clone(),
+            _ => Variables::new(),
+        }
+    }
+}
+
+impl Identify for EnumType {
+    fn identify(&self) -> Identifier {
+        self.symbol.identify()
+    }
+}
+
+impl Symbolise for EnumType {
+    fn symbolise(&self) -> Symbol {
+        self.symbol.clone()
+    }
+}
+
+/// A `ForExpr` evaluates its iteration once for every element of its
+/// iterator, binding the element to its variable. The iterator is a list, a
+/// `RangeExpr`, or a channel that is received from until it is closed, as
+/// given by the `Type` of the iterator. A concurrent `ForExpr` evaluates
+/// every iteration in a process of its own, and joins the processes at the end
+/// of the loop.
 #[derive(Clone)]
 pub struct ForExpr {
-    identifier: Identifier,
-    formals: Variables,
-    iterator: Expr,
-    iteration: Box<BlockExpr>,
+    pub identifier: Identifier,
+    pub is_concurrent: bool,
+    pub variable: Variable,
+    pub iterator: Expr,
+    pub iteration: Box<BlockExpr>,
+    pub ty: Type,
+    /// The variables from outside of a concurrent `ForExpr` that are used by
+    /// its iteration.
+    pub captures: Variables,
 }
 
-#[derive(Clone)]
-struct _ForExpr {
-    identifier: Identifier,
-    variables: Variables,
+impl ForExpr {
+    pub fn new<I, T>(is_concurrent: bool, variable: Variable, iterator: I, iteration: BlockExpr, ty: T) -> ForExpr
+        where I: Into<Expr>,
+              T: Into<Type>
+    {
+        ForExpr::new_with_id(Identifier::id(), is_concurrent, variable, iterator, iteration, ty)
+    }
+
+    pub fn new_with_id<I, T>(identifier: Identifier,
+                             is_concurrent: bool,
+                             variable: Variable,
+                             iterator: I,
+                             iteration: BlockExpr,
+                             ty: T)
+                             -> ForExpr
+        where I: Into<Expr>,
+              T: Into<Type>
+    {
+        ForExpr {
+            identifier: identifier,
+            is_concurrent: is_concurrent,
+            variable: variable,
+            iterator: iterator.into(),
+            iteration: Box::new(iteration),
+            ty: ty.into(),
+            captures: Variables::new(),
+        }
+    }
+
+    /// The profile of the function that evaluates one iteration of a
+    /// concurrent `ForExpr`, which is given a pointer to its environment: the
+    /// element, followed by the captures.
+    pub fn iteration_function(&self) -> Function {
+        Function::new(
+            Symbol::new(format!("__libruntime__forall_{}", self.id())),
+            vec![Variable::new(Symbol::new("environment"), PtrType::new(PrimitiveType::I8))],
+            PrimitiveType::Void,
+            None,
+        )
+    }
+}
+
+impl Identify for ForExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
 }
 
 ///
@@ -247,10 +595,33 @@ pub type FunctionTable = HashMap<Identifier, Function>;
 ///
 #[derive(Clone)]
 pub struct IfExpr {
-    identifier: Identifier,
-    condition: Expr,
-    then_block: Box<BlockExpr>,
-    else_block: Box<BlockExpr>,
+    pub identifier: Identifier,
+    pub condition: Expr,
+    pub then_block: Box<BlockExpr>,
+    pub else_block: Box<BlockExpr>,
+}
+
+impl IfExpr {
+    pub fn new<C: Into<Expr>>(condition: C, then_block: BlockExpr, else_block: BlockExpr) -> IfExpr {
+        IfExpr::new_with_id(Identifier::id(), condition, then_block, else_block)
+    }
+
+    pub fn new_with_id<C>(identifier: Identifier, condition: C, then_block: BlockExpr, else_block: BlockExpr) -> IfExpr
+        where C: Into<Expr>
+    {
+        IfExpr {
+            identifier: identifier,
+            condition: condition.into(),
+            then_block: Box::new(then_block),
+            else_block: Box::new(else_block),
+        }
+    }
+}
+
+impl Identify for IfExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
 }
 
 ///
@@ -279,6 +650,62 @@ impl Symbolise for ItemExpr {
     }
 }
 
+/// A `LambdaExpr` evaluates to a closure, which calls a function that
+/// evaluates the `body` with the values that the `captures` had when the
+/// closure was made.
+#[derive(Clone)]
+pub struct LambdaExpr {
+    pub identifier: Identifier,
+    pub formals: Variables,
+    pub ret: Type,
+    pub body: Box<BlockExpr>,
+    /// The variables from outside of the lambda that are used by its body.
+    pub captures: Variables,
+}
+
+impl LambdaExpr {
+    pub fn new<R: Into<Type>>(formals: Variables, ret: R, body: BlockExpr) -> LambdaExpr {
+        LambdaExpr::new_with_id(Identifier::id(), formals, ret, body)
+    }
+
+    pub fn new_with_id<R: Into<Type>>(identifier: Identifier, formals: Variables, ret: R, body: BlockExpr) -> LambdaExpr {
+        LambdaExpr {
+            identifier: identifier,
+            formals: formals,
+            ret: ret.into(),
+            body: Box::new(body),
+            captures: Variables::new(),
+        }
+    }
+
+    pub fn closure_type(&self) -> ClosureType {
+        ClosureType::new(self.formals
+                             .iter()
+                             .map(|formal| formal.ty.clone())
+                             .collect(),
+                         self.ret.clone())
+    }
+
+    /// The profile of the function of the closure, which is given a pointer
+    /// to its environment of captures before its formals.
+    pub fn function(&self) -> Function {
+        let mut formals = vec![Variable::new(Symbol::new("environment"), PtrType::new(PrimitiveType::I8))];
+        formals.extend(self.formals.iter().cloned());
+        Function::new(
+            Symbol::new(format!("__libruntime__lambda_{}", self.id())),
+            formals,
+            self.ret.clone(),
+            None,
+        )
+    }
+}
+
+impl Identify for LambdaExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
 ///
 #[derive(Clone)]
 pub struct LambdaType {
@@ -301,7 +728,7 @@ impl LambdaType {
     }
 }
 
-///
+/// The definition of a `LetExpr` is an `AssignExpr` to its variable.
 #[derive(Clone)]
 pub struct LetExpr {
     pub identifier: Identifier,
@@ -309,6 +736,141 @@ pub struct LetExpr {
     pub definition: AssignExpr,
 }
 
+impl LetExpr {
+    pub fn new<D: Into<Expr>>(variable: Variable, definition: D) -> LetExpr {
+        LetExpr::new_with_id(Identifier::id(), variable, definition)
+    }
+
+    pub fn new_with_id<D: Into<Expr>>(identifier: Identifier, variable: Variable, definition: D) -> LetExpr {
+        LetExpr {
+            identifier: identifier,
+            variable: variable.clone(),
+            definition: AssignExpr::new(VariableExpr::new(variable), definition),
+        }
+    }
+}
+
+impl Identify for LetExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `LetTupleExpr` binds each element of a tuple to a variable, in order.
+#[derive(Clone)]
+pub struct LetTupleExpr {
+    pub identifier: Identifier,
+    pub variables: Variables,
+    pub definition: Expr,
+}
+
+impl LetTupleExpr {
+    pub fn new<D: Into<Expr>>(variables: Variables, definition: D) -> LetTupleExpr {
+        LetTupleExpr::new_with_id(Identifier::id(), variables, definition)
+    }
+
+    pub fn new_with_id<D: Into<Expr>>(identifier: Identifier, variables: Variables, definition: D) -> LetTupleExpr {
+        LetTupleExpr {
+            identifier: identifier,
+            variables: variables,
+            definition: definition.into(),
+        }
+    }
+}
+
+impl Identify for LetTupleExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `ListExpr` builds a list from its elements. Its type is the type of the
+/// elements.
+#[derive(Clone)]
+pub struct ListExpr {
+    pub identifier: Identifier,
+    pub elements: Exprs,
+    pub ty: Type,
+}
+
+impl ListExpr {
+    pub fn new<E, T>(elements: Vec<E>, ty: T) -> ListExpr
+        where E: Into<Expr>,
+              T: Into<Type>
+    {
+        ListExpr::new_with_id(Identifier::id(), elements, ty)
+    }
+
+    pub fn new_with_id<E, T>(identifier: Identifier, elements: Vec<E>, ty: T) -> ListExpr
+        where E: Into<Expr>,
+              T: Into<Type>
+    {
+        ListExpr {
+            identifier: identifier,
+            elements: elements.into_iter().map(|element| element.into()).collect(),
+            ty: ty.into(),
+        }
+    }
+}
+
+impl Identify for ListExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `ListOp` is an operation on a list. Popping an element gives an
+/// optional, and so `Pop` holds the `EnumType` of the optional.
+#[derive(Clone)]
+pub enum ListOp {
+    Index(Expr, Expr),
+    Len(Expr),
+    Pop(Expr, EnumType),
+    Push(Expr, Expr),
+    Slice(Expr, Expr, Expr),
+}
+
+/// A `ListOpExpr` applies a `ListOp` to a list. Its type is the type of the
+/// elements of the list.
+#[derive(Clone)]
+pub struct ListOpExpr {
+    pub identifier: Identifier,
+    pub op: ListOp,
+    pub ty: Type,
+}
+
+impl ListOpExpr {
+    pub fn new<T: Into<Type>>(op: ListOp, ty: T) -> ListOpExpr {
+        ListOpExpr::new_with_id(Identifier::id(), op, ty)
+    }
+
+    pub fn new_with_id<T: Into<Type>>(identifier: Identifier, op: ListOp, ty: T) -> ListOpExpr {
+        ListOpExpr {
+            identifier: identifier,
+            op: op,
+            ty: ty.into(),
+        }
+    }
+}
+
+impl Identify for ListOpExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `ListType` is a pointer to a list that is managed by the runtime.
+#[derive(Clone)]
+pub struct ListType {
+    pub element: Type,
+}
+
+impl ListType {
+    pub fn new<E: Into<Type>>(element: E) -> ListType {
+        ListType { element: element.into() }
+    }
+}
+
 ///
 #[derive(Clone)]
 pub struct LiteralExpr {
@@ -324,11 +886,62 @@ impl LiteralExpr {
     pub fn new_with_id<L: Into<Literal>>(identifier: Identifier, literal: L) -> LiteralExpr {
         LiteralExpr {
             identifier: identifier,
-            literal: literal.into(),
+            literal: literal.into(),
+        }
+    }
+}
+
+/// An arm without a variant matches every variant.
+#[derive(Clone)]
+pub struct MatchArm {
+    pub variant: Option<Variable>,
+    pub bindings: Variables,
+    pub body: BlockExpr,
+}
+
+impl MatchArm {
+    pub fn new<V>(variant: V, bindings: Variables, body: BlockExpr) -> MatchArm
+        where V: Into<Option<Variable>>
+    {
+        MatchArm {
+            variant: variant.into(),
+            bindings: bindings,
+            body: body,
+        }
+    }
+}
+
+///
+pub type MatchArms = Vec<MatchArm>;
+
+///
+#[derive(Clone)]
+pub struct MatchExpr {
+    pub identifier: Identifier,
+    pub subject: Expr,
+    pub arms: MatchArms,
+}
+
+impl MatchExpr {
+    pub fn new<S: Into<Expr>>(subject: S, arms: MatchArms) -> MatchExpr {
+        MatchExpr::new_with_id(Identifier::id(), subject, arms)
+    }
+
+    pub fn new_with_id<S: Into<Expr>>(identifier: Identifier, subject: S, arms: MatchArms) -> MatchExpr {
+        MatchExpr {
+            identifier: identifier,
+            subject: subject.into(),
+            arms: arms,
         }
     }
 }
 
+impl Identify for MatchExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
 ///
 #[derive(Clone)]
 pub struct Module {
@@ -377,6 +990,8 @@ pub type ModuleTable = HashMap<Identifier, Module>;
 pub struct ProcessExpr {
     pub identifier: Identifier,
     pub body: Box<BlockExpr>,
+    /// The variables from outside of the process that are used by its body.
+    pub captures: Variables,
 }
 
 impl ProcessExpr {
@@ -388,15 +1003,18 @@ impl ProcessExpr {
         ProcessExpr {
             identifier: identifier,
             body: body.into(),
+            captures: Variables::new(),
         }
     }
 
+    /// The profile of the function that a process runs, which is given a
+    /// pointer to its environment of captures.
     pub fn function(&self) -> Function {
         Function::new(
             Symbol::new(format!("__libruntime__process_{}", self.id())),
-            Variables::new(),
+            vec![Variable::new(Symbol::new("environment"), PtrType::new(PrimitiveType::I8))],
             PrimitiveType::Void,
-            self.body.as_ref().clone(),
+            None,
         )
     }
 }
@@ -444,7 +1062,7 @@ pub struct PtrType {
 }
 
 impl PtrType {
-    fn new<I>(inner: I) -> PtrType
+    pub fn new<I>(inner: I) -> PtrType
         where I: Into<Type>
     {
         PtrType { inner: inner.into() }
@@ -457,21 +1075,163 @@ pub struct RefType {
     pub inner: Type,
 }
 
+impl RefType {
+    pub fn new<I>(inner: I) -> RefType
+        where I: Into<Type>
+    {
+        RefType { inner: inner.into() }
+    }
+}
+
+/// A `RangeExpr` is the list of integers from `start` up to, but not
+/// including, `end`. A `ForExpr` over a `RangeExpr` counts without building
+/// the list.
+#[derive(Clone)]
+pub struct RangeExpr {
+    pub identifier: Identifier,
+    pub start: Expr,
+    pub end: Expr,
+}
+
+impl RangeExpr {
+    pub fn new<S, E>(start: S, end: E) -> RangeExpr
+        where S: Into<Expr>,
+              E: Into<Expr>
+    {
+        RangeExpr::new_with_id(Identifier::id(), start, end)
+    }
+
+    pub fn new_with_id<S, E>(identifier: Identifier, start: S, end: E) -> RangeExpr
+        where S: Into<Expr>,
+              E: Into<Expr>
+    {
+        RangeExpr {
+            identifier: identifier,
+            start: start.into(),
+            end: end.into(),
+        }
+    }
+}
+
+impl Identify for RangeExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
 ///
 #[derive(Clone)]
 pub struct RefExpr {
-    identifier: Identifier,
-    variable: Variable,
+    pub identifier: Identifier,
+    pub inner: Expr,
+}
+
+impl RefExpr {
+    pub fn new<I: Into<Expr>>(inner: I) -> RefExpr {
+        RefExpr::new_with_id(Identifier::id(), inner)
+    }
+
+    pub fn new_with_id<I: Into<Expr>>(identifier: Identifier, inner: I) -> RefExpr {
+        RefExpr {
+            identifier: identifier,
+            inner: inner.into(),
+        }
+    }
+}
+
+impl Identify for RefExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `SelectExpr` without an `else` body waits until one of its guards is
+/// ready.
+#[derive(Clone)]
+pub struct SelectExpr {
+    pub identifier: Identifier,
+    pub guards: SelectGuards,
+    pub else_body: Option<BlockExpr>,
+}
+
+impl SelectExpr {
+    pub fn new(guards: SelectGuards, else_body: Option<BlockExpr>) -> SelectExpr {
+        SelectExpr::new_with_id(Identifier::id(), guards, else_body)
+    }
+
+    pub fn new_with_id(identifier: Identifier, guards: SelectGuards, else_body: Option<BlockExpr>) -> SelectExpr {
+        SelectExpr {
+            identifier: identifier,
+            guards: guards,
+            else_body: else_body,
+        }
+    }
+}
+
+impl Identify for SelectExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
 }
 
 ///
 #[derive(Clone)]
-pub struct StructExpr {
-    elements: Vec<(Variable, Expr)>,
-    ty: Type,
+pub enum SelectOp {
+    Recv(Expr, Option<Variable>),
+    Send(Expr, Expr),
+}
+
+/// The type of a `SelectGuard` is the type of the values in its channel.
+#[derive(Clone)]
+pub struct SelectGuard {
+    pub op: SelectOp,
+    pub body: BlockExpr,
+    pub ty: Type,
+}
+
+impl SelectGuard {
+    pub fn new<T: Into<Type>>(op: SelectOp, body: BlockExpr, ty: T) -> SelectGuard {
+        SelectGuard {
+            op: op,
+            body: body,
+            ty: ty.into(),
+        }
+    }
 }
 
 ///
+pub type SelectGuards = Vec<SelectGuard>;
+
+///
+#[derive(Clone)]
+pub struct StructExpr {
+    pub identifier: Identifier,
+    pub elements: Vec<(Variable, Expr)>,
+    pub ty: StructType,
+}
+
+impl StructExpr {
+    pub fn new(elements: Vec<(Variable, Expr)>, ty: StructType) -> StructExpr {
+        StructExpr::new_with_id(Identifier::id(), elements, ty)
+    }
+
+    pub fn new_with_id(identifier: Identifier, elements: Vec<(Variable, Expr)>, ty: StructType) -> StructExpr {
+        StructExpr {
+            identifier: identifier,
+            elements: elements,
+            ty: ty,
+        }
+    }
+}
+
+impl Identify for StructExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// The elements of a `StructType` are laid out in the order that they are
+/// declared.
 #[derive(Clone)]
 pub struct StructType {
     pub symbol: Symbol,
@@ -479,6 +1239,107 @@ pub struct StructType {
     pub elements: Variables,
 }
 
+impl StructType {
+    pub fn new<S, E>(symbol: S, elements: Vec<E>) -> StructType
+        where S: Into<Symbol>,
+              E: Into<Variable>
+    {
+        StructType {
+            symbol: symbol.into(),
+            params: Types::new(),
+            elements: elements
+                .into_iter()
+                .map(|element| element.into())
+                .collect(),
+        }
+    }
+
+    /// Get the position of an element in the layout of the `StructType`.
+    pub fn position(&self, element: &Variable) -> Option<usize> {
+        self.elements.iter().position(|other| other.identify() == element.identify())
+    }
+}
+
+impl Identify for StructType {
+    fn identify(&self) -> Identifier {
+        self.symbol.identify()
+    }
+}
+
+impl Symbolise for StructType {
+    fn symbolise(&self) -> Symbol {
+        self.symbol.clone()
+    }
+}
+
+///
+#[derive(Clone)]
+pub struct TupleExpr {
+    pub identifier: Identifier,
+    pub elements: Exprs,
+}
+
+impl TupleExpr {
+    pub fn new<E: Into<Expr>>(elements: Vec<E>) -> TupleExpr {
+        TupleExpr::new_with_id(Identifier::id(), elements)
+    }
+
+    pub fn new_with_id<E: Into<Expr>>(identifier: Identifier, elements: Vec<E>) -> TupleExpr {
+        TupleExpr {
+            identifier: identifier,
+            elements: elements.into_iter().map(|element| element.into()).collect(),
+        }
+    }
+}
+
+impl Identify for TupleExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
+/// A `TupleType` has the same layout as a `StructType` with the same
+/// elements, but it is identified by its elements rather than by a name.
+#[derive(Clone)]
+pub struct TupleType {
+    pub elements: Types,
+}
+
+impl TupleType {
+    pub fn new<E: Into<Type>>(elements: Vec<E>) -> TupleType {
+        TupleType { elements: elements.into_iter().map(|element| element.into()).collect() }
+    }
+}
+
+/// An `UnwrapExpr` evaluates to the first element of a variant of an enum.
+/// It is a runtime error for the enum to hold any other variant.
+#[derive(Clone)]
+pub struct UnwrapExpr {
+    pub identifier: Identifier,
+    pub inner: Expr,
+    pub variant: Variable,
+}
+
+impl UnwrapExpr {
+    pub fn new<I: Into<Expr>>(inner: I, variant: Variable) -> UnwrapExpr {
+        UnwrapExpr::new_with_id(Identifier::id(), inner, variant)
+    }
+
+    pub fn new_with_id<I: Into<Expr>>(identifier: Identifier, inner: I, variant: Variable) -> UnwrapExpr {
+        UnwrapExpr {
+            identifier: identifier,
+            inner: inner.into(),
+            variant: variant,
+        }
+    }
+}
+
+impl Identify for UnwrapExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
+}
+
 ///
 #[derive(Clone)]
 pub struct Variable {
@@ -513,11 +1374,44 @@ impl Symbolise for Variable {
 ///
 pub type Variables = Vec<Variable>;
 
-///
+/// A `VariableExpr` with a parent refers to an element of the struct, or
+/// the `ref` to a struct, that its parent evaluates to.
 #[derive(Clone)]
 pub struct VariableExpr {
-    variable: Variable,
-    parent: Exprs,
+    pub identifier: Identifier,
+    pub variable: Variable,
+    pub parent: Exprs,
+}
+
+impl VariableExpr {
+    pub fn new<V: Into<Variable>>(variable: V) -> VariableExpr {
+        VariableExpr::new_with_id(Identifier::id(), variable)
+    }
+
+    pub fn new_with_id<V: Into<Variable>>(identifier: Identifier, variable: V) -> VariableExpr {
+        VariableExpr {
+            identifier: identifier,
+            variable: variable.into(),
+            parent: Exprs::new(),
+        }
+    }
+
+    pub fn new_with_parent<P, V>(identifier: Identifier, parent: P, variable: V) -> VariableExpr
+        where P: Into<Expr>,
+              V: Into<Variable>
+    {
+        VariableExpr {
+            identifier: identifier,
+            variable: variable.into(),
+            parent: vec![parent.into()],
+        }
+    }
+}
+
+impl Identify for VariableExpr {
+    fn identify(&self) -> Identifier {
+        self.identifier.clone()
+    }
 }
 
 ///
@@ -547,17 +1441,31 @@ impl Identify for VoidExpr {
 pub enum Expr {
     Assign(Box<AssignExpr>),
     Block(Box<BlockExpr>),
+    Break(Box<BreakExpr>),
     Call(Box<CallExpr>),
+    Channel(Box<ChannelExpr>),
+    ChannelOp(Box<ChannelOpExpr>),
+    Continue(Box<ContinueExpr>),
     Deref(Box<DerefExpr>),
+    Enum(Box<EnumExpr>),
     For(Box<ForExpr>),
     If(Box<IfExpr>),
     Item(Box<ItemExpr>),
+    Lambda(Box<LambdaExpr>),
     Let(Box<LetExpr>),
+    LetTuple(Box<LetTupleExpr>),
+    List(Box<ListExpr>),
+    ListOp(Box<ListOpExpr>),
     Literal(Box<LiteralExpr>),
+    Match(Box<MatchExpr>),
     Process(Box<ProcessExpr>),
     ProcessJoin(Box<ProcessJoinExpr>),
+    Range(Box<RangeExpr>),
     Struct(Box<StructExpr>),
     Ref(Box<RefExpr>),
+    Select(Box<SelectExpr>),
+    Tuple(Box<TupleExpr>),
+    Unwrap(Box<UnwrapExpr>),
     Variable(Box<VariableExpr>),
     Void(Box<VoidExpr>),
 }
@@ -574,24 +1482,108 @@ impl From<BlockExpr> for Expr {
     }
 }
 
+impl From<BreakExpr> for Expr {
+    fn from(break_expr: BreakExpr) -> Expr {
+        Expr::Break(break_expr.into())
+    }
+}
+
 impl From<CallExpr> for Expr {
     fn from(call_expr: CallExpr) -> Expr {
         Expr::Call(call_expr.into())
     }
 }
 
+impl From<ChannelExpr> for Expr {
+    fn from(channel_expr: ChannelExpr) -> Expr {
+        Expr::Channel(channel_expr.into())
+    }
+}
+
+impl From<ChannelOpExpr> for Expr {
+    fn from(channel_op_expr: ChannelOpExpr) -> Expr {
+        Expr::ChannelOp(channel_op_expr.into())
+    }
+}
+
+impl From<IfExpr> for Expr {
+    fn from(if_expr: IfExpr) -> Expr {
+        Expr::If(if_expr.into())
+    }
+}
+
 impl From<ItemExpr> for Expr {
     fn from(item_expr: ItemExpr) -> Expr {
         Expr::Item(item_expr.into())
     }
 }
 
+impl From<LambdaExpr> for Expr {
+    fn from(lambda_expr: LambdaExpr) -> Expr {
+        Expr::Lambda(lambda_expr.into())
+    }
+}
+
+impl From<ContinueExpr> for Expr {
+    fn from(continue_expr: ContinueExpr) -> Expr {
+        Expr::Continue(continue_expr.into())
+    }
+}
+
+impl From<DerefExpr> for Expr {
+    fn from(deref_expr: DerefExpr) -> Expr {
+        Expr::Deref(deref_expr.into())
+    }
+}
+
+impl From<EnumExpr> for Expr {
+    fn from(enum_expr: EnumExpr) -> Expr {
+        Expr::Enum(enum_expr.into())
+    }
+}
+
+impl From<LetExpr> for Expr {
+    fn from(let_expr: LetExpr) -> Expr {
+        Expr::Let(let_expr.into())
+    }
+}
+
+impl From<LetTupleExpr> for Expr {
+    fn from(let_tuple_expr: LetTupleExpr) -> Expr {
+        Expr::LetTuple(let_tuple_expr.into())
+    }
+}
+
+impl From<ForExpr> for Expr {
+    fn from(for_expr: ForExpr) -> Expr {
+        Expr::For(for_expr.into())
+    }
+}
+
+impl From<ListExpr> for Expr {
+    fn from(list_expr: ListExpr) -> Expr {
+        Expr::List(list_expr.into())
+    }
+}
+
+impl From<ListOpExpr> for Expr {
+    fn from(list_op_expr: ListOpExpr) -> Expr {
+        Expr::ListOp(list_op_expr.into())
+    }
+}
+
 impl From<LiteralExpr> for Expr {
     fn from(literal_expr: LiteralExpr) -> Expr {
         Expr::Literal(literal_expr.into())
     }
 }
 
+impl From<MatchExpr> for Expr {
+    fn from(match_expr: MatchExpr) -> Expr {
+        Expr::Match(match_expr.into())
+    }
+}
+
 impl From<ProcessExpr> for Expr {
     fn from(process_expr: ProcessExpr) -> Expr {
         Expr::Process(process_expr.into())
@@ -604,6 +1596,48 @@ impl From<ProcessJoinExpr> for Expr {
     }
 }
 
+impl From<RangeExpr> for Expr {
+    fn from(range_expr: RangeExpr) -> Expr {
+        Expr::Range(range_expr.into())
+    }
+}
+
+impl From<RefExpr> for Expr {
+    fn from(ref_expr: RefExpr) -> Expr {
+        Expr::Ref(ref_expr.into())
+    }
+}
+
+impl From<SelectExpr> for Expr {
+    fn from(select_expr: SelectExpr) -> Expr {
+        Expr::Select(select_expr.into())
+    }
+}
+
+impl From<StructExpr> for Expr {
+    fn from(struct_expr: StructExpr) -> Expr {
+        Expr::Struct(struct_expr.into())
+    }
+}
+
+impl From<TupleExpr> for Expr {
+    fn from(tuple_expr: TupleExpr) -> Expr {
+        Expr::Tuple(tuple_expr.into())
+    }
+}
+
+impl From<UnwrapExpr> for Expr {
+    fn from(unwrap_expr: UnwrapExpr) -> Expr {
+        Expr::Unwrap(unwrap_expr.into())
+    }
+}
+
+impl From<VariableExpr> for Expr {
+    fn from(variable_expr: VariableExpr) -> Expr {
+        Expr::Variable(variable_expr.into())
+    }
+}
+
 impl From<VoidExpr> for Expr {
     fn from(void_expr: VoidExpr) -> Expr {
         Expr::Void(void_expr.into())
@@ -698,12 +1732,34 @@ pub enum PrimitiveType {
 #[derive(Clone)]
 pub enum Type {
     Alias(Box<AliasType>),
+    Channel(Box<ChannelType>),
+    Closure(Box<ClosureType>),
     Enum(Box<EnumType>),
     Lambda(Box<LambdaType>),
+    List(Box<ListType>),
     Primitive(Box<PrimitiveType>),
     Ptr(Box<PtrType>),
     Ref(Box<RefType>),
     Struct(Box<StructType>),
+    Tuple(Box<TupleType>),
+}
+
+impl From<ChannelType> for Type {
+    fn from(ty: ChannelType) -> Type {
+        Type::Channel(ty.into())
+    }
+}
+
+impl From<ClosureType> for Type {
+    fn from(ty: ClosureType) -> Type {
+        Type::Closure(ty.into())
+    }
+}
+
+impl From<EnumType> for Type {
+    fn from(ty: EnumType) -> Type {
+        Type::Enum(ty.into())
+    }
 }
 
 impl From<LambdaType> for Type {
@@ -712,6 +1768,12 @@ impl From<LambdaType> for Type {
     }
 }
 
+impl From<ListType> for Type {
+    fn from(ty: ListType) -> Type {
+        Type::List(ty.into())
+    }
+}
+
 impl From<PrimitiveType> for Type {
     fn from(ty: PrimitiveType) -> Type {
         Type::Primitive(ty.into())
@@ -724,8 +1786,29 @@ impl From<PtrType> for Type {
     }
 }
 
+impl From<RefType> for Type {
+    fn from(ty: RefType) -> Type {
+        Type::Ref(ty.into())
+    }
+}
+
+impl From<StructType> for Type {
+    fn from(ty: StructType) -> Type {
+        Type::Struct(ty.into())
+    }
+}
+
+impl From<TupleType> for Type {
+    fn from(ty: TupleType) -> Type {
+        Type::Tuple(ty.into())
+    }
+}
+
 ///
 pub type Types = Vec<Type>;
 
 ///
-pub type TypeTable = HashMap<Identifier, Type>;
\ No newline at end of file
+pub type TypeTable = HashMap<Identifier, Type>;
+
+///
+pub type SpanTable = HashMap<Identifier, Span>;
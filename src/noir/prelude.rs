@@ -38,6 +38,18 @@ pub struct Prelude {
     pub div_u64_fn: Function,
     pub div_usize_fn: Function,
 
+    pub diveq_f32_fn: Function,
+    pub diveq_f64_fn: Function,
+    pub diveq_i8_fn: Function,
+    pub diveq_i16_fn: Function,
+    pub diveq_i32_fn: Function,
+    pub diveq_i64_fn: Function,
+    pub diveq_u8_fn: Function,
+    pub diveq_u16_fn: Function,
+    pub diveq_u32_fn: Function,
+    pub diveq_u64_fn: Function,
+    pub diveq_usize_fn: Function,
+
     pub mul_f32_fn: Function,
     pub mul_f64_fn: Function,
     pub mul_i8_fn: Function,
@@ -50,6 +62,18 @@ pub struct Prelude {
     pub mul_u64_fn: Function,
     pub mul_usize_fn: Function,
 
+    pub muleq_f32_fn: Function,
+    pub muleq_f64_fn: Function,
+    pub muleq_i8_fn: Function,
+    pub muleq_i16_fn: Function,
+    pub muleq_i32_fn: Function,
+    pub muleq_i64_fn: Function,
+    pub muleq_u8_fn: Function,
+    pub muleq_u16_fn: Function,
+    pub muleq_u32_fn: Function,
+    pub muleq_u64_fn: Function,
+    pub muleq_usize_fn: Function,
+
     pub sub_f32_fn: Function,
     pub sub_f64_fn: Function,
     pub sub_i8_fn: Function,
@@ -62,6 +86,213 @@ pub struct Prelude {
     pub sub_u64_fn: Function,
     pub sub_usize_fn: Function,
 
+    pub subeq_f32_fn: Function,
+    pub subeq_f64_fn: Function,
+    pub subeq_i8_fn: Function,
+    pub subeq_i16_fn: Function,
+    pub subeq_i32_fn: Function,
+    pub subeq_i64_fn: Function,
+    pub subeq_u8_fn: Function,
+    pub subeq_u16_fn: Function,
+    pub subeq_u32_fn: Function,
+    pub subeq_u64_fn: Function,
+    pub subeq_usize_fn: Function,
+
+    pub eq_f32_fn: Function,
+    pub eq_f64_fn: Function,
+    pub eq_i8_fn: Function,
+    pub eq_i16_fn: Function,
+    pub eq_i32_fn: Function,
+    pub eq_i64_fn: Function,
+    pub eq_u8_fn: Function,
+    pub eq_u16_fn: Function,
+    pub eq_u32_fn: Function,
+    pub eq_u64_fn: Function,
+    pub eq_usize_fn: Function,
+
+    pub ne_f32_fn: Function,
+    pub ne_f64_fn: Function,
+    pub ne_i8_fn: Function,
+    pub ne_i16_fn: Function,
+    pub ne_i32_fn: Function,
+    pub ne_i64_fn: Function,
+    pub ne_u8_fn: Function,
+    pub ne_u16_fn: Function,
+    pub ne_u32_fn: Function,
+    pub ne_u64_fn: Function,
+    pub ne_usize_fn: Function,
+
+    pub lt_f32_fn: Function,
+    pub lt_f64_fn: Function,
+    pub lt_i8_fn: Function,
+    pub lt_i16_fn: Function,
+    pub lt_i32_fn: Function,
+    pub lt_i64_fn: Function,
+    pub lt_u8_fn: Function,
+    pub lt_u16_fn: Function,
+    pub lt_u32_fn: Function,
+    pub lt_u64_fn: Function,
+    pub lt_usize_fn: Function,
+
+    pub le_f32_fn: Function,
+    pub le_f64_fn: Function,
+    pub le_i8_fn: Function,
+    pub le_i16_fn: Function,
+    pub le_i32_fn: Function,
+    pub le_i64_fn: Function,
+    pub le_u8_fn: Function,
+    pub le_u16_fn: Function,
+    pub le_u32_fn: Function,
+    pub le_u64_fn: Function,
+    pub le_usize_fn: Function,
+
+    pub gt_f32_fn: Function,
+    pub gt_f64_fn: Function,
+    pub gt_i8_fn: Function,
+    pub gt_i16_fn: Function,
+    pub gt_i32_fn: Function,
+    pub gt_i64_fn: Function,
+    pub gt_u8_fn: Function,
+    pub gt_u16_fn: Function,
+    pub gt_u32_fn: Function,
+    pub gt_u64_fn: Function,
+    pub gt_usize_fn: Function,
+
+    pub ge_f32_fn: Function,
+    pub ge_f64_fn: Function,
+    pub ge_i8_fn: Function,
+    pub ge_i16_fn: Function,
+    pub ge_i32_fn: Function,
+    pub ge_i64_fn: Function,
+    pub ge_u8_fn: Function,
+    pub ge_u16_fn: Function,
+    pub ge_u32_fn: Function,
+    pub ge_u64_fn: Function,
+    pub ge_usize_fn: Function,
+
+    pub and_bool_fn: Function,
+    pub or_bool_fn: Function,
+
+    pub wrapping_add_i8_fn: Function,
+    pub wrapping_add_i16_fn: Function,
+    pub wrapping_add_i32_fn: Function,
+    pub wrapping_add_i64_fn: Function,
+    pub wrapping_add_u8_fn: Function,
+    pub wrapping_add_u16_fn: Function,
+    pub wrapping_add_u32_fn: Function,
+    pub wrapping_add_u64_fn: Function,
+    pub wrapping_add_usize_fn: Function,
+
+    pub wrapping_sub_i8_fn: Function,
+    pub wrapping_sub_i16_fn: Function,
+    pub wrapping_sub_i32_fn: Function,
+    pub wrapping_sub_i64_fn: Function,
+    pub wrapping_sub_u8_fn: Function,
+    pub wrapping_sub_u16_fn: Function,
+    pub wrapping_sub_u32_fn: Function,
+    pub wrapping_sub_u64_fn: Function,
+    pub wrapping_sub_usize_fn: Function,
+
+    pub wrapping_mul_i8_fn: Function,
+    pub wrapping_mul_i16_fn: Function,
+    pub wrapping_mul_i32_fn: Function,
+    pub wrapping_mul_i64_fn: Function,
+    pub wrapping_mul_u8_fn: Function,
+    pub wrapping_mul_u16_fn: Function,
+    pub wrapping_mul_u32_fn: Function,
+    pub wrapping_mul_u64_fn: Function,
+    pub wrapping_mul_usize_fn: Function,
+
+    pub wrapping_div_i8_fn: Function,
+    pub wrapping_div_i16_fn: Function,
+    pub wrapping_div_i32_fn: Function,
+    pub wrapping_div_i64_fn: Function,
+    pub wrapping_div_u8_fn: Function,
+    pub wrapping_div_u16_fn: Function,
+    pub wrapping_div_u32_fn: Function,
+    pub wrapping_div_u64_fn: Function,
+    pub wrapping_div_usize_fn: Function,
+
+    pub saturating_add_i8_fn: Function,
+    pub saturating_add_i16_fn: Function,
+    pub saturating_add_i32_fn: Function,
+    pub saturating_add_i64_fn: Function,
+    pub saturating_add_u8_fn: Function,
+    pub saturating_add_u16_fn: Function,
+    pub saturating_add_u32_fn: Function,
+    pub saturating_add_u64_fn: Function,
+    pub saturating_add_usize_fn: Function,
+
+    pub saturating_sub_i8_fn: Function,
+    pub saturating_sub_i16_fn: Function,
+    pub saturating_sub_i32_fn: Function,
+    pub saturating_sub_i64_fn: Function,
+    pub saturating_sub_u8_fn: Function,
+    pub saturating_sub_u16_fn: Function,
+    pub saturating_sub_u32_fn: Function,
+    pub saturating_sub_u64_fn: Function,
+    pub saturating_sub_usize_fn: Function,
+
+    pub saturating_mul_i8_fn: Function,
+    pub saturating_mul_i16_fn: Function,
+    pub saturating_mul_i32_fn: Function,
+    pub saturating_mul_i64_fn: Function,
+    pub saturating_mul_u8_fn: Function,
+    pub saturating_mul_u16_fn: Function,
+    pub saturating_mul_u32_fn: Function,
+    pub saturating_mul_u64_fn: Function,
+    pub saturating_mul_usize_fn: Function,
+
+    pub saturating_div_i8_fn: Function,
+    pub saturating_div_i16_fn: Function,
+    pub saturating_div_i32_fn: Function,
+    pub saturating_div_i64_fn: Function,
+    pub saturating_div_u8_fn: Function,
+    pub saturating_div_u16_fn: Function,
+    pub saturating_div_u32_fn: Function,
+    pub saturating_div_u64_fn: Function,
+    pub saturating_div_usize_fn: Function,
+
+    pub checked_add_i8_fn: Function,
+    pub checked_add_i16_fn: Function,
+    pub checked_add_i32_fn: Function,
+    pub checked_add_i64_fn: Function,
+    pub checked_add_u8_fn: Function,
+    pub checked_add_u16_fn: Function,
+    pub checked_add_u32_fn: Function,
+    pub checked_add_u64_fn: Function,
+    pub checked_add_usize_fn: Function,
+
+    pub checked_sub_i8_fn: Function,
+    pub checked_sub_i16_fn: Function,
+    pub checked_sub_i32_fn: Function,
+    pub checked_sub_i64_fn: Function,
+    pub checked_sub_u8_fn: Function,
+    pub checked_sub_u16_fn: Function,
+    pub checked_sub_u32_fn: Function,
+    pub checked_sub_u64_fn: Function,
+    pub checked_sub_usize_fn: Function,
+
+    pub checked_mul_i8_fn: Function,
+    pub checked_mul_i16_fn: Function,
+    pub checked_mul_i32_fn: Function,
+    pub checked_mul_i64_fn: Function,
+    pub checked_mul_u8_fn: Function,
+    pub checked_mul_u16_fn: Function,
+    pub checked_mul_u32_fn: Function,
+    pub checked_mul_u64_fn: Function,
+    pub checked_mul_usize_fn: Function,
+
+    pub checked_div_i8_fn: Function,
+    pub checked_div_i16_fn: Function,
+    pub checked_div_i32_fn: Function,
+    pub checked_div_i64_fn: Function,
+    pub checked_div_u8_fn: Function,
+    pub checked_div_u16_fn: Function,
+    pub checked_div_u32_fn: Function,
+    pub checked_div_u64_fn: Function,
+    pub checked_div_usize_fn: Function,
+
     pub concat_string_bool_fn: Function,
     pub concat_string_char_fn: Function,
     pub concat_string_f32_fn: Function,
@@ -147,6 +378,18 @@ impl Prelude {
             div_u64_fn: div_fn!("u64", PrimitiveType::U64),
             div_usize_fn: div_fn!("usize", PrimitiveType::USize),
 
+            diveq_f32_fn: diveq_fn!("f32", PrimitiveType::F32),
+            diveq_f64_fn: diveq_fn!("f64", PrimitiveType::F64),
+            diveq_i8_fn: diveq_fn!("i8", PrimitiveType::I8),
+            diveq_i16_fn: diveq_fn!("i16", PrimitiveType::I16),
+            diveq_i32_fn: diveq_fn!("i32", PrimitiveType::I32),
+            diveq_i64_fn: diveq_fn!("i64", PrimitiveType::I64),
+            diveq_u8_fn: diveq_fn!("u8", PrimitiveType::U8),
+            diveq_u16_fn: diveq_fn!("u16", PrimitiveType::U16),
+            diveq_u32_fn: diveq_fn!("u32", PrimitiveType::U32),
+            diveq_u64_fn: diveq_fn!("u64", PrimitiveType::U64),
+            diveq_usize_fn: diveq_fn!("usize", PrimitiveType::USize),
+
             mul_f32_fn: mul_fn!("f32", PrimitiveType::F32),
             mul_f64_fn: mul_fn!("f64", PrimitiveType::F64),
             mul_i8_fn: mul_fn!("i8", PrimitiveType::I8),
@@ -159,6 +402,18 @@ impl Prelude {
             mul_u64_fn: mul_fn!("u64", PrimitiveType::U64),
             mul_usize_fn: mul_fn!("usize", PrimitiveType::USize),
 
+            muleq_f32_fn: muleq_fn!("f32", PrimitiveType::F32),
+            muleq_f64_fn: muleq_fn!("f64", PrimitiveType::F64),
+            muleq_i8_fn: muleq_fn!("i8", PrimitiveType::I8),
+            muleq_i16_fn: muleq_fn!("i16", PrimitiveType::I16),
+            muleq_i32_fn: muleq_fn!("i32", PrimitiveType::I32),
+            muleq_i64_fn: muleq_fn!("i64", PrimitiveType::I64),
+            muleq_u8_fn: muleq_fn!("u8", PrimitiveType::U8),
+            muleq_u16_fn: muleq_fn!("u16", PrimitiveType::U16),
+            muleq_u32_fn: muleq_fn!("u32", PrimitiveType::U32),
+            muleq_u64_fn: muleq_fn!("u64", PrimitiveType::U64),
+            muleq_usize_fn: muleq_fn!("usize", PrimitiveType::USize),
+
             sub_f32_fn: sub_fn!("f32", PrimitiveType::F32),
             sub_f64_fn: sub_fn!("f64", PrimitiveType::F64),
             sub_i8_fn: sub_fn!("i8", PrimitiveType::I8),
@@ -171,6 +426,213 @@ impl Prelude {
             sub_u64_fn: sub_fn!("u64", PrimitiveType::U64),
             sub_usize_fn: sub_fn!("usize", PrimitiveType::USize),
 
+            subeq_f32_fn: subeq_fn!("f32", PrimitiveType::F32),
+            subeq_f64_fn: subeq_fn!("f64", PrimitiveType::F64),
+            subeq_i8_fn: subeq_fn!("i8", PrimitiveType::I8),
+            subeq_i16_fn: subeq_fn!("i16", PrimitiveType::I16),
+            subeq_i32_fn: subeq_fn!("i32", PrimitiveType::I32),
+            subeq_i64_fn: subeq_fn!("i64", PrimitiveType::I64),
+            subeq_u8_fn: subeq_fn!("u8", PrimitiveType::U8),
+            subeq_u16_fn: subeq_fn!("u16", PrimitiveType::U16),
+            subeq_u32_fn: subeq_fn!("u32", PrimitiveType::U32),
+            subeq_u64_fn: subeq_fn!("u64", PrimitiveType::U64),
+            subeq_usize_fn: subeq_fn!("usize", PrimitiveType::USize),
+
+            eq_f32_fn: eq_fn!("f32", PrimitiveType::F32),
+            eq_f64_fn: eq_fn!("f64", PrimitiveType::F64),
+            eq_i8_fn: eq_fn!("i8", PrimitiveType::I8),
+            eq_i16_fn: eq_fn!("i16", PrimitiveType::I16),
+            eq_i32_fn: eq_fn!("i32", PrimitiveType::I32),
+            eq_i64_fn: eq_fn!("i64", PrimitiveType::I64),
+            eq_u8_fn: eq_fn!("u8", PrimitiveType::U8),
+            eq_u16_fn: eq_fn!("u16", PrimitiveType::U16),
+            eq_u32_fn: eq_fn!("u32", PrimitiveType::U32),
+            eq_u64_fn: eq_fn!("u64", PrimitiveType::U64),
+            eq_usize_fn: eq_fn!("usize", PrimitiveType::USize),
+
+            ne_f32_fn: ne_fn!("f32", PrimitiveType::F32),
+            ne_f64_fn: ne_fn!("f64", PrimitiveType::F64),
+            ne_i8_fn: ne_fn!("i8", PrimitiveType::I8),
+            ne_i16_fn: ne_fn!("i16", PrimitiveType::I16),
+            ne_i32_fn: ne_fn!("i32", PrimitiveType::I32),
+            ne_i64_fn: ne_fn!("i64", PrimitiveType::I64),
+            ne_u8_fn: ne_fn!("u8", PrimitiveType::U8),
+            ne_u16_fn: ne_fn!("u16", PrimitiveType::U16),
+            ne_u32_fn: ne_fn!("u32", PrimitiveType::U32),
+            ne_u64_fn: ne_fn!("u64", PrimitiveType::U64),
+            ne_usize_fn: ne_fn!("usize", PrimitiveType::USize),
+
+            lt_f32_fn: lt_fn!("f32", PrimitiveType::F32),
+            lt_f64_fn: lt_fn!("f64", PrimitiveType::F64),
+            lt_i8_fn: lt_fn!("i8", PrimitiveType::I8),
+            lt_i16_fn: lt_fn!("i16", PrimitiveType::I16),
+            lt_i32_fn: lt_fn!("i32", PrimitiveType::I32),
+            lt_i64_fn: lt_fn!("i64", PrimitiveType::I64),
+            lt_u8_fn: lt_fn!("u8", PrimitiveType::U8),
+            lt_u16_fn: lt_fn!("u16", PrimitiveType::U16),
+            lt_u32_fn: lt_fn!("u32", PrimitiveType::U32),
+            lt_u64_fn: lt_fn!("u64", PrimitiveType::U64),
+            lt_usize_fn: lt_fn!("usize", PrimitiveType::USize),
+
+            le_f32_fn: le_fn!("f32", PrimitiveType::F32),
+            le_f64_fn: le_fn!("f64", PrimitiveType::F64),
+            le_i8_fn: le_fn!("i8", PrimitiveType::I8),
+            le_i16_fn: le_fn!("i16", PrimitiveType::I16),
+            le_i32_fn: le_fn!("i32", PrimitiveType::I32),
+            le_i64_fn: le_fn!("i64", PrimitiveType::I64),
+            le_u8_fn: le_fn!("u8", PrimitiveType::U8),
+            le_u16_fn: le_fn!("u16", PrimitiveType::U16),
+            le_u32_fn: le_fn!("u32", PrimitiveType::U32),
+            le_u64_fn: le_fn!("u64", PrimitiveType::U64),
+            le_usize_fn: le_fn!("usize", PrimitiveType::USize),
+
+            gt_f32_fn: gt_fn!("f32", PrimitiveType::F32),
+            gt_f64_fn: gt_fn!("f64", PrimitiveType::F64),
+            gt_i8_fn: gt_fn!("i8", PrimitiveType::I8),
+            gt_i16_fn: gt_fn!("i16", PrimitiveType::I16),
+            gt_i32_fn: gt_fn!("i32", PrimitiveType::I32),
+            gt_i64_fn: gt_fn!("i64", PrimitiveType::I64),
+            gt_u8_fn: gt_fn!("u8", PrimitiveType::U8),
+            gt_u16_fn: gt_fn!("u16", PrimitiveType::U16),
+            gt_u32_fn: gt_fn!("u32", PrimitiveType::U32),
+            gt_u64_fn: gt_fn!("u64", PrimitiveType::U64),
+            gt_usize_fn: gt_fn!("usize", PrimitiveType::USize),
+
+            ge_f32_fn: ge_fn!("f32", PrimitiveType::F32),
+            ge_f64_fn: ge_fn!("f64", PrimitiveType::F64),
+            ge_i8_fn: ge_fn!("i8", PrimitiveType::I8),
+            ge_i16_fn: ge_fn!("i16", PrimitiveType::I16),
+            ge_i32_fn: ge_fn!("i32", PrimitiveType::I32),
+            ge_i64_fn: ge_fn!("i64", PrimitiveType::I64),
+            ge_u8_fn: ge_fn!("u8", PrimitiveType::U8),
+            ge_u16_fn: ge_fn!("u16", PrimitiveType::U16),
+            ge_u32_fn: ge_fn!("u32", PrimitiveType::U32),
+            ge_u64_fn: ge_fn!("u64", PrimitiveType::U64),
+            ge_usize_fn: ge_fn!("usize", PrimitiveType::USize),
+
+            and_bool_fn: and_fn!("bool", PrimitiveType::Bool),
+            or_bool_fn: or_fn!("bool", PrimitiveType::Bool),
+
+            wrapping_add_i8_fn: wrapping_add_fn!("i8", PrimitiveType::I8),
+            wrapping_add_i16_fn: wrapping_add_fn!("i16", PrimitiveType::I16),
+            wrapping_add_i32_fn: wrapping_add_fn!("i32", PrimitiveType::I32),
+            wrapping_add_i64_fn: wrapping_add_fn!("i64", PrimitiveType::I64),
+            wrapping_add_u8_fn: wrapping_add_fn!("u8", PrimitiveType::U8),
+            wrapping_add_u16_fn: wrapping_add_fn!("u16", PrimitiveType::U16),
+            wrapping_add_u32_fn: wrapping_add_fn!("u32", PrimitiveType::U32),
+            wrapping_add_u64_fn: wrapping_add_fn!("u64", PrimitiveType::U64),
+            wrapping_add_usize_fn: wrapping_add_fn!("usize", PrimitiveType::USize),
+
+            wrapping_sub_i8_fn: wrapping_sub_fn!("i8", PrimitiveType::I8),
+            wrapping_sub_i16_fn: wrapping_sub_fn!("i16", PrimitiveType::I16),
+            wrapping_sub_i32_fn: wrapping_sub_fn!("i32", PrimitiveType::I32),
+            wrapping_sub_i64_fn: wrapping_sub_fn!("i64", PrimitiveType::I64),
+            wrapping_sub_u8_fn: wrapping_sub_fn!("u8", PrimitiveType::U8),
+            wrapping_sub_u16_fn: wrapping_sub_fn!("u16", PrimitiveType::U16),
+            wrapping_sub_u32_fn: wrapping_sub_fn!("u32", PrimitiveType::U32),
+            wrapping_sub_u64_fn: wrapping_sub_fn!("u64", PrimitiveType::U64),
+            wrapping_sub_usize_fn: wrapping_sub_fn!("usize", PrimitiveType::USize),
+
+            wrapping_mul_i8_fn: wrapping_mul_fn!("i8", PrimitiveType::I8),
+            wrapping_mul_i16_fn: wrapping_mul_fn!("i16", PrimitiveType::I16),
+            wrapping_mul_i32_fn: wrapping_mul_fn!("i32", PrimitiveType::I32),
+            wrapping_mul_i64_fn: wrapping_mul_fn!("i64", PrimitiveType::I64),
+            wrapping_mul_u8_fn: wrapping_mul_fn!("u8", PrimitiveType::U8),
+            wrapping_mul_u16_fn: wrapping_mul_fn!("u16", PrimitiveType::U16),
+            wrapping_mul_u32_fn: wrapping_mul_fn!("u32", PrimitiveType::U32),
+            wrapping_mul_u64_fn: wrapping_mul_fn!("u64", PrimitiveType::U64),
+            wrapping_mul_usize_fn: wrapping_mul_fn!("usize", PrimitiveType::USize),
+
+            wrapping_div_i8_fn: wrapping_div_fn!("i8", PrimitiveType::I8),
+            wrapping_div_i16_fn: wrapping_div_fn!("i16", PrimitiveType::I16),
+            wrapping_div_i32_fn: wrapping_div_fn!("i32", PrimitiveType::I32),
+            wrapping_div_i64_fn: wrapping_div_fn!("i64", PrimitiveType::I64),
+            wrapping_div_u8_fn: wrapping_div_fn!("u8", PrimitiveType::U8),
+            wrapping_div_u16_fn: wrapping_div_fn!("u16", PrimitiveType::U16),
+            wrapping_div_u32_fn: wrapping_div_fn!("u32", PrimitiveType::U32),
+            wrapping_div_u64_fn: wrapping_div_fn!("u64", PrimitiveType::U64),
+            wrapping_div_usize_fn: wrapping_div_fn!("usize", PrimitiveType::USize),
+
+            saturating_add_i8_fn: saturating_add_fn!("i8", PrimitiveType::I8),
+            saturating_add_i16_fn: saturating_add_fn!("i16", PrimitiveType::I16),
+            saturating_add_i32_fn: saturating_add_fn!("i32", PrimitiveType::I32),
+            saturating_add_i64_fn: saturating_add_fn!("i64", PrimitiveType::I64),
+            saturating_add_u8_fn: saturating_add_fn!("u8", PrimitiveType::U8),
+            saturating_add_u16_fn: saturating_add_fn!("u16", PrimitiveType::U16),
+            saturating_add_u32_fn: saturating_add_fn!("u32", PrimitiveType::U32),
+            saturating_add_u64_fn: saturating_add_fn!("u64", PrimitiveType::U64),
+            saturating_add_usize_fn: saturating_add_fn!("usize", PrimitiveType::USize),
+
+            saturating_sub_i8_fn: saturating_sub_fn!("i8", PrimitiveType::I8),
+            saturating_sub_i16_fn: saturating_sub_fn!("i16", PrimitiveType::I16),
+            saturating_sub_i32_fn: saturating_sub_fn!("i32", PrimitiveType::I32),
+            saturating_sub_i64_fn: saturating_sub_fn!("i64", PrimitiveType::I64),
+            saturating_sub_u8_fn: saturating_sub_fn!("u8", PrimitiveType::U8),
+            saturating_sub_u16_fn: saturating_sub_fn!("u16", PrimitiveType::U16),
+            saturating_sub_u32_fn: saturating_sub_fn!("u32", PrimitiveType::U32),
+            saturating_sub_u64_fn: saturating_sub_fn!("u64", PrimitiveType::U64),
+            saturating_sub_usize_fn: saturating_sub_fn!("usize", PrimitiveType::USize),
+
+            saturating_mul_i8_fn: saturating_mul_fn!("i8", PrimitiveType::I8),
+            saturating_mul_i16_fn: saturating_mul_fn!("i16", PrimitiveType::I16),
+            saturating_mul_i32_fn: saturating_mul_fn!("i32", PrimitiveType::I32),
+            saturating_mul_i64_fn: saturating_mul_fn!("i64", PrimitiveType::I64),
+            saturating_mul_u8_fn: saturating_mul_fn!("u8", PrimitiveType::U8),
+            saturating_mul_u16_fn: saturating_mul_fn!("u16", PrimitiveType::U16),
+            saturating_mul_u32_fn: saturating_mul_fn!("u32", PrimitiveType::U32),
+            saturating_mul_u64_fn: saturating_mul_fn!("u64", PrimitiveType::U64),
+            saturating_mul_usize_fn: saturating_mul_fn!("usize", PrimitiveType::USize),
+
+            saturating_div_i8_fn: saturating_div_fn!("i8", PrimitiveType::I8),
+            saturating_div_i16_fn: saturating_div_fn!("i16", PrimitiveType::I16),
+            saturating_div_i32_fn: saturating_div_fn!("i32", PrimitiveType::I32),
+            saturating_div_i64_fn: saturating_div_fn!("i64", PrimitiveType::I64),
+            saturating_div_u8_fn: saturating_div_fn!("u8", PrimitiveType::U8),
+            saturating_div_u16_fn: saturating_div_fn!("u16", PrimitiveType::U16),
+            saturating_div_u32_fn: saturating_div_fn!("u32", PrimitiveType::U32),
+            saturating_div_u64_fn: saturating_div_fn!("u64", PrimitiveType::U64),
+            saturating_div_usize_fn: saturating_div_fn!("usize", PrimitiveType::USize),
+
+            checked_add_i8_fn: checked_add_fn!("i8", PrimitiveType::I8),
+            checked_add_i16_fn: checked_add_fn!("i16", PrimitiveType::I16),
+            checked_add_i32_fn: checked_add_fn!("i32", PrimitiveType::I32),
+            checked_add_i64_fn: checked_add_fn!("i64", PrimitiveType::I64),
+            checked_add_u8_fn: checked_add_fn!("u8", PrimitiveType::U8),
+            checked_add_u16_fn: checked_add_fn!("u16", PrimitiveType::U16),
+            checked_add_u32_fn: checked_add_fn!("u32", PrimitiveType::U32),
+            checked_add_u64_fn: checked_add_fn!("u64", PrimitiveType::U64),
+            checked_add_usize_fn: checked_add_fn!("usize", PrimitiveType::USize),
+
+            checked_sub_i8_fn: checked_sub_fn!("i8", PrimitiveType::I8),
+            checked_sub_i16_fn: checked_sub_fn!("i16", PrimitiveType::I16),
+            checked_sub_i32_fn: checked_sub_fn!("i32", PrimitiveType::I32),
+            checked_sub_i64_fn: checked_sub_fn!("i64", PrimitiveType::I64),
+            checked_sub_u8_fn: checked_sub_fn!("u8", PrimitiveType::U8),
+            checked_sub_u16_fn: checked_sub_fn!("u16", PrimitiveType::U16),
+            checked_sub_u32_fn: checked_sub_fn!("u32", PrimitiveType::U32),
+            checked_sub_u64_fn: checked_sub_fn!("u64", PrimitiveType::U64),
+            checked_sub_usize_fn: checked_sub_fn!("usize", PrimitiveType::USize),
+
+            checked_mul_i8_fn: checked_mul_fn!("i8", PrimitiveType::I8),
+            checked_mul_i16_fn: checked_mul_fn!("i16", PrimitiveType::I16),
+            checked_mul_i32_fn: checked_mul_fn!("i32", PrimitiveType::I32),
+            checked_mul_i64_fn: checked_mul_fn!("i64", PrimitiveType::I64),
+            checked_mul_u8_fn: checked_mul_fn!("u8", PrimitiveType::U8),
+            checked_mul_u16_fn: checked_mul_fn!("u16", PrimitiveType::U16),
+            checked_mul_u32_fn: checked_mul_fn!("u32", PrimitiveType::U32),
+            checked_mul_u64_fn: checked_mul_fn!("u64", PrimitiveType::U64),
+            checked_mul_usize_fn: checked_mul_fn!("usize", PrimitiveType::USize),
+
+            checked_div_i8_fn: checked_div_fn!("i8", PrimitiveType::I8),
+            checked_div_i16_fn: checked_div_fn!("i16", PrimitiveType::I16),
+            checked_div_i32_fn: checked_div_fn!("i32", PrimitiveType::I32),
+            checked_div_i64_fn: checked_div_fn!("i64", PrimitiveType::I64),
+            checked_div_u8_fn: checked_div_fn!("u8", PrimitiveType::U8),
+            checked_div_u16_fn: checked_div_fn!("u16", PrimitiveType::U16),
+            checked_div_u32_fn: checked_div_fn!("u32", PrimitiveType::U32),
+            checked_div_u64_fn: checked_div_fn!("u64", PrimitiveType::U64),
+            checked_div_usize_fn: checked_div_fn!("usize", PrimitiveType::USize),
+
             concat_string_bool_fn: concat_string_fn!("bool", PrimitiveType::Bool),
             concat_string_char_fn: concat_string_fn!("char", PrimitiveType::Char),
             concat_string_f32_fn: concat_string_fn!("f32", PrimitiveType::F32),
@@ -231,6 +693,18 @@ impl Prelude {
              self.add_u64_fn.clone(),
              self.add_usize_fn.clone(),
 
+             self.addeq_f32_fn.clone(),
+             self.addeq_f64_fn.clone(),
+             self.addeq_i8_fn.clone(),
+             self.addeq_i16_fn.clone(),
+             self.addeq_i32_fn.clone(),
+             self.addeq_i64_fn.clone(),
+             self.addeq_u8_fn.clone(),
+             self.addeq_u16_fn.clone(),
+             self.addeq_u32_fn.clone(),
+             self.addeq_u64_fn.clone(),
+             self.addeq_usize_fn.clone(),
+
              self.div_f32_fn.clone(),
              self.div_f64_fn.clone(),
              self.div_i8_fn.clone(),
@@ -243,6 +717,18 @@ impl Prelude {
              self.div_u64_fn.clone(),
              self.div_usize_fn.clone(),
 
+             self.diveq_f32_fn.clone(),
+             self.diveq_f64_fn.clone(),
+             self.diveq_i8_fn.clone(),
+             self.diveq_i16_fn.clone(),
+             self.diveq_i32_fn.clone(),
+             self.diveq_i64_fn.clone(),
+             self.diveq_u8_fn.clone(),
+             self.diveq_u16_fn.clone(),
+             self.diveq_u32_fn.clone(),
+             self.diveq_u64_fn.clone(),
+             self.diveq_usize_fn.clone(),
+
              self.mul_f32_fn.clone(),
              self.mul_f64_fn.clone(),
              self.mul_i8_fn.clone(),
@@ -255,6 +741,18 @@ impl Prelude {
              self.mul_u64_fn.clone(),
              self.mul_usize_fn.clone(),
 
+             self.muleq_f32_fn.clone(),
+             self.muleq_f64_fn.clone(),
+             self.muleq_i8_fn.clone(),
+             self.muleq_i16_fn.clone(),
+             self.muleq_i32_fn.clone(),
+             self.muleq_i64_fn.clone(),
+             self.muleq_u8_fn.clone(),
+             self.muleq_u16_fn.clone(),
+             self.muleq_u32_fn.clone(),
+             self.muleq_u64_fn.clone(),
+             self.muleq_usize_fn.clone(),
+
              self.sub_f32_fn.clone(),
              self.sub_f64_fn.clone(),
              self.sub_i8_fn.clone(),
@@ -267,6 +765,213 @@ impl Prelude {
              self.sub_u64_fn.clone(),
              self.sub_usize_fn.clone(),
 
+             self.subeq_f32_fn.clone(),
+             self.subeq_f64_fn.clone(),
+             self.subeq_i8_fn.clone(),
+             self.subeq_i16_fn.clone(),
+             self.subeq_i32_fn.clone(),
+             self.subeq_i64_fn.clone(),
+             self.subeq_u8_fn.clone(),
+             self.subeq_u16_fn.clone(),
+             self.subeq_u32_fn.clone(),
+             self.subeq_u64_fn.clone(),
+             self.subeq_usize_fn.clone(),
+
+             self.eq_f32_fn.clone(),
+             self.eq_f64_fn.clone(),
+             self.eq_i8_fn.clone(),
+             self.eq_i16_fn.clone(),
+             self.eq_i32_fn.clone(),
+             self.eq_i64_fn.clone(),
+             self.eq_u8_fn.clone(),
+             self.eq_u16_fn.clone(),
+             self.eq_u32_fn.clone(),
+             self.eq_u64_fn.clone(),
+             self.eq_usize_fn.clone(),
+
+             self.ne_f32_fn.clone(),
+             self.ne_f64_fn.clone(),
+             self.ne_i8_fn.clone(),
+             self.ne_i16_fn.clone(),
+             self.ne_i32_fn.clone(),
+             self.ne_i64_fn.clone(),
+             self.ne_u8_fn.clone(),
+             self.ne_u16_fn.clone(),
+             self.ne_u32_fn.clone(),
+             self.ne_u64_fn.clone(),
+             self.ne_usize_fn.clone(),
+
+             self.lt_f32_fn.clone(),
+             self.lt_f64_fn.clone(),
+             self.lt_i8_fn.clone(),
+             self.lt_i16_fn.clone(),
+             self.lt_i32_fn.clone(),
+             self.lt_i64_fn.clone(),
+             self.lt_u8_fn.clone(),
+             self.lt_u16_fn.clone(),
+             self.lt_u32_fn.clone(),
+             self.lt_u64_fn.clone(),
+             self.lt_usize_fn.clone(),
+
+             self.le_f32_fn.clone(),
+             self.le_f64_fn.clone(),
+             self.le_i8_fn.clone(),
+             self.le_i16_fn.clone(),
+             self.le_i32_fn.clone(),
+             self.le_i64_fn.clone(),
+             self.le_u8_fn.clone(),
+             self.le_u16_fn.clone(),
+             self.le_u32_fn.clone(),
+             self.le_u64_fn.clone(),
+             self.le_usize_fn.clone(),
+
+             self.gt_f32_fn.clone(),
+             self.gt_f64_fn.clone(),
+             self.gt_i8_fn.clone(),
+             self.gt_i16_fn.clone(),
+             self.gt_i32_fn.clone(),
+             self.gt_i64_fn.clone(),
+             self.gt_u8_fn.clone(),
+             self.gt_u16_fn.clone(),
+             self.gt_u32_fn.clone(),
+             self.gt_u64_fn.clone(),
+             self.gt_usize_fn.clone(),
+
+             self.ge_f32_fn.clone(),
+             self.ge_f64_fn.clone(),
+             self.ge_i8_fn.clone(),
+             self.ge_i16_fn.clone(),
+             self.ge_i32_fn.clone(),
+             self.ge_i64_fn.clone(),
+             self.ge_u8_fn.clone(),
+             self.ge_u16_fn.clone(),
+             self.ge_u32_fn.clone(),
+             self.ge_u64_fn.clone(),
+             self.ge_usize_fn.clone(),
+
+             self.and_bool_fn.clone(),
+             self.or_bool_fn.clone(),
+
+             self.wrapping_add_i8_fn.clone(),
+             self.wrapping_add_i16_fn.clone(),
+             self.wrapping_add_i32_fn.clone(),
+             self.wrapping_add_i64_fn.clone(),
+             self.wrapping_add_u8_fn.clone(),
+             self.wrapping_add_u16_fn.clone(),
+             self.wrapping_add_u32_fn.clone(),
+             self.wrapping_add_u64_fn.clone(),
+             self.wrapping_add_usize_fn.clone(),
+
+             self.wrapping_sub_i8_fn.clone(),
+             self.wrapping_sub_i16_fn.clone(),
+             self.wrapping_sub_i32_fn.clone(),
+             self.wrapping_sub_i64_fn.clone(),
+             self.wrapping_sub_u8_fn.clone(),
+             self.wrapping_sub_u16_fn.clone(),
+             self.wrapping_sub_u32_fn.clone(),
+             self.wrapping_sub_u64_fn.clone(),
+             self.wrapping_sub_usize_fn.clone(),
+
+             self.wrapping_mul_i8_fn.clone(),
+             self.wrapping_mul_i16_fn.clone(),
+             self.wrapping_mul_i32_fn.clone(),
+             self.wrapping_mul_i64_fn.clone(),
+             self.wrapping_mul_u8_fn.clone(),
+             self.wrapping_mul_u16_fn.clone(),
+             self.wrapping_mul_u32_fn.clone(),
+             self.wrapping_mul_u64_fn.clone(),
+             self.wrapping_mul_usize_fn.clone(),
+
+             self.wrapping_div_i8_fn.clone(),
+             self.wrapping_div_i16_fn.clone(),
+             self.wrapping_div_i32_fn.clone(),
+             self.wrapping_div_i64_fn.clone(),
+             self.wrapping_div_u8_fn.clone(),
+             self.wrapping_div_u16_fn.clone(),
+             self.wrapping_div_u32_fn.clone(),
+             self.wrapping_div_u64_fn.clone(),
+             self.wrapping_div_usize_fn.clone(),
+
+             self.saturating_add_i8_fn.clone(),
+             self.saturating_add_i16_fn.clone(),
+             self.saturating_add_i32_fn.clone(),
+             self.saturating_add_i64_fn.clone(),
+             self.saturating_add_u8_fn.clone(),
+             self.saturating_add_u16_fn.clone(),
+             self.saturating_add_u32_fn.clone(),
+             self.saturating_add_u64_fn.clone(),
+             self.saturating_add_usize_fn.clone(),
+
+             self.saturating_sub_i8_fn.clone(),
+             self.saturating_sub_i16_fn.clone(),
+             self.saturating_sub_i32_fn.clone(),
+             self.saturating_sub_i64_fn.clone(),
+             self.saturating_sub_u8_fn.clone(),
+             self.saturating_sub_u16_fn.clone(),
+             self.saturating_sub_u32_fn.clone(),
+             self.saturating_sub_u64_fn.clone(),
+             self.saturating_sub_usize_fn.clone(),
+
+             self.saturating_mul_i8_fn.clone(),
+             self.saturating_mul_i16_fn.clone(),
+             self.saturating_mul_i32_fn.clone(),
+             self.saturating_mul_i64_fn.clone(),
+             self.saturating_mul_u8_fn.clone(),
+             self.saturating_mul_u16_fn.clone(),
+             self.saturating_mul_u32_fn.clone(),
+             self.saturating_mul_u64_fn.clone(),
+             self.saturating_mul_usize_fn.clone(),
+
+             self.saturating_div_i8_fn.clone(),
+             self.saturating_div_i16_fn.clone(),
+             self.saturating_div_i32_fn.clone(),
+             self.saturating_div_i64_fn.clone(),
+             self.saturating_div_u8_fn.clone(),
+             self.saturating_div_u16_fn.clone(),
+             self.saturating_div_u32_fn.clone(),
+             self.saturating_div_u64_fn.clone(),
+             self.saturating_div_usize_fn.clone(),
+
+             self.checked_add_i8_fn.clone(),
+             self.checked_add_i16_fn.clone(),
+             self.checked_add_i32_fn.clone(),
+             self.checked_add_i64_fn.clone(),
+             self.checked_add_u8_fn.clone(),
+             self.checked_add_u16_fn.clone(),
+             self.checked_add_u32_fn.clone(),
+             self.checked_add_u64_fn.clone(),
+             self.checked_add_usize_fn.clone(),
+
+             self.checked_sub_i8_fn.clone(),
+             self.checked_sub_i16_fn.clone(),
+             self.checked_sub_i32_fn.clone(),
+             self.checked_sub_i64_fn.clone(),
+             self.checked_sub_u8_fn.clone(),
+             self.checked_sub_u16_fn.clone(),
+             self.checked_sub_u32_fn.clone(),
+             self.checked_sub_u64_fn.clone(),
+             self.checked_sub_usize_fn.clone(),
+
+             self.checked_mul_i8_fn.clone(),
+             self.checked_mul_i16_fn.clone(),
+             self.checked_mul_i32_fn.clone(),
+             self.checked_mul_i64_fn.clone(),
+             self.checked_mul_u8_fn.clone(),
+             self.checked_mul_u16_fn.clone(),
+             self.checked_mul_u32_fn.clone(),
+             self.checked_mul_u64_fn.clone(),
+             self.checked_mul_usize_fn.clone(),
+
+             self.checked_div_i8_fn.clone(),
+             self.checked_div_i16_fn.clone(),
+             self.checked_div_i32_fn.clone(),
+             self.checked_div_i64_fn.clone(),
+             self.checked_div_u8_fn.clone(),
+             self.checked_div_u16_fn.clone(),
+             self.checked_div_u32_fn.clone(),
+             self.checked_div_u64_fn.clone(),
+             self.checked_div_usize_fn.clone(),
+
              self.concat_string_bool_fn.clone(),
              self.concat_string_char_fn.clone(),
              self.concat_string_f32_fn.clone(),
@@ -312,4 +1017,106 @@ impl Prelude {
              self.writeln_u64_fn.clone(),
              self.writeln_usize_fn.clone()]
     }
-}
\ No newline at end of file
+}
+
+/// A binary operator of the prelude, which a backend can build as an
+/// instruction instead of calling the prelude library.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PreludeOp {
+    Add,
+    Div,
+    Eq,
+    Ge,
+    Gt,
+    Le,
+    Lt,
+    Mul,
+    Ne,
+    Sub,
+}
+
+impl PreludeOp {
+    /// The operator that a function of the prelude implements, how it
+    /// behaves when the result overflows, and the type of its operands, or
+    /// `None` if the function is not one of the binary operators of the
+    /// prelude.
+    pub fn of(function: &Function) -> Option<(PreludeOp, Overflow, PrimitiveType)> {
+        if !function.is_extern() || function.formals.len() != 2 {
+            return None;
+        }
+        let name = function.symbolise().name();
+        if !name.starts_with("__libprelude__") {
+            return None;
+        }
+        let mut name = &name["__libprelude__".len()..];
+        let mut overflow = Overflow::Trap;
+        for &(prefix, mode) in [("checked_", Overflow::Check),
+                                ("saturating_", Overflow::Saturate),
+                                ("wrapping_", Overflow::Wrap)].iter() {
+            if name.starts_with(prefix) {
+                name = &name[prefix.len()..];
+                overflow = mode;
+            }
+        }
+        let op = match name.splitn(2, '_').next() {
+            Some("add") => PreludeOp::Add,
+            Some("div") => PreludeOp::Div,
+            Some("eq") => PreludeOp::Eq,
+            Some("ge") => PreludeOp::Ge,
+            Some("gt") => PreludeOp::Gt,
+            Some("le") => PreludeOp::Le,
+            Some("lt") => PreludeOp::Lt,
+            Some("mul") => PreludeOp::Mul,
+            Some("ne") => PreludeOp::Ne,
+            Some("sub") => PreludeOp::Sub,
+            _ => return None,
+        };
+        match function.formals[0].ty {
+            Type::Primitive(ref ty) => Some((op, overflow, ty.as_ref().clone())),
+            _ => None,
+        }
+    }
+
+    /// The operator that an assignment operator of the prelude, such as
+    /// `addeq`, applies to the value behind its first operand, and the type
+    /// of that value, or `None` if the function is not one of the assignment
+    /// operators of the prelude. An assignment operator always traps when its
+    /// result overflows.
+    pub fn of_assign(function: &Function) -> Option<(PreludeOp, PrimitiveType)> {
+        if !function.is_extern() || function.formals.len() != 2 {
+            return None;
+        }
+        let name = function.symbolise().name();
+        if !name.starts_with("__libprelude__") {
+            return None;
+        }
+        let op = match name["__libprelude__".len()..].splitn(2, '_').next() {
+            Some("addeq") => PreludeOp::Add,
+            Some("diveq") => PreludeOp::Div,
+            Some("muleq") => PreludeOp::Mul,
+            Some("subeq") => PreludeOp::Sub,
+            _ => return None,
+        };
+        match function.formals[1].ty {
+            Type::Primitive(ref ty) => Some((op, ty.as_ref().clone())),
+            _ => None,
+        }
+    }
+}
+
+/// The behaviour of an integer operator of the prelude when its result does
+/// not fit in its type, or when it divides by zero.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Overflow {
+    /// Return nil instead of the result, which is otherwise wrapped in an
+    /// optional.
+    Check,
+    /// Clamp the result to the bounds of its type. Dividing by zero is a
+    /// runtime error.
+    Saturate,
+    /// Stop the program with a runtime error.
+    Trap,
+    /// Wrap the result around the bounds of its type. Dividing by zero is a
+    /// runtime error.
+    Wrap,
+}
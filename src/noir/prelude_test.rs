@@ -0,0 +1,66 @@
+use super::super::identifier::{Name, Symbolise};
+use super::prelude::*;
+use super::PrimitiveType;
+
+use std::collections::HashSet;
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+/// Build the library that the builder links programs with, and check that it
+/// defines every function of the prelude. The library is a crate of its own,
+/// which is built with its dependencies, and its symbols are listed by `nm`,
+/// so the test is ignored by default. Run it with `cargo test -- --ignored`.
+#[test]
+#[ignore]
+fn prelude_library_symbols() {
+    let library_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src").join("libprelude");
+    let status = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .arg("build")
+        .arg("--manifest-path")
+        .arg(library_dir.join("Cargo.toml"))
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success());
+
+    let output = Command::new("nm")
+        .args(&["--defined-only", "--extern-only"])
+        .arg(library_dir.join("target").join("debug").join("libprelude.a"))
+        .output()
+        .expect("failed to run nm");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    // every symbol is listed on a line of its own, after its address and kind
+    let symbols = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(2).map(|symbol| symbol.to_string()))
+        .collect::<HashSet<_>>();
+    let missing = Prelude::new()
+        .functions()
+        .iter()
+        .map(|function| function.symbolise().name())
+        .filter(|name| !symbols.contains(name))
+        .collect::<Vec<_>>();
+    assert!(missing.is_empty(), "undefined in the prelude library: {:?}", missing);
+}
+
+#[test]
+fn prelude_ops() {
+    let prelude = Prelude::new();
+    match PreludeOp::of(&prelude.wrapping_sub_u8_fn) {
+        Some((PreludeOp::Sub, Overflow::Wrap, PrimitiveType::U8)) => (),
+        _ => panic!("expected a wrapping subtraction of u8"),
+    }
+    assert!(PreludeOp::of(&prelude.addeq_i64_fn).is_none());
+
+    // assignment operators update their first operand in place
+    match PreludeOp::of_assign(&prelude.addeq_i64_fn) {
+        Some((PreludeOp::Add, PrimitiveType::I64)) => (),
+        _ => panic!("expected an addition of i64"),
+    }
+    match PreludeOp::of_assign(&prelude.diveq_u32_fn) {
+        Some((PreludeOp::Div, PrimitiveType::U32)) => (),
+        _ => panic!("expected a division of u32"),
+    }
+    assert!(PreludeOp::of_assign(&prelude.add_i64_fn).is_none());
+}
@@ -2,21 +2,185 @@ use super::*;
 
 #[derive(Clone)]
 pub struct Runtime {
+    pub arithmetic_error_fn: Function,
+    pub channel_close_fn: Function,
+    pub channel_new_fn: Function,
+    pub channel_next_fn: Function,
+    pub channel_recv_fn: Function,
+    pub channel_send_fn: Function,
+    pub environment_new_fn: Function,
+    pub list_index_fn: Function,
+    pub list_len_fn: Function,
+    pub list_new_fn: Function,
+    pub list_pop_fn: Function,
+    pub list_push_fn: Function,
+    pub list_slice_fn: Function,
     pub process_fn: Function,
+    pub process_group_join_fn: Function,
+    pub process_group_new_fn: Function,
+    pub process_group_spawn_fn: Function,
     pub process_join_fn: Function,
+    pub select_new_fn: Function,
+    pub select_recv_fn: Function,
+    pub select_send_fn: Function,
+    pub select_wait_fn: Function,
+    pub unwrap_nil_fn: Function,
 }
 
 impl Runtime {
     pub fn new() -> Runtime {
         Runtime {
+            arithmetic_error_fn: Function::new(
+                Symbol::new("__libruntime__arithmetic_error"),
+                vec![
+                    Variable::new(Symbol::new("message"), PtrType::new(PrimitiveType::I8))
+                ],
+                PrimitiveType::Void,
+                None
+            ),
+            channel_close_fn: Function::new(
+                Symbol::new("__libruntime__channel_close"),
+                vec![
+                    Variable::new(Symbol::new("channel"), PtrType::new(PrimitiveType::I8))
+                ],
+                PrimitiveType::Void,
+                None
+            ),
+            channel_new_fn: Function::new(
+                Symbol::new("__libruntime__channel_new"),
+                vec![
+                    Variable::new(Symbol::new("size"), PrimitiveType::I64),
+                    Variable::new(Symbol::new("capacity"), PrimitiveType::I64)
+                ],
+                PtrType::new(PrimitiveType::I8),
+                None
+            ),
+            channel_next_fn: Function::new(
+                Symbol::new("__libruntime__channel_next"),
+                vec![
+                    Variable::new(Symbol::new("channel"), PtrType::new(PrimitiveType::I8)),
+                    Variable::new(Symbol::new("value"), PtrType::new(PrimitiveType::I8))
+                ],
+                PrimitiveType::Bool,
+                None
+            ),
+            channel_recv_fn: Function::new(
+                Symbol::new("__libruntime__channel_recv"),
+                vec![
+                    Variable::new(Symbol::new("channel"), PtrType::new(PrimitiveType::I8)),
+                    Variable::new(Symbol::new("value"), PtrType::new(PrimitiveType::I8))
+                ],
+                PrimitiveType::Void,
+                None
+            ),
+            channel_send_fn: Function::new(
+                Symbol::new("__libruntime__channel_send"),
+                vec![
+                    Variable::new(Symbol::new("channel"), PtrType::new(PrimitiveType::I8)),
+                    Variable::new(Symbol::new("value"), PtrType::new(PrimitiveType::I8))
+                ],
+                PrimitiveType::Void,
+                None
+            ),
+            environment_new_fn: Function::new(
+                Symbol::new("__libruntime__environment_new"),
+                vec![
+                    Variable::new(Symbol::new("environment"), PtrType::new(PrimitiveType::I8)),
+                    Variable::new(Symbol::new("size"), PrimitiveType::I64)
+                ],
+                PtrType::new(PrimitiveType::I8),
+                None
+            ),
+            list_index_fn: Function::new(
+                Symbol::new("__libruntime__list_index"),
+                vec![
+                    Variable::new(Symbol::new("list"), PtrType::new(PrimitiveType::I8)),
+                    Variable::new(Symbol::new("index"), PrimitiveType::I64)
+                ],
+                PtrType::new(PrimitiveType::I8),
+                None
+            ),
+            list_len_fn: Function::new(
+                Symbol::new("__libruntime__list_len"),
+                vec![
+                    Variable::new(Symbol::new("list"), PtrType::new(PrimitiveType::I8))
+                ],
+                PrimitiveType::I64,
+                None
+            ),
+            list_new_fn: Function::new(
+                Symbol::new("__libruntime__list_new"),
+                vec![
+                    Variable::new(Symbol::new("size"), PrimitiveType::I64)
+                ],
+                PtrType::new(PrimitiveType::I8),
+                None
+            ),
+            list_pop_fn: Function::new(
+                Symbol::new("__libruntime__list_pop"),
+                vec![
+                    Variable::new(Symbol::new("list"), PtrType::new(PrimitiveType::I8)),
+                    Variable::new(Symbol::new("element"), PtrType::new(PrimitiveType::I8))
+                ],
+                PrimitiveType::Bool,
+                None
+            ),
+            list_push_fn: Function::new(
+                Symbol::new("__libruntime__list_push"),
+                vec![
+                    Variable::new(Symbol::new("list"), PtrType::new(PrimitiveType::I8)),
+                    Variable::new(Symbol::new("element"), PtrType::new(PrimitiveType::I8))
+                ],
+                PrimitiveType::Void,
+                None
+            ),
+            list_slice_fn: Function::new(
+                Symbol::new("__libruntime__list_slice"),
+                vec![
+                    Variable::new(Symbol::new("list"), PtrType::new(PrimitiveType::I8)),
+                    Variable::new(Symbol::new("start"), PrimitiveType::I64),
+                    Variable::new(Symbol::new("end"), PrimitiveType::I64)
+                ],
+                PtrType::new(PrimitiveType::I8),
+                None
+            ),
             process_fn: Function::new(
                 Symbol::new("__libruntime__process"),
                 vec![
-                    Variable::new(Symbol::new("f"), LambdaType::new(Types::new(), PrimitiveType::Void))
+                    Variable::new(Symbol::new("f"),
+                                  LambdaType::new(vec![PtrType::new(PrimitiveType::I8)], PrimitiveType::Void)),
+                    Variable::new(Symbol::new("environment"), PtrType::new(PrimitiveType::I8)),
+                    Variable::new(Symbol::new("size"), PrimitiveType::I64)
+                ],
+                PtrType::new(PrimitiveType::I8),
+                None
+            ),
+            process_group_join_fn: Function::new(
+                Symbol::new("__libruntime__process_group_join"),
+                vec![
+                    Variable::new(Symbol::new("group"), PtrType::new(PrimitiveType::I8))
                 ],
+                PrimitiveType::Void,
+                None
+            ),
+            process_group_new_fn: Function::new(
+                Symbol::new("__libruntime__process_group_new"),
+                Variables::new(),
                 PtrType::new(PrimitiveType::I8),
                 None
             ),
+            process_group_spawn_fn: Function::new(
+                Symbol::new("__libruntime__process_group_spawn"),
+                vec![
+                    Variable::new(Symbol::new("group"), PtrType::new(PrimitiveType::I8)),
+                    Variable::new(Symbol::new("f"),
+                                  LambdaType::new(vec![PtrType::new(PrimitiveType::I8)], PrimitiveType::Void)),
+                    Variable::new(Symbol::new("value"), PtrType::new(PrimitiveType::I8)),
+                    Variable::new(Symbol::new("size"), PrimitiveType::I64)
+                ],
+                PrimitiveType::Void,
+                None
+            ),
             process_join_fn: Function::new(
                 Symbol::new("__libruntime__process_join"),
                 vec![
@@ -25,11 +189,73 @@ impl Runtime {
                 PrimitiveType::Void,
                 None
             ),
+            select_new_fn: Function::new(
+                Symbol::new("__libruntime__select_new"),
+                Variables::new(),
+                PtrType::new(PrimitiveType::I8),
+                None
+            ),
+            select_recv_fn: Function::new(
+                Symbol::new("__libruntime__select_recv"),
+                vec![
+                    Variable::new(Symbol::new("select"), PtrType::new(PrimitiveType::I8)),
+                    Variable::new(Symbol::new("channel"), PtrType::new(PrimitiveType::I8)),
+                    Variable::new(Symbol::new("value"), PtrType::new(PrimitiveType::I8))
+                ],
+                PrimitiveType::Void,
+                None
+            ),
+            select_send_fn: Function::new(
+                Symbol::new("__libruntime__select_send"),
+                vec![
+                    Variable::new(Symbol::new("select"), PtrType::new(PrimitiveType::I8)),
+                    Variable::new(Symbol::new("channel"), PtrType::new(PrimitiveType::I8)),
+                    Variable::new(Symbol::new("value"), PtrType::new(PrimitiveType::I8))
+                ],
+                PrimitiveType::Void,
+                None
+            ),
+            select_wait_fn: Function::new(
+                Symbol::new("__libruntime__select_wait"),
+                vec![
+                    Variable::new(Symbol::new("select"), PtrType::new(PrimitiveType::I8)),
+                    Variable::new(Symbol::new("is_blocking"), PrimitiveType::Bool)
+                ],
+                PrimitiveType::I64,
+                None
+            ),
+            unwrap_nil_fn: Function::new(
+                Symbol::new("__libruntime__unwrap_nil"),
+                Variables::new(),
+                PrimitiveType::Void,
+                None
+            ),
         }
     }
 
     pub fn functions(&self) -> Functions {
-        vec![self.process_fn.clone(),
-             self.process_join_fn.clone()]
+        vec![self.arithmetic_error_fn.clone(),
+             self.channel_close_fn.clone(),
+             self.channel_new_fn.clone(),
+             self.channel_next_fn.clone(),
+             self.channel_recv_fn.clone(),
+             self.channel_send_fn.clone(),
+             self.environment_new_fn.clone(),
+             self.list_index_fn.clone(),
+             self.list_len_fn.clone(),
+             self.list_new_fn.clone(),
+             self.list_pop_fn.clone(),
+             self.list_push_fn.clone(),
+             self.list_slice_fn.clone(),
+             self.process_fn.clone(),
+             self.process_group_join_fn.clone(),
+             self.process_group_new_fn.clone(),
+             self.process_group_spawn_fn.clone(),
+             self.process_join_fn.clone(),
+             self.select_new_fn.clone(),
+             self.select_recv_fn.clone(),
+             self.select_send_fn.clone(),
+             self.select_wait_fn.clone(),
+             self.unwrap_nil_fn.clone()]
     }
 }
\ No newline at end of file
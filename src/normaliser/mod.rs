@@ -1,56 +1,607 @@
+pub mod monomorphiser;
+
+#[cfg(test)]
+mod mod_test;
+#[cfg(test)]
+mod monomorphiser_test;
+
 use super::air;
-use super::air::Typedef;
-use super::identifier::{Identifier, Identify, Symbolise};
+use super::identifier::{Identifier, Identify, Symbol, Symbolise};
 use super::noir::*;
-use super::noir::context::Context;
+
+use self::monomorphiser::Monomorphiser;
+
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+/// The variables that are declared by a function, a process, or an iteration
+/// of a concurrent `ForExpr`, and the variables from outside of it that it
+/// captures.
+struct Closure {
+    declarations: HashSet<Identifier>,
+    captures: Variables,
+}
+
+impl Closure {
+    fn new(declarations: &Variables) -> Closure {
+        Closure {
+            declarations: declarations.iter().map(|variable| variable.identify()).collect(),
+            captures: Variables::new(),
+        }
+    }
+}
 
 pub struct Normaliser {
-    context: Context,
+    optional_types: HashMap<String, EnumType>,
+    /// The processes that have been started in each of the blocks that are
+    /// being normalised, from the outermost block to the innermost.
+    processes: Vec<Vec<ProcessExpr>>,
+    /// The closures that are being normalised, from the outermost closure to
+    /// the innermost.
+    closures: Vec<Closure>,
 }
 
 impl Normaliser {
-    pub fn new(context: Context) -> Normaliser {
+    pub fn new() -> Normaliser {
         Normaliser {
-            context: context
+            optional_types: HashMap::new(),
+            processes: Vec::new(),
+            closures: Vec::new(),
         }
     }
 
+    /// Normalise a `Module` that has been type checked. Generics are
+    /// monomorphised first, so that only concrete types reach the NoIR.
+    pub fn normalise_module(&mut self, module: &air::Module) -> Module {
+        let mut module = module.clone();
+        Monomorphiser::new().monomorphise(&mut module);
+        self.normalise_monomorphic_module(&module)
+    }
+
+    fn normalise_monomorphic_module(&mut self, module: &air::Module) -> Module {
+        Module::new(
+            module.symbolise(),
+            self.normalise_function_table(&module.function_table),
+            self.normalise_module_table(&module.module_table),
+            self.normalise_type_table(&module.type_table),
+        )
+    }
+
+    fn normalise_function_table(&mut self, function_table: &air::FunctionTable) -> FunctionTable {
+        function_table.values()
+            .map(|function| (function.identify(), self.normalise_function(function)))
+            .collect()
+    }
+
+    fn normalise_module_table(&mut self, module_table: &air::ModuleTable) -> ModuleTable {
+        module_table.values()
+            .map(|module| (module.identify(), self.normalise_monomorphic_module(module)))
+            .collect()
+    }
+
+    fn normalise_type_table(&mut self, type_table: &air::TypeTable) -> TypeTable {
+        type_table.iter()
+            .map(|(identifier, ty)| (identifier.clone(), self.normalise_type(ty)))
+            .collect()
+    }
+
+    pub fn normalise_assign_expr(&mut self, assign_expr: &air::AssignExpr) -> AssignExpr {
+        AssignExpr::new_with_id(
+            assign_expr.identify(),
+            self.normalise_expr(&assign_expr.lhs),
+            self.normalise_expr(&assign_expr.rhs),
+        )
+    }
+
+    /// Every process that is started in a block is joined in the epilogue of
+    /// the block, after the value of the block has been evaluated.
     pub fn normalise_block_expr(&mut self, block_expr: &air::BlockExpr) -> BlockExpr {
-        BlockExpr::new(
+        let function_table = self.normalise_function_table(&block_expr.function_table);
+        let module_table = self.normalise_module_table(&block_expr.module_table);
+        let type_table = self.normalise_type_table(&block_expr.type_table);
+        self.processes.push(Vec::new());
+        let body = self.normalise_exprs(&block_expr.body);
+        let ret = self.normalise_expr(&block_expr.ret);
+        let epilogue = self.processes
+            .pop()
+            .expect("expected processes for the block")
+            .into_iter()
+            .map(ProcessJoinExpr::new)
+            .collect::<Vec<_>>();
+        BlockExpr::new_with_id(
             block_expr.identify(),
-            self.normalise_exprs(block_expr.body()),
-            self.normalise_expr(block_expr.ret()),
+            Exprs::new(),
+            epilogue,
+            body,
+            ret,
+            function_table,
+            module_table,
+            type_table,
+        )
+    }
+
+    pub fn normalise_break_expr(&mut self, break_expr: &air::BreakExpr) -> BreakExpr {
+        BreakExpr::new_with_id(break_expr.identify())
+    }
+
+    /// A call to an item is a direct call. Any other call is a call through
+    /// a closure.
+    pub fn normalise_call_expr(&mut self, call_expr: &air::CallExpr) -> CallExpr {
+        let target = match call_expr.target {
+            air::Expr::Item(ref item_expr) => self.normalise_item_expr(item_expr).into(),
+            ref target => self.normalise_expr(target),
+        };
+        CallExpr::new_with_id(
+            call_expr.identify(),
+            target,
+            self.normalise_exprs(&call_expr.arguments),
+        )
+    }
+
+    pub fn normalise_channel_expr(&mut self, channel_expr: &air::ChannelExpr) -> ChannelExpr {
+        let capacity = channel_expr.capacity.as_ref().map(|capacity| self.normalise_expr(capacity));
+        ChannelExpr::new_with_id(channel_expr.identify(), capacity, self.normalise_type(&channel_expr.ty))
+    }
+
+    pub fn normalise_channel_op_expr(&mut self, channel_op_expr: &air::ChannelOpExpr) -> ChannelOpExpr {
+        let op = match channel_op_expr.op {
+            air::ChannelOp::Close(ref channel) => ChannelOp::Close(self.normalise_expr(channel)),
+            air::ChannelOp::Recv(ref channel) => ChannelOp::Recv(self.normalise_expr(channel)),
+            air::ChannelOp::Send(ref channel, ref value) => {
+                ChannelOp::Send(self.normalise_expr(channel), self.normalise_expr(value))
+            }
+        };
+        ChannelOpExpr::new_with_id(channel_op_expr.identify(), op, self.normalise_type(&channel_op_expr.ty))
+    }
+
+    pub fn normalise_channel_type(&mut self, channel_type: &air::ChannelType) -> ChannelType {
+        ChannelType::new(self.normalise_type(&channel_type.element))
+    }
+
+    /// Normalise a `CoalesceExpr` into a `MatchExpr` that evaluates to the
+    /// value of the `Some` variant, or to the default for any other variant.
+    pub fn normalise_coalesce_expr(&mut self, coalesce_expr: &air::CoalesceExpr) -> MatchExpr {
+        let optional_type = self.normalise_optional_type(&air::OptionalType::new(coalesce_expr.ty.clone()));
+        let (some, _) = optional_type.optional_variants();
+        let value = Variable::new(Symbol::new("value"), self.normalise_type(&coalesce_expr.ty));
+        let default = self.normalise_expr(&coalesce_expr.rhs);
+        MatchExpr::new_with_id(
+            coalesce_expr.identify(),
+            self.normalise_expr(&coalesce_expr.lhs),
+            vec![
+                MatchArm::new(
+                    some,
+                    vec![value.clone()],
+                    BlockExpr::new(
+                        Exprs::new(),
+                        Exprs::new(),
+                        Exprs::new(),
+                        VariableExpr::new(value),
+                        FunctionTable::new(),
+                        ModuleTable::new(),
+                        TypeTable::new(),
+                    ),
+                ),
+                MatchArm::new(
+                    None,
+                    Variables::new(),
+                    BlockExpr::new(
+                        Exprs::new(),
+                        Exprs::new(),
+                        Exprs::new(),
+                        default,
+                        FunctionTable::new(),
+                        ModuleTable::new(),
+                        TypeTable::new(),
+                    ),
+                ),
+            ],
+        )
+    }
+
+    pub fn normalise_continue_expr(&mut self, continue_expr: &air::ContinueExpr) -> ContinueExpr {
+        ContinueExpr::new_with_id(continue_expr.identify())
+    }
+
+    pub fn normalise_deref_expr(&mut self, deref_expr: &air::DerefExpr) -> DerefExpr {
+        DerefExpr::new_with_id(deref_expr.identify(), self.normalise_expr(&deref_expr.inner))
+    }
+
+    /// Every expression on either side of a `do .. then ..` is started as a
+    /// process. Each side is a block of its own, which joins its processes
+    /// before the next side begins.
+    pub fn normalise_do_expr(&mut self, do_expr: &air::DoExpr) -> BlockExpr {
+        let do_block = self.normalise_concurrent_expr(&do_expr.do_expr);
+        let then_block = self.normalise_concurrent_expr(&do_expr.then_expr);
+        BlockExpr::new_with_id(
+            do_expr.identify(),
+            Exprs::new(),
+            Exprs::new(),
+            vec![do_block, then_block],
+            VoidExpr::new(),
             FunctionTable::new(),
+            ModuleTable::new(),
             TypeTable::new(),
-            VariableTable::new(),
         )
     }
 
-    pub fn normalise_function(&mut self, function: &air::Function) -> Function {
-        let body = function.body().and_then(|body| if let air::Expr::Block(block_expr) = body {
-            Some(*block_expr.clone())
+    pub fn normalise_enum_expr(&mut self, enum_expr: &air::EnumExpr) -> EnumExpr {
+        let ty = match self.normalise_type(&enum_expr.ty) {
+            Type::Enum(ty) => *ty,
+            _ => unreachable!("enum expression with a type that is not an enum"),
+        };
+        EnumExpr::new_with_id(
+            enum_expr.identify(),
+            self.normalise_variable(&enum_expr.variant),
+            enum_expr.elements
+                .iter()
+                .map(|&(ref variable, ref expr)| (self.normalise_variable(variable), self.normalise_expr(expr)))
+                .collect(),
+            ty,
+        )
+    }
+
+    pub fn normalise_enum_type(&mut self, enum_type: &air::EnumType) -> EnumType {
+        EnumType::new(
+            enum_type.symbol.clone(),
+            self.normalise_variables(&enum_type.variants.elements),
+        )
+    }
+
+    /// The iteration of a concurrent `ForExpr` is a closure, which captures
+    /// the variables that it uses from outside of the loop. The type checker
+    /// makes sure that a `ForExpr` declares exactly one variable.
+    pub fn normalise_for_expr(&mut self, for_expr: &air::ForExpr) -> ForExpr {
+        let variable = match for_expr.formals.len() {
+            1 => self.normalise_variable(&for_expr.formals[0]),
+            len => unreachable!("`for` with {} variables after type checking", len),
+        };
+        let iterator = self.normalise_expr(&for_expr.iterator);
+        let (iteration, captures) = if for_expr.is_concurrent {
+            self.normalise_closure(&vec![variable.clone()],
+                                   |normaliser| normaliser.normalise_block_expr(&for_expr.iteration))
         } else {
-            Some(air::BlockExpr::new(
-                Identifier::id(),
-                air::Exprs::new(),
-                body.clone(),
-            ))
-        });
-        Function::new(
-            function.symbolise(),
-            self.normalise_variables(function.formals()),
-            self.normalise_type(function.ret()),
-            body.and_then(|body| Some(self.normalise_block_expr(&body))),
+            self.declare_variables(&vec![variable.clone()]);
+            (self.normalise_block_expr(&for_expr.iteration), Variables::new())
+        };
+        let mut for_expr = ForExpr::new_with_id(for_expr.identify(),
+                                                for_expr.is_concurrent,
+                                                variable,
+                                                iterator,
+                                                iteration,
+                                                self.normalise_type(&for_expr.ty));
+        for_expr.captures = captures;
+        for_expr
+    }
+
+    /// The body of a `Function` is always normalised into a `BlockExpr`.
+    pub fn normalise_function(&mut self, function: &air::Function) -> Function {
+        let body = function.body.as_ref().map(air_block_expr);
+        let formals = self.normalise_variables(&function.formals);
+        let ret = self.normalise_type(&function.ret);
+        // a function captures nothing, and so it begins a new stack of closures
+        let closures = mem::replace(&mut self.closures, vec![Closure::new(&formals)]);
+        let body = body.map(|body| self.normalise_block_expr(&body));
+        self.closures = closures;
+        Function::new(function.symbolise(), formals, ret, body)
+    }
+
+    /// A `Function` that is used as a value, rather than called, is
+    /// normalised into a `LambdaExpr` that calls it, so that every function
+    /// value is a closure.
+    pub fn normalise_function_value(&mut self, function: &air::Function) -> LambdaExpr {
+        let item = self.normalise_item(&function.clone().into());
+        let formals = self.normalise_variables(&function.formals);
+        let ret = self.normalise_type(&function.ret);
+        let arguments = formals.iter()
+            .map(|formal| VariableExpr::new(formal.clone()).into())
+            .collect::<Exprs>();
+        let body = BlockExpr::new(Exprs::new(),
+                                  Exprs::new(),
+                                  Exprs::new(),
+                                  CallExpr::new(ItemExpr::new(item), arguments),
+                                  FunctionTable::new(),
+                                  ModuleTable::new(),
+                                  TypeTable::new());
+        LambdaExpr::new(formals, ret, body)
+    }
+
+    pub fn normalise_if_expr(&mut self, if_expr: &air::IfExpr) -> IfExpr {
+        IfExpr::new_with_id(
+            if_expr.identify(),
+            self.normalise_expr(&if_expr.condition),
+            self.normalise_block_expr(&if_expr.then_block),
+            self.normalise_block_expr(&if_expr.else_block),
         )
     }
 
-    pub fn normalise_lambda_type(&mut self, lambda_type: &air::LambdaType) -> LambdaType {
-        LambdaType::new(
-            self.normalise_types(lambda_type.formals()),
-            self.normalise_type(lambda_type.ret()),
+    /// An `Item` refers to its definition by symbol, and so a `Function` or a
+    /// `Module` is normalised without its body, which is normalised where it
+    /// is defined.
+    pub fn normalise_item(&mut self, item: &air::Item) -> Item {
+        match *item {
+            air::Item::Function(ref function) => {
+                let formals = self.normalise_variables(&function.formals);
+                let ret = self.normalise_type(&function.ret);
+                Function::new(function.symbolise(), formals, ret, None).into()
+            }
+            air::Item::Module(ref module) => {
+                Module::new(module.symbolise(), FunctionTable::new(), ModuleTable::new(), TypeTable::new()).into()
+            }
+            air::Item::Type(ref ty) => self.normalise_type(ty).into(),
+            air::Item::Variable(ref variable) => self.normalise_variable(variable).into(),
+        }
+    }
+
+    pub fn normalise_item_expr(&mut self, item_expr: &air::ItemExpr) -> ItemExpr {
+        ItemExpr::new_with_id(item_expr.identify(), self.normalise_item(&item_expr.item))
+    }
+
+    /// The body of a `LambdaExpr` is a closure, which captures the variables
+    /// that it uses from outside of the lambda.
+    pub fn normalise_lambda_expr(&mut self, lambda_expr: &air::LambdaExpr) -> LambdaExpr {
+        let formals = self.normalise_variables(&lambda_expr.formals);
+        let ret = self.normalise_type(&lambda_expr.ret);
+        let body = air_block_expr(&lambda_expr.body);
+        let (body, captures) = self.normalise_closure(&formals, |normaliser| normaliser.normalise_block_expr(&body));
+        let mut lambda_expr = LambdaExpr::new_with_id(lambda_expr.identify(), formals, ret, body);
+        lambda_expr.captures = captures;
+        lambda_expr
+    }
+
+    /// Every function value is a closure.
+    pub fn normalise_lambda_type(&mut self, lambda_type: &air::LambdaType) -> ClosureType {
+        ClosureType::new(
+            self.normalise_types(&lambda_type.formals),
+            self.normalise_type(&lambda_type.ret),
         )
     }
 
+    pub fn normalise_let_expr(&mut self, let_expr: &air::LetExpr) -> LetExpr {
+        let variable = self.normalise_variable(&let_expr.variable);
+        let definition = self.normalise_expr(&let_expr.definition);
+        self.declare_variables(&vec![variable.clone()]);
+        LetExpr::new_with_id(let_expr.identify(), variable, definition)
+    }
+
+    pub fn normalise_let_tuple_expr(&mut self, let_tuple_expr: &air::LetTupleExpr) -> LetTupleExpr {
+        let variables = self.normalise_variables(&let_tuple_expr.variables);
+        let definition = self.normalise_expr(&let_tuple_expr.definition);
+        self.declare_variables(&variables);
+        LetTupleExpr::new_with_id(let_tuple_expr.identify(), variables, definition)
+    }
+
+    pub fn normalise_list_expr(&mut self, list_expr: &air::ListExpr) -> ListExpr {
+        ListExpr::new_with_id(
+            list_expr.identify(),
+            self.normalise_exprs(&list_expr.elements),
+            self.normalise_type(&list_expr.ty),
+        )
+    }
+
+    pub fn normalise_list_op_expr(&mut self, list_op_expr: &air::ListOpExpr) -> ListOpExpr {
+        let op = match list_op_expr.op {
+            air::ListOp::Index(ref list, ref index) => {
+                ListOp::Index(self.normalise_expr(list), self.normalise_expr(index))
+            }
+            air::ListOp::Len(ref list) => ListOp::Len(self.normalise_expr(list)),
+            air::ListOp::Pop(ref list) => {
+                let optional_type = self.normalise_optional_type(&air::OptionalType::new(list_op_expr.ty.clone()));
+                ListOp::Pop(self.normalise_expr(list), optional_type)
+            }
+            air::ListOp::Push(ref list, ref value) => {
+                ListOp::Push(self.normalise_expr(list), self.normalise_expr(value))
+            }
+            air::ListOp::Slice(ref list, ref start, ref end) => {
+                ListOp::Slice(self.normalise_expr(list),
+                              self.normalise_expr(start),
+                              self.normalise_expr(end))
+            }
+        };
+        ListOpExpr::new_with_id(list_op_expr.identify(), op, self.normalise_type(&list_op_expr.ty))
+    }
+
+    pub fn normalise_list_type(&mut self, list_type: &air::ListType) -> ListType {
+        ListType::new(self.normalise_type(&list_type.element))
+    }
+
+    /// Integer and float literals that were not given a type by the type
+    /// checker are given the same default types, `i64` and `f64`.
+    pub fn normalise_literal(&mut self, literal: &air::Literal) -> Literal {
+        match *literal {
+            air::Literal::Bool(value) => Literal::Bool(value),
+            air::Literal::Char(value) => Literal::Char(value),
+            air::Literal::F32(value) => Literal::F32(value),
+            air::Literal::F64(value) => Literal::F64(value),
+            air::Literal::Float(value) => Literal::F64(value),
+            air::Literal::I8(value) => Literal::I8(value),
+            air::Literal::I16(value) => Literal::I16(value),
+            air::Literal::I32(value) => Literal::I32(value),
+            air::Literal::I64(value) => Literal::I64(value),
+            air::Literal::Int(value) => Literal::I64(value as i64),
+            air::Literal::Str(ref value) => Literal::Str(value.clone()),
+            air::Literal::U8(value) => Literal::U8(value),
+            air::Literal::U16(value) => Literal::U16(value),
+            air::Literal::U32(value) => Literal::U32(value),
+            air::Literal::U64(value) => Literal::U64(value),
+            air::Literal::USize(value) => Literal::USize(value),
+        }
+    }
+
+    pub fn normalise_literal_expr(&mut self, literal_expr: &air::LiteralExpr) -> LiteralExpr {
+        LiteralExpr::new_with_id(literal_expr.identify(), self.normalise_literal(&literal_expr.literal))
+    }
+
+    pub fn normalise_match_expr(&mut self, match_expr: &air::MatchExpr) -> MatchExpr {
+        MatchExpr::new_with_id(
+            match_expr.identify(),
+            self.normalise_expr(&match_expr.subject),
+            match_expr.arms
+                .iter()
+                .map(|arm| {
+                    let bindings = self.normalise_variables(&arm.bindings);
+                    self.declare_variables(&bindings);
+                    MatchArm::new(
+                        arm.variant.as_ref().map(|variant| self.normalise_variable(variant)),
+                        bindings,
+                        self.normalise_block_expr(&arm.body),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    pub fn normalise_optional_expr(&mut self, optional_expr: &air::OptionalExpr) -> EnumExpr {
+        let ty = self.normalise_optional_type(&air::OptionalType::new(optional_expr.ty.clone()));
+        let (some, nil) = ty.optional_variants();
+        match optional_expr.value {
+            Some(ref value) => {
+                let element = EnumType::variant_elements(&some)[0].clone();
+                let value = self.normalise_expr(value);
+                EnumExpr::new_with_id(optional_expr.identify(), some, vec![(element, value)], ty)
+            }
+            None => EnumExpr::new_with_id(optional_expr.identify(), nil, Vec::new(), ty),
+        }
+    }
+
+    /// Normalise an `OptionalType` into an `EnumType`. Every optional of the
+    /// same `Type` is normalised into the same `EnumType`.
+    pub fn normalise_optional_type(&mut self, optional_type: &air::OptionalType) -> EnumType {
+        let name = format!("{}", air::Type::from(optional_type.clone()));
+        if let Some(ty) = self.optional_types.get(&name) {
+            return ty.clone();
+        }
+        let ty = EnumType::new_optional(Symbol::new(name.clone()), self.normalise_type(&optional_type.inner));
+        self.optional_types.insert(name, ty.clone());
+        ty
+    }
+
+    pub fn normalise_primitive_type(&mut self, primitive_type: &air::PrimitiveType) -> PrimitiveType {
+        match *primitive_type {
+            air::PrimitiveType::Bool => PrimitiveType::Bool,
+            air::PrimitiveType::Char => PrimitiveType::Char,
+            air::PrimitiveType::F32 => PrimitiveType::F32,
+            air::PrimitiveType::F64 => PrimitiveType::F64,
+            air::PrimitiveType::I8 => PrimitiveType::I8,
+            air::PrimitiveType::I16 => PrimitiveType::I16,
+            air::PrimitiveType::I32 => PrimitiveType::I32,
+            air::PrimitiveType::I64 => PrimitiveType::I64,
+            air::PrimitiveType::Str => PrimitiveType::Str,
+            air::PrimitiveType::U8 => PrimitiveType::U8,
+            air::PrimitiveType::U16 => PrimitiveType::U16,
+            air::PrimitiveType::U32 => PrimitiveType::U32,
+            air::PrimitiveType::U64 => PrimitiveType::U64,
+            air::PrimitiveType::USize => PrimitiveType::USize,
+            air::PrimitiveType::Void => PrimitiveType::Void,
+        }
+    }
+
+    /// The body of a process is a closure, which captures the variables that
+    /// it uses from outside of the process.
+    pub fn normalise_process_expr(&mut self, process_expr: &air::ProcessExpr) -> ProcessExpr {
+        let (body, captures) = self.normalise_closure(&Variables::new(),
+                                                      |normaliser| normaliser.normalise_block_expr(&process_expr.body));
+        let mut process_expr = ProcessExpr::new_with_id(process_expr.identify(), body);
+        process_expr.captures = captures;
+        self.processes
+            .last_mut()
+            .expect("expected a block to start the process in")
+            .push(process_expr.clone());
+        process_expr
+    }
+
+    pub fn normalise_ptr_type(&mut self, ptr_type: &air::PtrType) -> PtrType {
+        PtrType::new(self.normalise_type(&ptr_type.inner))
+    }
+
+    pub fn normalise_range_expr(&mut self, range_expr: &air::RangeExpr) -> RangeExpr {
+        RangeExpr::new_with_id(
+            range_expr.identify(),
+            self.normalise_expr(&range_expr.start),
+            self.normalise_expr(&range_expr.end),
+        )
+    }
+
+    pub fn normalise_ref_expr(&mut self, ref_expr: &air::RefExpr) -> RefExpr {
+        RefExpr::new_with_id(ref_expr.identify(), self.normalise_expr(&ref_expr.inner))
+    }
+
+    pub fn normalise_select_expr(&mut self, select_expr: &air::SelectExpr) -> SelectExpr {
+        SelectExpr::new_with_id(
+            select_expr.identify(),
+            select_expr.guards
+                .iter()
+                .map(|guard| {
+                    let op = match guard.op {
+                        air::SelectOp::Recv(ref channel, ref variable) => {
+                            let channel = self.normalise_expr(channel);
+                            let variable = variable.as_ref().map(|variable| self.normalise_variable(variable));
+                            if let Some(ref variable) = variable {
+                                self.declare_variables(&vec![variable.clone()]);
+                            }
+                            SelectOp::Recv(channel, variable)
+                        }
+                        air::SelectOp::Send(ref channel, ref value) => {
+                            SelectOp::Send(self.normalise_expr(channel), self.normalise_expr(value))
+                        }
+                    };
+                    SelectGuard::new(op, self.normalise_block_expr(&guard.body), self.normalise_type(&guard.ty))
+                })
+                .collect(),
+            select_expr.else_body.as_ref().map(|else_body| self.normalise_block_expr(else_body)),
+        )
+    }
+
+    pub fn normalise_struct_expr(&mut self, struct_expr: &air::StructExpr) -> StructExpr {
+        let ty = match self.normalise_type(&struct_expr.ty) {
+            Type::Struct(ty) => *ty,
+            _ => unreachable!("struct expression with a type that is not a struct"),
+        };
+        StructExpr::new_with_id(
+            struct_expr.identify(),
+            struct_expr.elements
+                .iter()
+                .map(|&(ref variable, ref expr)| (self.normalise_variable(variable), self.normalise_expr(expr)))
+                .collect(),
+            ty,
+        )
+    }
+
+    pub fn normalise_struct_type(&mut self, struct_type: &air::StructType) -> StructType {
+        StructType::new(
+            struct_type.symbol.clone(),
+            self.normalise_variables(&struct_type.elements),
+        )
+    }
+
+    pub fn normalise_tuple_expr(&mut self, tuple_expr: &air::TupleExpr) -> TupleExpr {
+        TupleExpr::new_with_id(tuple_expr.identify(), self.normalise_exprs(&tuple_expr.elements))
+    }
+
+    pub fn normalise_tuple_type(&mut self, tuple_type: &air::TupleType) -> TupleType {
+        TupleType::new(self.normalise_types(&tuple_type.elements))
+    }
+
+    pub fn normalise_unwrap_expr(&mut self, unwrap_expr: &air::UnwrapExpr) -> UnwrapExpr {
+        let optional_type = self.normalise_optional_type(&air::OptionalType::new(unwrap_expr.ty.clone()));
+        let (some, _) = optional_type.optional_variants();
+        UnwrapExpr::new_with_id(unwrap_expr.identify(), self.normalise_expr(&unwrap_expr.inner), some)
+    }
+
+    pub fn normalise_variable_expr(&mut self, variable_expr: &air::VariableExpr) -> VariableExpr {
+        let variable = self.normalise_variable(&variable_expr.variable);
+        match variable_expr.parent.first() {
+            Some(parent) => {
+                let parent = self.normalise_expr(parent);
+                VariableExpr::new_with_parent(variable_expr.identify(), parent, variable)
+            }
+            None => {
+                self.capture(&variable);
+                VariableExpr::new_with_id(variable_expr.identify(), variable)
+            }
+        }
+    }
+
     pub fn normalise_variables(&mut self, variables: &air::Variables) -> Variables {
         variables.iter().map(|variable| self.normalise_variable(variable)).collect()
     }
@@ -58,33 +609,169 @@ impl Normaliser {
     pub fn normalise_variable(&mut self, variable: &air::Variable) -> Variable {
         Variable::new(
             variable.symbolise(),
-            self.normalise_type(&variable.typedef()),
+            self.normalise_type(&variable.ty),
         )
     }
 
     pub fn normalise_void_expr(&mut self, void_expr: &air::VoidExpr) -> VoidExpr {
-        VoidExpr::new(void_expr.identify())
+        VoidExpr::new_with_id(void_expr.identify())
     }
 
     pub fn normalise_expr(&mut self, expr: &air::Expr) -> Expr {
         match *expr {
-            air::Expr::Block(ref block_expr) => self.normalise_block_expr(block_expr),
-            _ => unimplemented!(),
-        }.into()
+            air::Expr::Assign(ref assign_expr) => self.normalise_assign_expr(assign_expr).into(),
+            air::Expr::Block(ref block_expr) => self.normalise_block_expr(block_expr).into(),
+            air::Expr::Break(ref break_expr) => self.normalise_break_expr(break_expr).into(),
+            air::Expr::Call(ref call_expr) => self.normalise_call_expr(call_expr).into(),
+            air::Expr::Channel(ref channel_expr) => self.normalise_channel_expr(channel_expr).into(),
+            air::Expr::ChannelOp(ref channel_op_expr) => self.normalise_channel_op_expr(channel_op_expr).into(),
+            air::Expr::Coalesce(ref coalesce_expr) => self.normalise_coalesce_expr(coalesce_expr).into(),
+            air::Expr::Continue(ref continue_expr) => self.normalise_continue_expr(continue_expr).into(),
+            air::Expr::Deref(ref deref_expr) => self.normalise_deref_expr(deref_expr).into(),
+            air::Expr::Do(ref do_expr) => self.normalise_do_expr(do_expr).into(),
+            air::Expr::Enum(ref enum_expr) => self.normalise_enum_expr(enum_expr).into(),
+            air::Expr::For(ref for_expr) => self.normalise_for_expr(for_expr).into(),
+            air::Expr::If(ref if_expr) => self.normalise_if_expr(if_expr).into(),
+            air::Expr::Item(ref item_expr) => {
+                match item_expr.item {
+                    air::Item::Function(ref function) => self.normalise_function_value(function).into(),
+                    _ => self.normalise_item_expr(item_expr).into(),
+                }
+            }
+            air::Expr::Lambda(ref lambda_expr) => self.normalise_lambda_expr(lambda_expr).into(),
+            air::Expr::Let(ref let_expr) => self.normalise_let_expr(let_expr).into(),
+            air::Expr::LetTuple(ref let_tuple_expr) => self.normalise_let_tuple_expr(let_tuple_expr).into(),
+            air::Expr::List(ref list_expr) => self.normalise_list_expr(list_expr).into(),
+            air::Expr::ListOp(ref list_op_expr) => self.normalise_list_op_expr(list_op_expr).into(),
+            air::Expr::Literal(ref literal_expr) => self.normalise_literal_expr(literal_expr).into(),
+            air::Expr::Match(ref match_expr) => self.normalise_match_expr(match_expr).into(),
+            air::Expr::Optional(ref optional_expr) => self.normalise_optional_expr(optional_expr).into(),
+            air::Expr::Process(ref process_expr) => self.normalise_process_expr(process_expr).into(),
+            air::Expr::Range(ref range_expr) => self.normalise_range_expr(range_expr).into(),
+            air::Expr::Select(ref select_expr) => self.normalise_select_expr(select_expr).into(),
+            air::Expr::Struct(ref struct_expr) => self.normalise_struct_expr(struct_expr).into(),
+            air::Expr::Ref(ref ref_expr) => self.normalise_ref_expr(ref_expr).into(),
+            air::Expr::Tuple(ref tuple_expr) => self.normalise_tuple_expr(tuple_expr).into(),
+            air::Expr::Unwrap(ref unwrap_expr) => self.normalise_unwrap_expr(unwrap_expr).into(),
+            air::Expr::Variable(ref variable_expr) => self.normalise_variable_expr(variable_expr).into(),
+            air::Expr::Void(ref void_expr) => self.normalise_void_expr(void_expr).into(),
+        }
     }
 
     pub fn normalise_exprs(&mut self, exprs: &air::Exprs) -> Exprs {
         exprs.iter().map(|expr| self.normalise_expr(expr)).collect()
     }
 
+    /// An alias is normalised into the type that it names. Generic types are
+    /// removed by the monomorphiser, and inference variables that survive the
+    /// type checker are integer or float literals with the default types.
     pub fn normalise_type(&mut self, expr: &air::Type) -> Type {
         match *expr {
-            air::Type::Lambda(ref lambda_type) => self.normalise_lambda_type(lambda_type),
-            _ => unimplemented!(),
-        }.into()
+            air::Type::Alias(ref alias_type) => self.normalise_type(&alias_type.inner),
+            air::Type::Channel(ref channel_type) => self.normalise_channel_type(channel_type).into(),
+            air::Type::Enum(ref enum_type) => self.normalise_enum_type(enum_type).into(),
+            air::Type::Generic(..) => unreachable!("generic type after monomorphisation"),
+            air::Type::Infer(ref infer_type) => {
+                match infer_type.kind {
+                    air::InferKind::Any => unreachable!("uninferred type after type checking"),
+                    air::InferKind::Float => PrimitiveType::F64.into(),
+                    air::InferKind::Int => PrimitiveType::I64.into(),
+                }
+            }
+            air::Type::Lambda(ref lambda_type) => self.normalise_lambda_type(lambda_type).into(),
+            air::Type::List(ref list_type) => self.normalise_list_type(list_type).into(),
+            air::Type::Optional(ref optional_type) => self.normalise_optional_type(optional_type).into(),
+            air::Type::Primitive(ref primitive_type) => self.normalise_primitive_type(primitive_type).into(),
+            air::Type::Ptr(ref ptr_type) => self.normalise_ptr_type(ptr_type).into(),
+            air::Type::Ref(ref ref_type) => RefType::new(self.normalise_type(&ref_type.inner)).into(),
+            air::Type::Struct(ref struct_type) => self.normalise_struct_type(struct_type).into(),
+            air::Type::Tuple(ref tuple_type) => self.normalise_tuple_type(tuple_type).into(),
+        }
     }
 
     pub fn normalise_types(&mut self, types: &air::Types) -> Types {
         types.iter().map(|ty| self.normalise_type(ty)).collect()
     }
-}
\ No newline at end of file
+
+    /// Capture a variable in the innermost closure, unless it is declared by
+    /// the closure.
+    fn capture(&mut self, variable: &Variable) {
+        if let Some(closure) = self.closures.last_mut() {
+            let identifier = variable.identify();
+            if !closure.declarations.contains(&identifier) &&
+               !closure.captures.iter().any(|capture| capture.identify() == identifier) {
+                closure.captures.push(variable.clone());
+            }
+        }
+    }
+
+    fn declare_variables(&mut self, variables: &Variables) {
+        if let Some(closure) = self.closures.last_mut() {
+            closure.declarations.extend(variables.iter().map(|variable| variable.identify()));
+        }
+    }
+
+    /// Normalise within a new closure that declares `declarations`. The
+    /// captures of the closure are also captured by the enclosing closure,
+    /// which must pass them on.
+    fn normalise_closure<F, T>(&mut self, declarations: &Variables, normalise: F) -> (T, Variables)
+        where F: FnOnce(&mut Normaliser) -> T
+    {
+        self.closures.push(Closure::new(declarations));
+        let normalised = normalise(self);
+        let captures = self.closures.pop().expect("expected a closure").captures;
+        for capture in captures.iter() {
+            self.capture(capture);
+        }
+        (normalised, captures)
+    }
+
+    /// Normalise an expression into a block that starts each of its
+    /// expressions as a process. The expressions of a block are started
+    /// separately, and the block joins them all.
+    fn normalise_concurrent_expr(&mut self, expr: &air::Expr) -> BlockExpr {
+        let exprs = match *expr {
+            air::Expr::Block(ref block_expr) => {
+                let mut exprs = block_expr.body.clone();
+                exprs.push(block_expr.ret.clone());
+                exprs
+            }
+            _ => vec![expr.clone()],
+        };
+        let void_expr = || -> air::Expr { air::VoidExpr::new(Identifier::id()).into() };
+        let processes = exprs.into_iter()
+            .filter(|expr| if let air::Expr::Void(..) = *expr { false } else { true })
+            .map(|expr| {
+                let body = air::BlockExpr::new(Identifier::id(),
+                                               vec![expr],
+                                               void_expr(),
+                                               air::FunctionTable::new(),
+                                               air::ModuleTable::new(),
+                                               air::TypeTable::new());
+                air::ProcessExpr::new(Identifier::id(), body).into()
+            })
+            .collect();
+        self.normalise_block_expr(&air::BlockExpr::new(Identifier::id(),
+                                                       processes,
+                                                       void_expr(),
+                                                       air::FunctionTable::new(),
+                                                       air::ModuleTable::new(),
+                                                       air::TypeTable::new()))
+    }
+}
+
+/// Wrap the body of a function, or of a lambda, in a `BlockExpr` if it is not
+/// one already.
+fn air_block_expr(body: &air::Expr) -> air::BlockExpr {
+    match *body {
+        air::Expr::Block(ref block_expr) => block_expr.as_ref().clone(),
+        _ => {
+            air::BlockExpr::new(Identifier::id(),
+                                air::Exprs::new(),
+                                body.clone(),
+                                air::FunctionTable::new(),
+                                air::ModuleTable::new(),
+                                air::TypeTable::new())
+        }
+    }
+}
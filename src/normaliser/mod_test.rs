@@ -0,0 +1,266 @@
+use super::*;
+
+fn block(body: air::Exprs) -> air::BlockExpr {
+    air::BlockExpr::new(
+        Identifier::id(),
+        body,
+        air::VoidExpr::new(Identifier::id()).into(),
+        air::FunctionTable::new(),
+        air::ModuleTable::new(),
+        air::TypeTable::new(),
+    )
+}
+
+#[test]
+fn normalise_process_joins() {
+    // a process within a process is joined by the block of the outer process
+    let inner = air::ProcessExpr::new(Identifier::id(), block(air::Exprs::new()));
+    let outer = air::ProcessExpr::new(Identifier::id(), block(vec![inner.clone().into()]));
+    let other = air::ProcessExpr::new(Identifier::id(), block(air::Exprs::new()));
+    let block_expr = block(vec![outer.clone().into(), other.clone().into()]);
+
+    let block_expr = Normaliser::new().normalise_block_expr(&block_expr);
+    let joined = |block_expr: &BlockExpr| -> Vec<Identifier> {
+        block_expr.epilogue
+            .iter()
+            .map(|expr| match *expr {
+                Expr::ProcessJoin(ref process_join_expr) => process_join_expr.process_expr.identify(),
+                _ => panic!("expected a process join"),
+            })
+            .collect()
+    };
+    assert_eq!(joined(&block_expr), vec![outer.identify(), other.identify()]);
+    match block_expr.body[0] {
+        Expr::Process(ref process_expr) => assert_eq!(joined(&process_expr.body), vec![inner.identify()]),
+        _ => panic!("expected a process"),
+    }
+}
+
+#[test]
+fn normalise_block_tables() {
+    let f = air::Function::new(
+        Symbol::new("f"),
+        air::Variables::new(),
+        air::PrimitiveType::Void.into(),
+        Some(air::VoidExpr::new(Identifier::id()).into()),
+    );
+    let m = air::Module::new(Symbol::new("m"), air::FunctionTable::new(), air::ModuleTable::new(), air::TypeTable::new());
+    let t = Identifier::id();
+    let mut inner = block(air::Exprs::new());
+    inner.function_table.insert(f.identify(), f.clone());
+    inner.module_table.insert(m.identify(), m.clone());
+    inner.type_table.insert(t.clone(), air::PrimitiveType::I64.into());
+
+    // the items declared in a nested block are kept
+    let block_expr = Normaliser::new().normalise_block_expr(&block(vec![inner.into()]));
+    match block_expr.body[0] {
+        Expr::Block(ref inner) => {
+            assert_eq!(inner.function_table[&f.identify()].symbolise(), f.symbolise());
+            assert_eq!(inner.module_table[&m.identify()].symbolise(), m.symbolise());
+            match inner.type_table[&t] {
+                Type::Primitive(ref ty) => if let PrimitiveType::I64 = **ty {} else { panic!("expected i64") },
+                _ => panic!("expected a primitive type"),
+            }
+        }
+        _ => panic!("expected a block"),
+    }
+}
+
+#[test]
+fn normalise_do_expr() {
+    let x = air::Variable::new(Symbol::new("x"), air::PrimitiveType::I64.into());
+    let variable = || -> air::Expr { air::VariableExpr::new(Identifier::id(), x.clone()).into() };
+    let do_expr = air::DoExpr::new(
+        Identifier::id(),
+        air::BlockExpr::new(
+            Identifier::id(),
+            vec![variable()],
+            variable(),
+            air::FunctionTable::new(),
+            air::ModuleTable::new(),
+            air::TypeTable::new(),
+        ).into(),
+        variable(),
+    );
+
+    // each side starts a process for each of its expressions, and joins them
+    let block_expr = Normaliser::new().normalise_do_expr(&do_expr);
+    let sides = block_expr.body
+        .iter()
+        .map(|expr| match *expr {
+            Expr::Block(ref block_expr) => (block_expr.body.len(), block_expr.epilogue.len()),
+            _ => panic!("expected a block"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(sides, vec![(2, 2), (1, 1)]);
+}
+
+#[test]
+fn normalise_function() {
+    let x = air::Variable::new(Symbol::new("x"), air::PrimitiveType::I64.into());
+    let g = air::Function::new(Symbol::new("g"), vec![x.clone()], air::PrimitiveType::I64.into(), None);
+    let call = air::CallExpr::new(
+        Identifier::id(),
+        air::ItemExpr::new(Identifier::id(), g.clone().into()).into(),
+        vec![air::LiteralExpr::new(Identifier::id(), air::Literal::Int(1)).into()],
+    );
+    let f = air::Function::new(Symbol::new("f"), air::Variables::new(), air::PrimitiveType::I64.into(), Some(call.into()));
+
+    // a body that is not a block is normalised into the return value of a block
+    let function = Normaliser::new().normalise_function(&f);
+    let body = function.body.expect("expected a body");
+    assert!(body.body.is_empty());
+    match body.ret {
+        Expr::Call(ref call_expr) => {
+            match call_expr.target {
+                Expr::Item(ref item_expr) => assert_eq!(item_expr.symbolise(), g.symbolise()),
+                _ => panic!("expected an item"),
+            }
+            match call_expr.arguments[0] {
+                Expr::Literal(ref literal_expr) => {
+                    match literal_expr.literal {
+                        Literal::I64(1) => (),
+                        _ => panic!("expected an i64 literal"),
+                    }
+                }
+                _ => panic!("expected a literal"),
+            }
+        }
+        _ => panic!("expected a call"),
+    }
+}
+
+#[test]
+fn normalise_type() {
+    let mut normaliser = Normaliser::new();
+    let alias_type = air::AliasType { inner: air::PrimitiveType::Bool.into() };
+    let ptr_type = air::PtrType { inner: air::PrimitiveType::U8.into() };
+
+    // an alias is the type that it names
+    match normaliser.normalise_type(&alias_type.into()) {
+        Type::Primitive(ref ty) => if let PrimitiveType::Bool = **ty {} else { panic!("expected bool") },
+        _ => panic!("expected a primitive type"),
+    }
+    match normaliser.normalise_type(&ptr_type.into()) {
+        Type::Ptr(ref ty) => if let Type::Primitive(..) = ty.inner {} else { panic!("expected a primitive type") },
+        _ => panic!("expected a pointer type"),
+    }
+
+    // integer and float literals default to `i64` and `f64`
+    match normaliser.normalise_type(&air::InferType::new(air::InferKind::Int).into()) {
+        Type::Primitive(ref ty) => if let PrimitiveType::I64 = **ty {} else { panic!("expected i64") },
+        _ => panic!("expected a primitive type"),
+    }
+    match normaliser.normalise_type(&air::InferType::new(air::InferKind::Float).into()) {
+        Type::Primitive(ref ty) => if let PrimitiveType::F64 = **ty {} else { panic!("expected f64") },
+        _ => panic!("expected a primitive type"),
+    }
+}
+
+#[test]
+fn normalise_process_captures() {
+    let x = air::Variable::new(Symbol::new("x"), air::PrimitiveType::I64.into());
+    let y = air::Variable::new(Symbol::new("y"), air::PrimitiveType::I64.into());
+    let i = air::Variable::new(Symbol::new("i"), air::PrimitiveType::I64.into());
+    let variable = |variable: &air::Variable| -> air::Expr {
+        air::VariableExpr::new(Identifier::id(), variable.clone()).into()
+    };
+    let int = |value: i64| -> air::Expr { air::LiteralExpr::new(Identifier::id(), air::Literal::I64(value)).into() };
+
+    // `x` is declared by the function, and `y` is declared by the outer process
+    let inner = air::ProcessExpr::new(Identifier::id(), block(vec![variable(&x), variable(&y)]));
+    let outer = air::ProcessExpr::new(Identifier::id(),
+                                      block(vec![air::LetExpr::new(Identifier::id(), y.clone(), int(1)).into(),
+                                                 inner.into()]));
+    let mut forall = air::ForExpr::new(Identifier::id(),
+                                       true,
+                                       vec![i.clone()],
+                                       air::RangeExpr::new(Identifier::id(), int(0), int(8)).into(),
+                                       block(vec![variable(&i), variable(&x)]));
+    forall.ty = air::PrimitiveType::I64.into();
+    let f = air::Function::new(Symbol::new("f"),
+                               vec![x.clone()],
+                               air::PrimitiveType::Void.into(),
+                               Some(block(vec![outer.into(), forall.into()]).into()));
+
+    let function = Normaliser::new().normalise_function(&f);
+    let body = function.body.expect("expected a body");
+    let captures = |variables: &Variables| -> Vec<Identifier> {
+        variables.iter().map(|variable| variable.identify()).collect()
+    };
+    match body.body[0] {
+        Expr::Process(ref process_expr) => {
+            assert_eq!(captures(&process_expr.captures), vec![x.identify()]);
+            match process_expr.body.body[1] {
+                Expr::Process(ref process_expr) => {
+                    assert_eq!(captures(&process_expr.captures), vec![x.identify(), y.identify()])
+                }
+                _ => panic!("expected a process"),
+            }
+        }
+        _ => panic!("expected a process"),
+    }
+    match body.body[1] {
+        Expr::For(ref for_expr) => assert_eq!(captures(&for_expr.captures), vec![x.identify()]),
+        _ => panic!("expected a for loop"),
+    }
+}
+
+#[test]
+fn normalise_lambda_exprs() {
+    let n = air::Variable::new(Symbol::new("n"), air::PrimitiveType::I64.into());
+    let x = air::Variable::new(Symbol::new("x"), air::PrimitiveType::I64.into());
+    let variable = |variable: &air::Variable| -> air::Expr {
+        air::VariableExpr::new(Identifier::id(), variable.clone()).into()
+    };
+    let g = air::Function::new(Symbol::new("g"),
+                               vec![x.clone()],
+                               air::PrimitiveType::I64.into(),
+                               None);
+    let item = || -> air::Expr { air::ItemExpr::new(Identifier::id(), g.clone().into()).into() };
+
+    // the lambda captures `n`, but not its own formal
+    let mut lambda_expr = air::LambdaExpr::new(Identifier::id(), vec![x.clone()], variable(&n));
+    lambda_expr.ret = air::PrimitiveType::I64.into();
+    let f = air::Function::new(Symbol::new("f"),
+                               vec![n.clone()],
+                               air::PrimitiveType::Void.into(),
+                               Some(block(vec![
+                                   lambda_expr.into(),
+                                   item(),
+                                   air::CallExpr::new(Identifier::id(), item(), vec![variable(&n)]).into(),
+                               ]).into()));
+
+    let function = Normaliser::new().normalise_function(&f);
+    let body = function.body.expect("expected a body");
+    match body.body[0] {
+        Expr::Lambda(ref lambda_expr) => {
+            let captures = lambda_expr.captures.iter().map(|capture| capture.identify()).collect::<Vec<_>>();
+            assert_eq!(captures, vec![n.identify()]);
+            assert_eq!(lambda_expr.function().formals.len(), 2);
+        }
+        _ => panic!("expected a lambda"),
+    }
+
+    // a function used as a value is a lambda that calls it, but a function
+    // that is called is called directly
+    match body.body[1] {
+        Expr::Lambda(ref lambda_expr) => {
+            assert_eq!(lambda_expr.captures.len(), 0);
+            match lambda_expr.body.ret {
+                Expr::Call(ref call_expr) => assert_eq!(call_expr.arguments.len(), 1),
+                _ => panic!("expected a call"),
+            }
+        }
+        _ => panic!("expected a lambda"),
+    }
+    match body.body[2] {
+        Expr::Call(ref call_expr) => {
+            match call_expr.target {
+                Expr::Item(ref item_expr) => assert_eq!(item_expr.symbolise().identify(), g.identify()),
+                _ => panic!("expected an item"),
+            }
+        }
+        _ => panic!("expected a call"),
+    }
+}
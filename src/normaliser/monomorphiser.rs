@@ -0,0 +1,443 @@
+//! # Monomorphiser
+//!
+//! The NoIR has no notion of generics, so before an AIR is normalised every
+//! generic `Function`, `StructType` and `EnumType` is replaced by a concrete
+//! instance for each set of type arguments that it is used with. Instances
+//! are named after the generic that they instantiate, followed by their type
+//! arguments, e.g. `swap<i64, bool>`. Generic functions that are never used
+//! produce no instance at all.
+
+use super::super::air::*;
+use super::super::identifier::{Identifier, Identify, Name, Symbol, Symbolise};
+
+use std::collections::HashMap;
+
+pub struct Monomorphiser {
+    function_instances: HashMap<(Identifier, String), Function>,
+    type_instances: HashMap<(Identifier, String), Symbol>,
+    /// Whether every instance has been created, and instances are being put
+    /// in place of their generic functions.
+    placing_instances: bool,
+}
+
+impl Monomorphiser {
+    pub fn new() -> Monomorphiser {
+        Monomorphiser {
+            function_instances: HashMap::new(),
+            type_instances: HashMap::new(),
+            placing_instances: false,
+        }
+    }
+
+    /// Monomorphise a `Module` and all of its submodules. Generic functions
+    /// and types are removed, and their instances are added to the function
+    /// table that declares the generic, so that they are in the same scope.
+    ///
+    /// # Arguments
+    /// * `module` - A module that has been type checked.
+    pub fn monomorphise(&mut self, module: &mut Module) {
+        // a generic can be used before its declaration is reached, so every
+        // instance is created before any of them is put in place
+        self.monomorphise_module(module);
+        self.placing_instances = true;
+        self.monomorphise_module(module);
+        self.placing_instances = false;
+    }
+
+    pub fn monomorphise_module(&mut self, module: &mut Module) {
+        self.place_instances(&mut module.function_table);
+        for function in module.function_table.values_mut() {
+            if !function.is_generic() {
+                self.monomorphise_function(function, &Substitution::new());
+            }
+        }
+        for module in module.module_table.values_mut() {
+            self.monomorphise_module(module);
+        }
+        module.type_table.retain(|_, ty| !is_generic_type(ty));
+    }
+
+    pub fn monomorphise_function(&mut self, function: &mut Function, substitution: &Substitution) {
+        for formal in function.formals.iter_mut() {
+            self.monomorphise_variable(formal, substitution);
+        }
+        function.ret = self.monomorphise_type(&function.ret, substitution);
+        if let Some(ref mut body) = function.body {
+            self.monomorphise_expr(body, substitution);
+        }
+    }
+
+    /// Get the instance of a generic `Function` for a set of type arguments,
+    /// creating it if it does not exist yet.
+    ///
+    /// # Arguments
+    /// * `function` - A generic function.
+    /// * `type_args` - The concrete types that substitute the type parameters
+    ///   of the function.
+    ///
+    /// # Return
+    /// A `Function` without type parameters.
+    pub fn instantiate(&mut self, function: &Function, type_args: &Types) -> Function {
+        let name = mangle(&function.symbolise().name(), type_args);
+        let key = (function.identify(), name.clone());
+        if let Some(instance) = self.function_instances.get(&key) {
+            return instance.clone();
+        }
+        let substitution = function.type_params
+            .iter()
+            .zip(type_args.iter())
+            .filter_map(|(param, type_arg)| match *param {
+                Type::Generic(ref param) => Some((param.identify(), type_arg.clone())),
+                _ => None,
+            })
+            .collect::<Substitution>();
+        let mut instance = function.clone();
+        instance.symbol = Symbol::new(name);
+        instance.type_params = Types::new();
+        for formal in instance.formals.iter_mut() {
+            self.monomorphise_variable(formal, &substitution);
+        }
+        instance.ret = self.monomorphise_type(&instance.ret, &substitution);
+        // the instance is registered before its body is monomorphised, so
+        // that recursive uses of the function refer to this instance
+        self.function_instances.insert(key.clone(), instance.clone());
+        self.monomorphise_function(&mut instance, &substitution);
+        self.function_instances.insert(key, instance.clone());
+        instance
+    }
+
+    pub fn monomorphise_block_expr(&mut self, block_expr: &mut BlockExpr, substitution: &Substitution) {
+        self.place_instances(&mut block_expr.function_table);
+        for function in block_expr.function_table.values_mut() {
+            if !function.is_generic() {
+                self.monomorphise_function(function, substitution);
+            }
+        }
+        for module in block_expr.module_table.values_mut() {
+            self.monomorphise_module(module);
+        }
+        block_expr.type_table.retain(|_, ty| !is_generic_type(ty));
+        for expr in block_expr.body.iter_mut() {
+            self.monomorphise_expr(expr, substitution);
+        }
+        self.monomorphise_expr(&mut block_expr.ret, substitution);
+    }
+
+    /// Replace the generic functions of a function table by their instances,
+    /// once every instance has been created.
+    fn place_instances(&mut self, function_table: &mut FunctionTable) {
+        if !self.placing_instances {
+            return;
+        }
+        let generics = function_table.values()
+            .filter(|function| function.is_generic())
+            .map(|function| function.identify())
+            .collect::<Vec<_>>();
+        function_table.retain(|_, function| !function.is_generic());
+        for (&(ref generic, _), instance) in self.function_instances.iter() {
+            if generics.contains(generic) {
+                function_table.insert(instance.identify(), instance.clone());
+            }
+        }
+    }
+
+    pub fn monomorphise_item_expr(&mut self, item_expr: &mut ItemExpr, substitution: &Substitution) {
+        let type_args = item_expr.type_args
+            .iter()
+            .map(|type_arg| self.monomorphise_type(type_arg, substitution))
+            .collect::<Types>();
+        let instance = match item_expr.item {
+            Item::Function(ref function) if function.is_generic() => {
+                Some(self.instantiate(function, &type_args))
+            }
+            Item::Type(ref mut ty) => {
+                **ty = self.monomorphise_type(ty, substitution);
+                None
+            }
+            Item::Variable(ref mut variable) => {
+                self.monomorphise_variable(variable, substitution);
+                None
+            }
+            Item::Function(..) |
+            Item::Module(..) => None,
+        };
+        if let Some(instance) = instance {
+            item_expr.item = instance.into();
+            item_expr.type_args = Types::new();
+        }
+    }
+
+    pub fn monomorphise_variable(&mut self, variable: &mut Variable, substitution: &Substitution) {
+        variable.ty = self.monomorphise_type(&variable.ty, substitution);
+    }
+
+    pub fn monomorphise_expr(&mut self, expr: &mut Expr, substitution: &Substitution) {
+        match *expr {
+            Expr::Assign(ref mut expr) => {
+                self.monomorphise_expr(&mut expr.lhs, substitution);
+                self.monomorphise_expr(&mut expr.rhs, substitution);
+            }
+            Expr::Block(ref mut expr) => self.monomorphise_block_expr(expr, substitution),
+            Expr::Break(..) => (),
+            Expr::Call(ref mut expr) => {
+                self.monomorphise_expr(&mut expr.target, substitution);
+                for argument in expr.arguments.iter_mut() {
+                    self.monomorphise_expr(argument, substitution);
+                }
+            }
+            Expr::Channel(ref mut expr) => {
+                if let Some(ref mut capacity) = expr.capacity {
+                    self.monomorphise_expr(capacity, substitution);
+                }
+                expr.ty = self.monomorphise_type(&expr.ty, substitution);
+            }
+            Expr::ChannelOp(ref mut expr) => {
+                expr.ty = self.monomorphise_type(&expr.ty, substitution);
+                match expr.op {
+                    ChannelOp::Close(ref mut channel) |
+                    ChannelOp::Recv(ref mut channel) => self.monomorphise_expr(channel, substitution),
+                    ChannelOp::Send(ref mut channel, ref mut value) => {
+                        self.monomorphise_expr(channel, substitution);
+                        self.monomorphise_expr(value, substitution);
+                    }
+                }
+            }
+            Expr::Coalesce(ref mut expr) => {
+                expr.ty = self.monomorphise_type(&expr.ty, substitution);
+                self.monomorphise_expr(&mut expr.lhs, substitution);
+                self.monomorphise_expr(&mut expr.rhs, substitution);
+            }
+            Expr::Continue(..) => (),
+            Expr::Deref(ref mut expr) => self.monomorphise_expr(&mut expr.inner, substitution),
+            Expr::Do(ref mut expr) => {
+                self.monomorphise_expr(&mut expr.do_expr, substitution);
+                self.monomorphise_expr(&mut expr.then_expr, substitution);
+            }
+            Expr::Enum(ref mut expr) => {
+                expr.ty = self.monomorphise_type(&expr.ty, substitution);
+                self.monomorphise_variable(&mut expr.variant, substitution);
+                for &mut (ref mut variable, ref mut expr) in expr.elements.iter_mut() {
+                    self.monomorphise_variable(variable, substitution);
+                    self.monomorphise_expr(expr, substitution);
+                }
+            }
+            Expr::For(ref mut expr) => {
+                expr.ty = self.monomorphise_type(&expr.ty, substitution);
+                for formal in expr.formals.iter_mut() {
+                    self.monomorphise_variable(formal, substitution);
+                }
+                self.monomorphise_expr(&mut expr.iterator, substitution);
+                self.monomorphise_block_expr(&mut expr.iteration, substitution);
+            }
+            Expr::If(ref mut expr) => {
+                self.monomorphise_expr(&mut expr.condition, substitution);
+                self.monomorphise_block_expr(&mut expr.then_block, substitution);
+                self.monomorphise_block_expr(&mut expr.else_block, substitution);
+            }
+            Expr::Item(ref mut expr) => self.monomorphise_item_expr(expr, substitution),
+            Expr::Lambda(ref mut expr) => {
+                expr.ret = self.monomorphise_type(&expr.ret, substitution);
+                for formal in expr.formals.iter_mut() {
+                    self.monomorphise_variable(formal, substitution);
+                }
+                self.monomorphise_expr(&mut expr.body, substitution);
+            }
+            Expr::Let(ref mut expr) => {
+                self.monomorphise_variable(&mut expr.variable, substitution);
+                self.monomorphise_expr(&mut expr.definition, substitution);
+            }
+            Expr::LetTuple(ref mut expr) => {
+                for variable in expr.variables.iter_mut() {
+                    self.monomorphise_variable(variable, substitution);
+                }
+                self.monomorphise_expr(&mut expr.definition, substitution);
+            }
+            Expr::List(ref mut expr) => {
+                expr.ty = self.monomorphise_type(&expr.ty, substitution);
+                for element in expr.elements.iter_mut() {
+                    self.monomorphise_expr(element, substitution);
+                }
+            }
+            Expr::ListOp(ref mut expr) => {
+                expr.ty = self.monomorphise_type(&expr.ty, substitution);
+                match expr.op {
+                    ListOp::Index(ref mut list, ref mut operand) |
+                    ListOp::Push(ref mut list, ref mut operand) => {
+                        self.monomorphise_expr(list, substitution);
+                        self.monomorphise_expr(operand, substitution);
+                    }
+                    ListOp::Len(ref mut list) |
+                    ListOp::Pop(ref mut list) => self.monomorphise_expr(list, substitution),
+                    ListOp::Slice(ref mut list, ref mut start, ref mut end) => {
+                        self.monomorphise_expr(list, substitution);
+                        self.monomorphise_expr(start, substitution);
+                        self.monomorphise_expr(end, substitution);
+                    }
+                }
+            }
+            Expr::Literal(..) => (),
+            Expr::Match(ref mut expr) => {
+                self.monomorphise_expr(&mut expr.subject, substitution);
+                for arm in expr.arms.iter_mut() {
+                    if let Some(ref mut variant) = arm.variant {
+                        self.monomorphise_variable(variant, substitution);
+                    }
+                    for binding in arm.bindings.iter_mut() {
+                        self.monomorphise_variable(binding, substitution);
+                    }
+                    self.monomorphise_block_expr(&mut arm.body, substitution);
+                }
+            }
+            Expr::Optional(ref mut expr) => {
+                expr.ty = self.monomorphise_type(&expr.ty, substitution);
+                for value in expr.value.iter_mut() {
+                    self.monomorphise_expr(value, substitution);
+                }
+            }
+            Expr::Process(ref mut expr) => self.monomorphise_block_expr(&mut expr.body, substitution),
+            Expr::Range(ref mut expr) => {
+                expr.ty = self.monomorphise_type(&expr.ty, substitution);
+                self.monomorphise_expr(&mut expr.start, substitution);
+                self.monomorphise_expr(&mut expr.end, substitution);
+            }
+            Expr::Ref(ref mut expr) => self.monomorphise_expr(&mut expr.inner, substitution),
+            Expr::Select(ref mut expr) => {
+                for guard in expr.guards.iter_mut() {
+                    guard.ty = self.monomorphise_type(&guard.ty, substitution);
+                    match guard.op {
+                        SelectOp::Recv(ref mut channel, ref mut variable) => {
+                            self.monomorphise_expr(channel, substitution);
+                            if let Some(ref mut variable) = *variable {
+                                self.monomorphise_variable(variable, substitution);
+                            }
+                        }
+                        SelectOp::Send(ref mut channel, ref mut value) => {
+                            self.monomorphise_expr(channel, substitution);
+                            self.monomorphise_expr(value, substitution);
+                        }
+                    }
+                    self.monomorphise_block_expr(&mut guard.body, substitution);
+                }
+                if let Some(ref mut else_body) = expr.else_body {
+                    self.monomorphise_block_expr(else_body, substitution);
+                }
+            }
+            Expr::Struct(ref mut expr) => {
+                expr.ty = self.monomorphise_type(&expr.ty, substitution);
+                for &mut (ref mut variable, ref mut expr) in expr.elements.iter_mut() {
+                    self.monomorphise_variable(variable, substitution);
+                    self.monomorphise_expr(expr, substitution);
+                }
+            }
+            Expr::Tuple(ref mut expr) => {
+                for element in expr.elements.iter_mut() {
+                    self.monomorphise_expr(element, substitution);
+                }
+            }
+            Expr::Unwrap(ref mut expr) => {
+                expr.ty = self.monomorphise_type(&expr.ty, substitution);
+                self.monomorphise_expr(&mut expr.inner, substitution);
+            }
+            Expr::Variable(ref mut expr) => {
+                self.monomorphise_variable(&mut expr.variable, substitution);
+                for parent in expr.parent.iter_mut() {
+                    self.monomorphise_expr(parent, substitution);
+                }
+            }
+            Expr::Void(..) => (),
+        }
+    }
+
+    /// Substitute type parameters in a `Type`, and replace every instance of
+    /// a generic `StructType` or `EnumType` by a concrete type with its own
+    /// `Symbol`.
+    pub fn monomorphise_type(&mut self, ty: &Type, substitution: &Substitution) -> Type {
+        match ty.substitute(substitution) {
+            Type::Alias(ty) => {
+                Type::Alias(Box::new(AliasType { inner: self.monomorphise_type(&ty.inner, &Substitution::new()) }))
+            }
+            Type::Channel(ty) => ChannelType::new(self.monomorphise_type(&ty.element, &Substitution::new())).into(),
+            Type::Enum(ty) => {
+                if ty.params.is_empty() {
+                    return Type::Enum(ty);
+                }
+                let params = self.monomorphise_types(&ty.params);
+                let symbol = self.instantiate_symbol(&ty.symbol, &params);
+                let mut variants = self.monomorphise_struct_type(&ty.variants);
+                variants.symbol = symbol.clone();
+                Type::Enum(Box::new(EnumType {
+                    symbol: symbol,
+                    params: Types::new(),
+                    variants: Box::new(variants),
+                }))
+            }
+            Type::Lambda(ty) => {
+                LambdaType::new(self.monomorphise_types(&ty.formals),
+                                self.monomorphise_type(&ty.ret, &Substitution::new()))
+                    .into()
+            }
+            Type::List(ty) => ListType::new(self.monomorphise_type(&ty.element, &Substitution::new())).into(),
+            Type::Optional(ty) => OptionalType::new(self.monomorphise_type(&ty.inner, &Substitution::new())).into(),
+            Type::Ptr(ty) => {
+                Type::Ptr(Box::new(PtrType { inner: self.monomorphise_type(&ty.inner, &Substitution::new()) }))
+            }
+            Type::Ref(ty) => {
+                Type::Ref(Box::new(RefType {
+                    inner: self.monomorphise_type(&ty.inner, &Substitution::new()),
+                    is_mut: ty.is_mut,
+                }))
+            }
+            Type::Struct(ty) => {
+                if ty.params.is_empty() {
+                    return Type::Struct(ty);
+                }
+                let params = self.monomorphise_types(&ty.params);
+                let mut instance = self.monomorphise_struct_type(&ty);
+                instance.symbol = self.instantiate_symbol(&ty.symbol, &params);
+                instance.into()
+            }
+            Type::Tuple(ty) => TupleType::new(self.monomorphise_types(&ty.elements)).into(),
+            ty => ty,
+        }
+    }
+
+    fn monomorphise_struct_type(&mut self, struct_type: &StructType) -> StructType {
+        StructType::new(struct_type.symbol.clone(),
+                        Types::new(),
+                        struct_type.elements
+                            .iter()
+                            .map(|element| {
+                                Variable::new(element.symbol.clone(),
+                                              self.monomorphise_type(&element.ty, &Substitution::new()))
+                            })
+                            .collect())
+    }
+
+    fn monomorphise_types(&mut self, types: &Types) -> Types {
+        types.iter().map(|ty| self.monomorphise_type(ty, &Substitution::new())).collect()
+    }
+
+    /// Get the `Symbol` of the instance of a generic type. Every use of the
+    /// same instance shares the same `Symbol`.
+    fn instantiate_symbol(&mut self, symbol: &Symbol, type_args: &Types) -> Symbol {
+        let name = mangle(&symbol.name(), type_args);
+        self.type_instances
+            .entry((symbol.identify(), name.clone()))
+            .or_insert_with(|| Symbol::new(name))
+            .clone()
+    }
+}
+
+fn mangle(name: &str, type_args: &Types) -> String {
+    format!("{}<{}>",
+            name,
+            type_args.iter().map(|type_arg| format!("{}", type_arg)).collect::<Vec<_>>().join(", "))
+}
+
+fn is_generic_type(ty: &Type) -> bool {
+    match *ty {
+        Type::Enum(ref ty) => !ty.params.is_empty(),
+        Type::Struct(ref ty) => !ty.params.is_empty(),
+        _ => false,
+    }
+}
@@ -0,0 +1,149 @@
+use super::monomorphiser::*;
+use super::super::air::*;
+use super::super::identifier::{Identifier, Identify, Name, Symbol, Symbolise};
+
+fn id_fn() -> Function {
+    let a = GenericType::new(Identifier::name("a"));
+    let x = Variable::new(Symbol::new("x"), a.clone().into());
+    Function::new_generic(
+        Symbol::new("id"),
+        vec![a.clone().into()],
+        vec![x.clone()],
+        a.into(),
+        Some(VariableExpr::new(Identifier::id(), x).into()),
+    )
+}
+
+fn call_id(id: &Function, type_arg: PrimitiveType, literal: Literal) -> Expr {
+    let mut item_expr = ItemExpr::new(Identifier::id(), id.clone().into());
+    item_expr.type_args = vec![type_arg.into()];
+    CallExpr::new(
+        Identifier::id(),
+        item_expr.into(),
+        vec![LiteralExpr::new(Identifier::id(), literal).into()],
+    ).into()
+}
+
+#[test]
+fn monomorphise_function() {
+    let id = id_fn();
+    let main = Function::new(
+        Symbol::new("main"),
+        Variables::new(),
+        PrimitiveType::Void.into(),
+        Some(BlockExpr::new(
+            Identifier::id(),
+            vec![
+                call_id(&id, PrimitiveType::I64, Literal::I64(1)),
+                call_id(&id, PrimitiveType::Bool, Literal::Bool(true)),
+                call_id(&id, PrimitiveType::I64, Literal::I64(2)),
+            ],
+            VoidExpr::new(Identifier::id()).into(),
+            FunctionTable::new(),
+            ModuleTable::new(),
+            TypeTable::new(),
+        ).into()),
+    );
+    let mut module = Module::new(
+        Symbol::new("main"),
+        vec![(id.identify(), id.clone()), (main.identify(), main.clone())].into_iter().collect::<FunctionTable>(),
+        ModuleTable::new(),
+        TypeTable::new(),
+    );
+    Monomorphiser::new().monomorphise(&mut module);
+
+    // the generic is replaced by one instance for each set of type arguments
+    let mut names = module.function_table.values().map(|function| function.symbolise().name()).collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, vec!["id<bool>", "id<i64>", "main"]);
+    for function in module.function_table.values() {
+        assert!(!function.is_generic());
+        if function.symbolise().name() == "id<i64>" {
+            assert_eq!(function.formals[0].ty, PrimitiveType::I64.into());
+            assert_eq!(function.ret, PrimitiveType::I64.into());
+        }
+    }
+
+    // every call refers to its instance
+    let main = module.function_table.get(&main.identify()).unwrap();
+    let body = match main.body {
+        Some(Expr::Block(ref block_expr)) => block_expr.body.clone(),
+        _ => panic!("expected a block"),
+    };
+    let targets = body.iter().map(|expr| match *expr {
+        Expr::Call(ref call_expr) => match call_expr.target {
+            Expr::Item(ref item_expr) => match item_expr.item {
+                Item::Function(ref function) => function.identify(),
+                _ => panic!("expected a function"),
+            },
+            _ => panic!("expected an item"),
+        },
+        _ => panic!("expected a call"),
+    }).collect::<Vec<_>>();
+    assert!(targets[0] == targets[2]);
+    assert!(targets[0] != targets[1]);
+    assert!(targets[0] != id.identify());
+}
+
+#[test]
+fn monomorphise_function_in_submodule() {
+    let id = id_fn();
+    let main = Function::new(
+        Symbol::new("main"),
+        Variables::new(),
+        PrimitiveType::Void.into(),
+        Some(call_id(&id, PrimitiveType::I64, Literal::I64(1))),
+    );
+    let util = Module::new(
+        Symbol::new("util"),
+        vec![(id.identify(), id.clone())].into_iter().collect::<FunctionTable>(),
+        ModuleTable::new(),
+        TypeTable::new(),
+    );
+    let mut module = Module::new(
+        Symbol::new("main"),
+        vec![(main.identify(), main.clone())].into_iter().collect::<FunctionTable>(),
+        vec![(util.identify(), util.clone())].into_iter().collect::<ModuleTable>(),
+        TypeTable::new(),
+    );
+    Monomorphiser::new().monomorphise(&mut module);
+
+    // the instance is declared next to its generic, even though it is used
+    // from another module
+    let names = |function_table: &FunctionTable| -> Vec<String> {
+        function_table.values().map(|function| function.symbolise().name()).collect()
+    };
+    assert_eq!(names(&module.function_table), vec!["main"]);
+    assert_eq!(names(&module.module_table.get(&util.identify()).unwrap().function_table), vec!["id<i64>"]);
+}
+
+#[test]
+fn monomorphise_struct_type() {
+    let a = GenericType::new(Identifier::name("a"));
+    let point = StructType::new(
+        Symbol::new("Point"),
+        vec![a.clone().into()],
+        vec![
+            Variable::new(Symbol::new("x"), a.clone().into()),
+            Variable::new(Symbol::new("y"), a.into()),
+        ],
+    );
+    let mut monomorphiser = Monomorphiser::new();
+    let substitution = Substitution::new();
+
+    let point_i64 = monomorphiser.monomorphise_type(&point.instantiate(vec![PrimitiveType::I64.into()]).into(), &substitution);
+    let point_f64 = monomorphiser.monomorphise_type(&point.instantiate(vec![PrimitiveType::F64.into()]).into(), &substitution);
+    assert_eq!(format!("{}", point_i64), "Point<i64>");
+    assert_eq!(format!("{}", point_f64), "Point<f64>");
+    match point_i64 {
+        Type::Struct(ref ty) => {
+            assert!(ty.params.is_empty());
+            assert_eq!(ty.elements[0].ty, PrimitiveType::I64.into());
+        }
+        _ => panic!("expected a struct type"),
+    }
+
+    // the same instance always has the same symbol
+    let point_i64_again = monomorphiser.monomorphise_type(&point.instantiate(vec![PrimitiveType::I64.into()]).into(), &substitution);
+    assert_eq!(point_i64, point_i64_again);
+}
@@ -84,8 +84,16 @@ impl Parser {
         let operator = match token {
             Token::Add(..) => Operator::Add,
             Token::Div(..) => Operator::Div,
+            Token::DotDot(..) => Operator::Range,
             Token::Mul(..) => Operator::Mul,
+            Token::PushPop(..) => Operator::PushPop,
+            Token::QuestionQuestion(..) => Operator::Coalesce,
             Token::Sub(..) => Operator::Sub,
+            // Tokens that end an expression.
+            Token::BracketR(..) |
+            Token::Comma(..) |
+            Token::ParenR(..) |
+            Token::Then(..) => return lhs_expr,
             _ => {
                 self.errors.push(ParserErr::new(token.span().clone(), format!("Unexpect token {}", token)));
                 return lhs_expr;
@@ -129,14 +137,36 @@ impl Parser {
 
     pub fn parse_unary_expr(&mut self) -> Expr {
         let token = self.current_token();
-        match token {
+        let expr = match token {
+            Some(Token::BracketL(..)) => self.parse_list_expr().into(),
+            Some(Token::Do(..)) => return self.parse_do_expr().into(),
+            Some(Token::Pipe(..)) => return self.parse_lambda_expr().into(),
+            Some(Token::DotDot(..)) => self.parse_channel_expr().into(),
+            Some(Token::PushPop(..)) => {
+                self.next_token();
+                let rhs_expr = self.parse_unary_expr();
+                return PrefixOperatorExpr::new(Operator::PushPop, rhs_expr).into();
+            }
             Some(Token::ParenL(..)) => self.parse_paren_expr(),
             Some(Token::Bool(..)) => self.parse_literal_bool_expr().into(),
             Some(Token::Char(..)) => self.parse_literal_char_expr().into(),
             Some(Token::Float(..)) => self.parse_literal_float_expr().into(),
             Some(Token::Int(..)) => self.parse_literal_int_expr().into(),
+            Some(Token::Nil(..)) => self.parse_literal_nil_expr().into(),
             Some(Token::Str(..)) => self.parse_literal_str_expr().into(),
             _ => unimplemented!(),
+        };
+        self.parse_suffix_expr(expr)
+    }
+
+    pub fn parse_suffix_expr(&mut self, lhs_expr: Expr) -> Expr {
+        match self.current_token() {
+            Some(Token::Unwrap(..)) => {
+                self.next_token();
+                let expr = SuffixOperatorExpr::new(Operator::Unwrap, lhs_expr).into();
+                self.parse_suffix_expr(expr)
+            }
+            _ => lhs_expr,
         }
     }
 
@@ -149,8 +179,17 @@ impl Parser {
             _ => unimplemented!(),
         };
 
-        // Parse the inner expression.
-        let expr = self.parse_expr();
+        // Parse the inner expression. A comma after the inner expression
+        // makes it the first field of a tuple.
+        let mut expr = self.parse_expr();
+        if let Some(Token::Comma(..)) = self.current_token() {
+            let mut fields = vec![expr];
+            while let Some(Token::Comma(..)) = self.current_token() {
+                self.next_token();
+                fields.push(self.parse_expr());
+            }
+            expr = TupleExpr::new(fields).into();
+        }
 
         // Eat the right parenthesis.
         let token = self.current_token();
@@ -162,6 +201,187 @@ impl Parser {
         expr
     }
 
+    pub fn parse_channel_expr(&mut self) -> ChannelExpr {
+
+        // Eat the range operator.
+        let token = self.current_token();
+        let span = match token {
+            Some(Token::DotDot(span, ..)) => {
+                self.next_token();
+                span
+            }
+            _ => unimplemented!(),
+        };
+
+        // A channel without a bound is closed by a parenthesis, which belongs
+        // to the enclosing expression.
+        match self.current_token() {
+            Some(Token::ParenR(..)) | None => ChannelExpr::new(None, VoidExpr::new(span)),
+            _ => ChannelExpr::new(None, self.parse_unary_expr()),
+        }
+    }
+
+    pub fn parse_do_expr(&mut self) -> DoExpr {
+
+        // Eat the do keyword.
+        let token = self.current_token();
+        match token {
+            Some(Token::Do(..)) => self.next_token(),
+            _ => unimplemented!(),
+        };
+
+        let do_expr = self.parse_expr();
+
+        // Eat the then keyword.
+        let token = self.current_token();
+        match token {
+            Some(Token::Then(..)) => self.next_token(),
+            _ => unimplemented!(),
+        };
+
+        let then_expr = self.parse_expr();
+        DoExpr::new(do_expr, then_expr)
+    }
+
+    pub fn parse_lambda_expr(&mut self) -> LambdaExpr {
+
+        // Eat the left pipe.
+        let token = self.current_token();
+        match token {
+            Some(Token::Pipe(..)) => self.next_token(),
+            _ => unimplemented!(),
+        };
+
+        // Parse the formals, which are separated by commas.
+        let mut formals = FunctionFormals::new();
+        loop {
+            if let Some(Token::Pipe(..)) = self.current_token() {
+                break;
+            }
+            formals.push(self.parse_function_formal());
+            match self.current_token() {
+                Some(Token::Comma(..)) => self.next_token(),
+                _ => break,
+            }
+        }
+
+        // Eat the right pipe, and the arrow.
+        let token = self.current_token();
+        match token {
+            Some(Token::Pipe(..)) => self.next_token(),
+            _ => unimplemented!(),
+        };
+        let token = self.current_token();
+        match token {
+            Some(Token::LambdaR(..)) => self.next_token(),
+            _ => unimplemented!(),
+        };
+
+        // The body extends as far to the right as possible.
+        let body = self.parse_expr();
+        LambdaExpr::new(formals, body)
+    }
+
+    pub fn parse_function_formal(&mut self) -> FunctionFormal {
+        let token = self.current_token();
+        let identifier = match token {
+            Some(Token::Ident(identifier, ..)) => {
+                self.next_token();
+                identifier
+            }
+            _ => unimplemented!(),
+        };
+        let ty = self.parse_type();
+        FunctionFormal::new(false, identifier, ty)
+    }
+
+    pub fn parse_type(&mut self) -> Type {
+        let token = self.current_token();
+        match token {
+            Some(Token::Func(..)) => {
+                self.next_token();
+
+                // Eat the left parenthesis.
+                match self.current_token() {
+                    Some(Token::ParenL(..)) => self.next_token(),
+                    _ => unimplemented!(),
+                };
+
+                // Parse the types of the formals, which are separated by
+                // commas.
+                let mut formals = Types::new();
+                loop {
+                    if let Some(Token::ParenR(..)) = self.current_token() {
+                        break;
+                    }
+                    formals.push(self.parse_type());
+                    match self.current_token() {
+                        Some(Token::Comma(..)) => self.next_token(),
+                        _ => break,
+                    }
+                }
+
+                // Eat the right parenthesis.
+                match self.current_token() {
+                    Some(Token::ParenR(..)) => self.next_token(),
+                    _ => unimplemented!(),
+                };
+
+                let ret = self.parse_type();
+                Type::Lambda(LambdaType::new(formals, ret).into())
+            }
+            Some(Token::Ident(identifier, ..)) => {
+                self.next_token();
+                UnresolvedType::new(identifier, Types::new()).into()
+            }
+            Some(Token::Mut(..)) => {
+                self.next_token();
+                match self.current_token() {
+                    Some(Token::Ref(..)) => self.next_token(),
+                    _ => unimplemented!(),
+                };
+                Type::RefMut(self.parse_type().into())
+            }
+            Some(Token::Ref(..)) => {
+                self.next_token();
+                Type::Ref(self.parse_type().into())
+            }
+            _ => unimplemented!(),
+        }
+    }
+
+    pub fn parse_list_expr(&mut self) -> ListExpr {
+
+        // Eat the left bracket.
+        let token = self.current_token();
+        match token {
+            Some(Token::BracketL(..)) => self.next_token(),
+            _ => unimplemented!(),
+        };
+
+        // Parse the items, which are separated by commas.
+        let mut items = Exprs::new();
+        loop {
+            if let Some(Token::BracketR(..)) = self.current_token() {
+                break;
+            }
+            items.push(self.parse_expr());
+            match self.current_token() {
+                Some(Token::Comma(..)) => self.next_token(),
+                _ => break,
+            }
+        }
+
+        // Eat the right bracket.
+        let token = self.current_token();
+        match token {
+            Some(Token::BracketR(..)) => self.next_token(),
+            _ => unimplemented!(),
+        };
+
+        ListExpr::new(items)
+    }
+
     //
     pub fn parse_literal_bool_expr(&mut self) -> LiteralExpr {
         let token = self.current_token();
@@ -202,6 +422,16 @@ impl Parser {
         }
     }
 
+    //
+    pub fn parse_literal_nil_expr(&mut self) -> LiteralExpr {
+        let token = self.current_token();
+        self.next_token();
+        match token {
+            Some(Token::Nil(span, ..)) => LiteralExpr::Nil(span),
+            _ => unimplemented!(),
+        }
+    }
+
     //
     pub fn parse_literal_str_expr(&mut self) -> LiteralExpr {
         let token = self.current_token();
@@ -130,4 +130,198 @@ fn parse_binary_operator_expr_with_precedence() {
             ),
         ).into()
     );
-}
\ No newline at end of file
+}
+#[test]
+fn parse_optional_operator_expr() {
+    assert_eq!(
+        Parser::new(vec![
+            Token::Int(1, Span::new("", 1, 1, 1, 1)),
+            Token::Unwrap(Span::new("", 1, 2, 1, 2)),
+            Token::QuestionQuestion(Span::new("", 1, 3, 1, 4)),
+            Token::Int(2, Span::new("", 1, 5, 1, 5)),
+            Token::Add(Span::new("", 1, 6, 1, 6)),
+            Token::Nil(Span::new("", 1, 7, 1, 9)),
+        ]).parse_expr(),
+        BinaryOperatorExpr::new(
+            Operator::Coalesce,
+            SuffixOperatorExpr::new(
+                Operator::Unwrap,
+                LiteralExpr::Int(1, Span::new("", 1, 1, 1, 1)),
+            ),
+            BinaryOperatorExpr::new(
+                Operator::Add,
+                LiteralExpr::Int(2, Span::new("", 1, 5, 1, 5)),
+                LiteralExpr::Nil(Span::new("", 1, 7, 1, 9)),
+            ),
+        ).into()
+    );
+}
+
+#[test]
+fn parse_tuple_expr() {
+    assert_eq!(
+        Parser::new(vec![
+            Token::ParenL(Span::new("", 1, 1, 1, 1)),
+            Token::Int(1, Span::new("", 1, 2, 1, 2)),
+            Token::Comma(Span::new("", 1, 3, 1, 3)),
+            Token::Int(2, Span::new("", 1, 5, 1, 5)),
+            Token::Add(Span::new("", 1, 6, 1, 6)),
+            Token::Int(3, Span::new("", 1, 7, 1, 7)),
+            Token::ParenR(Span::new("", 1, 8, 1, 8)),
+        ]).parse_expr(),
+        TupleExpr::new(vec![
+            LiteralExpr::Int(1, Span::new("", 1, 2, 1, 2)).into(),
+            BinaryOperatorExpr::new(
+                Operator::Add,
+                LiteralExpr::Int(2, Span::new("", 1, 5, 1, 5)),
+                LiteralExpr::Int(3, Span::new("", 1, 7, 1, 7)),
+            ).into(),
+        ]).into()
+    );
+}
+
+#[test]
+fn parse_list_expr() {
+    assert_eq!(
+        Parser::new(vec![
+            Token::BracketL(Span::new("", 1, 1, 1, 1)),
+            Token::Int(1, Span::new("", 1, 2, 1, 2)),
+            Token::Comma(Span::new("", 1, 3, 1, 3)),
+            Token::Int(2, Span::new("", 1, 5, 1, 5)),
+            Token::BracketR(Span::new("", 1, 6, 1, 6)),
+        ]).parse_expr(),
+        ListExpr::new(vec![
+            LiteralExpr::Int(1, Span::new("", 1, 2, 1, 2)).into(),
+            LiteralExpr::Int(2, Span::new("", 1, 5, 1, 5)).into(),
+        ]).into()
+    );
+    assert_eq!(
+        Parser::new(vec![
+            Token::Int(1, Span::new("", 1, 1, 1, 1)),
+            Token::DotDot(Span::new("", 1, 3, 1, 4)),
+            Token::Int(2, Span::new("", 1, 6, 1, 6)),
+            Token::Add(Span::new("", 1, 8, 1, 8)),
+            Token::Int(3, Span::new("", 1, 10, 1, 10)),
+        ]).parse_expr(),
+        BinaryOperatorExpr::new(
+            Operator::Range,
+            LiteralExpr::Int(1, Span::new("", 1, 1, 1, 1)),
+            BinaryOperatorExpr::new(
+                Operator::Add,
+                LiteralExpr::Int(2, Span::new("", 1, 6, 1, 6)),
+                LiteralExpr::Int(3, Span::new("", 1, 10, 1, 10)),
+            ),
+        ).into()
+    );
+}
+
+#[test]
+fn parse_channel_expr() {
+    assert_eq!(
+        Parser::new(vec![
+            Token::PushPop(Span::new("", 1, 1, 1, 2)),
+            Token::ParenL(Span::new("", 1, 3, 1, 3)),
+            Token::DotDot(Span::new("", 1, 4, 1, 5)),
+            Token::ParenR(Span::new("", 1, 6, 1, 6)),
+        ]).parse_expr(),
+        PrefixOperatorExpr::new(
+            Operator::PushPop,
+            ChannelExpr::new(None, VoidExpr::new(Span::new("", 1, 4, 1, 5))),
+        ).into()
+    );
+    assert_eq!(
+        Parser::new(vec![
+            Token::ParenL(Span::new("", 1, 1, 1, 1)),
+            Token::DotDot(Span::new("", 1, 2, 1, 3)),
+            Token::ParenR(Span::new("", 1, 4, 1, 4)),
+            Token::PushPop(Span::new("", 1, 6, 1, 7)),
+            Token::Int(1, Span::new("", 1, 9, 1, 9)),
+            Token::Add(Span::new("", 1, 11, 1, 11)),
+            Token::Int(2, Span::new("", 1, 13, 1, 13)),
+        ]).parse_expr(),
+        BinaryOperatorExpr::new(
+            Operator::PushPop,
+            ChannelExpr::new(None, VoidExpr::new(Span::new("", 1, 2, 1, 3))),
+            BinaryOperatorExpr::new(
+                Operator::Add,
+                LiteralExpr::Int(1, Span::new("", 1, 9, 1, 9)),
+                LiteralExpr::Int(2, Span::new("", 1, 13, 1, 13)),
+            ),
+        ).into()
+    );
+    assert_eq!(
+        Parser::new(vec![
+            Token::ParenL(Span::new("", 1, 1, 1, 1)),
+            Token::DotDot(Span::new("", 1, 2, 1, 3)),
+            Token::Int(16, Span::new("", 1, 4, 1, 5)),
+            Token::ParenR(Span::new("", 1, 6, 1, 6)),
+        ]).parse_expr(),
+        ChannelExpr::new(None, LiteralExpr::Int(16, Span::new("", 1, 4, 1, 5))).into()
+    );
+}
+
+#[test]
+fn parse_do_expr() {
+    assert_eq!(
+        Parser::new(vec![
+            Token::Do(Span::new("", 1, 1, 1, 2)),
+            Token::Int(1, Span::new("", 1, 4, 1, 4)),
+            Token::Add(Span::new("", 1, 6, 1, 6)),
+            Token::Int(2, Span::new("", 1, 8, 1, 8)),
+            Token::Then(Span::new("", 1, 10, 1, 13)),
+            Token::Int(3, Span::new("", 1, 15, 1, 15)),
+        ]).parse_expr(),
+        DoExpr::new(
+            BinaryOperatorExpr::new(
+                Operator::Add,
+                LiteralExpr::Int(1, Span::new("", 1, 4, 1, 4)),
+                LiteralExpr::Int(2, Span::new("", 1, 8, 1, 8)),
+            ),
+            LiteralExpr::Int(3, Span::new("", 1, 15, 1, 15)),
+        ).into()
+    );
+}
+
+#[test]
+fn parse_lambda_expr() {
+    let expr = Parser::new(vec![
+        Token::Pipe(Span::new("", 1, 1, 1, 1)),
+        Token::Ident("x".to_string(), Span::new("", 1, 2, 1, 2)),
+        Token::Ident("i64".to_string(), Span::new("", 1, 4, 1, 6)),
+        Token::Comma(Span::new("", 1, 7, 1, 7)),
+        Token::Ident("f".to_string(), Span::new("", 1, 9, 1, 9)),
+        Token::Func(Span::new("", 1, 11, 1, 12)),
+        Token::ParenL(Span::new("", 1, 13, 1, 13)),
+        Token::Ident("i64".to_string(), Span::new("", 1, 14, 1, 16)),
+        Token::ParenR(Span::new("", 1, 17, 1, 17)),
+        Token::Ident("i64".to_string(), Span::new("", 1, 19, 1, 21)),
+        Token::Pipe(Span::new("", 1, 22, 1, 22)),
+        Token::LambdaR(Span::new("", 1, 24, 1, 25)),
+        Token::Int(1, Span::new("", 1, 27, 1, 27)),
+        Token::Add(Span::new("", 1, 29, 1, 29)),
+        Token::Int(2, Span::new("", 1, 31, 1, 31)),
+    ]).parse_expr();
+
+    // identifiers are unique, so the formals are compared by name
+    let lambda_expr = match expr {
+        Expr::Lambda(lambda_expr) => lambda_expr,
+        _ => panic!("expected a lambda"),
+    };
+    let names = lambda_expr.formals
+        .iter()
+        .map(|formal| formal.identifier.name.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["x".to_string(), "f".to_string()]);
+    match lambda_expr.formals[1].ty {
+        Type::Lambda(ref lambda_type) => assert_eq!(lambda_type.formals.len(), 1),
+        _ => panic!("expected a lambda type"),
+    }
+    assert_eq!(
+        lambda_expr.body,
+        BinaryOperatorExpr::new(
+            Operator::Add,
+            LiteralExpr::Int(1, Span::new("", 1, 27, 1, 27)),
+            LiteralExpr::Int(2, Span::new("", 1, 31, 1, 31)),
+        ).into()
+    );
+}
@@ -0,0 +1,1369 @@
+//! # Type Checker
+//!
+//! The type checker assigns a `Type` to every expression in an AIR and checks
+//! that types agree wherever they meet: call arguments against the formals of
+//! the `LambdaType` being called, `let` definitions against their variable,
+//! and function bodies against the declared return type. Only an AIR that has
+//! been checked without errors is passed on to the normaliser.
+//!
+//! Types are inferred locally, within the body of each function. Every
+//! unannotated `let` variable and every integer or float literal is given an
+//! `InferType`, which is solved by unification. Integer literals that are not
+//! constrained by their usage default to `i64`, and float literals default to
+//! `f64`. Once a function has been checked, applying the solution rewrites
+//! its AIR so that no `InferType` remains.
+//!
+//! The type parameters of a generic function are opaque within its body. At
+//! every use of a generic function its type parameters are instantiated with
+//! fresh `InferType`s, and the solution is recorded as the type arguments of
+//! the `ItemExpr` so that the normaliser can monomorphise the function.
+//!
+//! A value of type `T` can be used wherever a `T?` is expected. Applying the
+//! solution wraps every such value in an `OptionalExpr`, so that optionals are
+//! explicit by the time they reach the normaliser.
+//!
+//! A list is indexed, and sliced, by `i64`s, which is also the type of its
+//! length. A `for` iterates over the elements of a list, or a range, or over
+//! the values received from a channel until the channel is closed.
+//!
+//! A `match` must be exhaustive: every variant of the enum that is matched
+//! must be covered by one of its arms, or by an arm that matches anything.
+//!
+//! Like the arms of a `match`, the guards of a `select`, and its `else` body,
+//! must all have the same type.
+//!
+//! A `break` or a `continue` must be within a `for`. The iterations of a
+//! `forall`, and the bodies of processes and lambdas, are not part of any
+//! enclosing loop.
+
+#[cfg(test)]
+pub mod mod_test;
+
+use super::air::*;
+use super::identifier::{Identifier, Identify, Name, Symbolise};
+use super::lexer::Span;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::mem;
+
+pub struct TypeErr {
+    span: Option<Span>,
+    message: String,
+}
+
+impl TypeErr {
+    pub fn new(span: Option<Span>, message: String) -> TypeErr {
+        TypeErr {
+            span: span,
+            message: message,
+        }
+    }
+
+    pub fn span(&self) -> Option<&Span> {
+        self.span.as_ref()
+    }
+
+    pub fn message(&self) -> &String {
+        &self.message
+    }
+}
+
+impl fmt::Display for TypeErr {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.span {
+            Some(ref span) => write!(formatter, "{}: {}", span.begin(), self.message),
+            None => write!(formatter, "{}", self.message),
+        }
+    }
+}
+
+pub struct TypeChecker {
+    context: Context,
+    types: HashMap<Identifier, Type>,
+    substitution: HashMap<Identifier, Type>,
+    infer_types: Vec<(InferType, Identifier)>,
+    type_args: HashMap<Identifier, Types>,
+    wraps: HashSet<Identifier>,
+    loops: usize,
+    errors: Vec<TypeErr>,
+}
+
+impl TypeChecker {
+    pub fn new(context: Context) -> TypeChecker {
+        TypeChecker {
+            context: context,
+            types: HashMap::new(),
+            substitution: HashMap::new(),
+            infer_types: Vec::new(),
+            type_args: HashMap::new(),
+            wraps: HashSet::new(),
+            loops: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn check_module(&mut self, module: &Module) {
+        for function in module.function_table.values() {
+            self.check_function(function);
+        }
+        for module in module.module_table.values() {
+            self.check_module(module);
+        }
+    }
+
+    pub fn check_function(&mut self, function: &Function) {
+        // inference is local to the function body, so the types inferred in
+        // an enclosing function are put aside until this one is checked
+        let infer_types = mem::replace(&mut self.infer_types, Vec::new());
+        let loops = mem::replace(&mut self.loops, 0);
+        if let Some(ref body) = function.body {
+            let ty = self.check_expr(body);
+            if !self.coerce(&body.identify(), &function.ret, &ty) {
+                let message = format!("mismatched return type in function `{}`: expected `{}`, found `{}`",
+                                      function.symbolise().name(),
+                                      self.resolve(&function.ret),
+                                      self.resolve(&ty));
+                self.error(&body.identify(), message);
+            }
+        }
+        self.default_infer_types();
+        self.infer_types = infer_types;
+        self.loops = loops;
+    }
+
+    pub fn check_assign_expr(&mut self, assign_expr: &AssignExpr) -> Type {
+        match assign_expr.lhs {
+            Expr::Deref(..) | Expr::Variable(..) => (),
+            _ => {
+                let message = "invalid left-hand side of assignment".to_string();
+                self.error(&assign_expr.lhs.identify(), message);
+            }
+        }
+        let lhs_ty = self.check_expr(&assign_expr.lhs);
+        let rhs_ty = self.check_expr(&assign_expr.rhs);
+        self.expect_type(&assign_expr.rhs.identify(), &lhs_ty, &rhs_ty);
+        PrimitiveType::Void.into()
+    }
+
+    pub fn check_block_expr(&mut self, block_expr: &BlockExpr) -> Type {
+        for function in block_expr.function_table.values() {
+            self.check_function(function);
+        }
+        for module in block_expr.module_table.values() {
+            self.check_module(module);
+        }
+        for expr in block_expr.body.iter() {
+            self.check_expr(expr);
+        }
+        self.check_expr(&block_expr.ret)
+    }
+
+    pub fn check_break_expr(&mut self, break_expr: &BreakExpr) -> Type {
+        if self.loops == 0 {
+            self.error(&break_expr.identify(), "`break` outside of a loop".to_string());
+        }
+        PrimitiveType::Void.into()
+    }
+
+    pub fn check_call_expr(&mut self, call_expr: &CallExpr) -> Type {
+        let target_ty = self.check_expr(&call_expr.target);
+        let argument_tys = call_expr.arguments
+            .iter()
+            .map(|argument| self.check_expr(argument))
+            .collect::<Types>();
+        let target_ty = match self.resolve(&target_ty) {
+            Type::Infer(..) => {
+                let lambda_type = LambdaType::new(argument_tys.iter()
+                                                      .map(|_| Type::infer())
+                                                      .collect(),
+                                                  Type::infer());
+                self.unify(&target_ty, &lambda_type.clone().into());
+                lambda_type.into()
+            }
+            ty => ty,
+        };
+        match target_ty {
+            Type::Lambda(lambda_type) => {
+                if lambda_type.formals.len() != argument_tys.len() {
+                    let message = format!("expected {} arguments, found {}",
+                                          lambda_type.formals.len(),
+                                          argument_tys.len());
+                    self.error(&call_expr.identify(), message);
+                } else {
+                    for (i, argument) in call_expr.arguments.iter().enumerate() {
+                        self.expect_type(&argument.identify(),
+                                         &lambda_type.formals[i],
+                                         &argument_tys[i]);
+                    }
+                }
+                lambda_type.ret.clone()
+            }
+            ty => {
+                let message = format!("expected a function, found `{}`", ty);
+                self.error(&call_expr.target.identify(), message);
+                PrimitiveType::Void.into()
+            }
+        }
+    }
+
+    pub fn check_channel_expr(&mut self, channel_expr: &ChannelExpr) -> Type {
+        if let Type::Infer(ref infer_type) = channel_expr.ty {
+            self.infer_types.push((infer_type.as_ref().clone(), channel_expr.identify()));
+        }
+        if let Some(ref capacity) = channel_expr.capacity {
+            let capacity_ty = self.check_expr(capacity);
+            self.expect_type(&capacity.identify(), &PrimitiveType::I64.into(), &capacity_ty);
+        }
+        ChannelType::new(channel_expr.ty.clone()).into()
+    }
+
+    pub fn check_channel_op_expr(&mut self, channel_op_expr: &ChannelOpExpr) -> Type {
+        let channel = match channel_op_expr.op {
+            ChannelOp::Close(ref channel) |
+            ChannelOp::Recv(ref channel) |
+            ChannelOp::Send(ref channel, _) => channel,
+        };
+        let channel_ty = self.check_expr(channel);
+        let element_ty = self.check_channel(&channel.identify(), &channel_ty);
+        self.unify(&channel_op_expr.ty, &element_ty);
+        match channel_op_expr.op {
+            ChannelOp::Close(..) => PrimitiveType::Void.into(),
+            ChannelOp::Recv(..) => self.resolve(&channel_op_expr.ty),
+            ChannelOp::Send(_, ref value) => {
+                let value_ty = self.check_expr(value);
+                self.expect_type(&value.identify(), &channel_op_expr.ty, &value_ty);
+                PrimitiveType::Void.into()
+            }
+        }
+    }
+
+    pub fn check_coalesce_expr(&mut self, coalesce_expr: &CoalesceExpr) -> Type {
+        let lhs_ty = self.check_expr(&coalesce_expr.lhs);
+        let rhs_ty = self.check_expr(&coalesce_expr.rhs);
+        let inner_ty = self.check_optional(&coalesce_expr.lhs.identify(), &lhs_ty);
+        self.expect_type(&coalesce_expr.rhs.identify(), &inner_ty, &rhs_ty);
+        self.unify(&coalesce_expr.ty, &inner_ty);
+        self.resolve(&coalesce_expr.ty)
+    }
+
+    pub fn check_continue_expr(&mut self, continue_expr: &ContinueExpr) -> Type {
+        if self.loops == 0 {
+            self.error(&continue_expr.identify(), "`continue` outside of a loop".to_string());
+        }
+        PrimitiveType::Void.into()
+    }
+
+    /// The values of the expressions in a `do .. then ..` are never seen.
+    pub fn check_do_expr(&mut self, do_expr: &DoExpr) -> Type {
+        let loops = mem::replace(&mut self.loops, 0);
+        self.check_expr(&do_expr.do_expr);
+        self.check_expr(&do_expr.then_expr);
+        self.loops = loops;
+        PrimitiveType::Void.into()
+    }
+
+    pub fn check_deref_expr(&mut self, deref_expr: &DerefExpr) -> Type {
+        let inner_ty = self.check_expr(&deref_expr.inner);
+        match self.resolve(&inner_ty) {
+            Type::Infer(..) => {
+                let ty = Type::infer();
+                self.unify(&inner_ty, &RefType::new(ty.clone()).into());
+                ty
+            }
+            Type::Ptr(ty) => ty.inner.clone(),
+            Type::Ref(ty) => ty.inner.clone(),
+            ty => {
+                let message = format!("cannot dereference a value of type `{}`", ty);
+                self.error(&deref_expr.identify(), message);
+                ty
+            }
+        }
+    }
+
+    pub fn check_enum_expr(&mut self, enum_expr: &EnumExpr) -> Type {
+        let enum_type = match self.resolve(&enum_expr.ty) {
+            Type::Enum(enum_type) => enum_type,
+            ty => {
+                let message = format!("expected an enum type, found `{}`", ty);
+                self.error(&enum_expr.identify(), message);
+                return enum_expr.ty.clone();
+            }
+        };
+        let variant = match enum_type.variant(&enum_expr.variant) {
+            Some(variant) => variant.clone(),
+            None => {
+                let message = format!("enum `{}` has no variant named `{}`",
+                                      enum_type.symbol.name(),
+                                      enum_expr.variant.symbol.name());
+                self.error(&enum_expr.identify(), message);
+                return enum_expr.ty.clone();
+            }
+        };
+        let elements = EnumType::variant_elements(&variant);
+        for &(ref variable, ref expr) in enum_expr.elements.iter() {
+            let expr_ty = self.check_expr(expr);
+            match elements.iter().find(|element| element.symbol == variable.symbol) {
+                Some(element) => self.expect_type(&expr.identify(), &element.ty, &expr_ty),
+                None => {
+                    let message = format!("variant `{}` has no field named `{}`",
+                                          variant.symbol.name(),
+                                          variable.symbol.name());
+                    self.error(&expr.identify(), message);
+                }
+            }
+        }
+        for element in elements.iter() {
+            if !enum_expr.elements.iter().any(|&(ref variable, _)| variable.symbol == element.symbol) {
+                let message = format!("missing field `{}` in variant `{}`",
+                                      element.symbol.name(),
+                                      variant.symbol.name());
+                self.error(&enum_expr.identify(), message);
+            }
+        }
+        enum_expr.ty.clone()
+    }
+
+    pub fn check_for_expr(&mut self, for_expr: &ForExpr) -> Type {
+        let iterator_ty = self.check_expr(&for_expr.iterator);
+        self.unify(&for_expr.ty, &iterator_ty);
+        let element_ty = match self.resolve(&iterator_ty) {
+            Type::Channel(ty) => ty.element.clone(),
+            _ => self.check_list(&for_expr.iterator.identify(), &iterator_ty),
+        };
+        if for_expr.formals.len() != 1 {
+            let message = format!("expected 1 variable in `for`, found {}", for_expr.formals.len());
+            self.error(&for_expr.identify(), message);
+        }
+        for formal in for_expr.formals.iter() {
+            if let Type::Infer(ref infer_type) = formal.ty {
+                self.infer_types.push((infer_type.as_ref().clone(), for_expr.identify()));
+            }
+            self.expect_type(&for_expr.iterator.identify(), &formal.ty, &element_ty);
+        }
+        // every iteration of a `forall` is a process of its own
+        let loops = if for_expr.is_concurrent { 0 } else { self.loops + 1 };
+        let loops = mem::replace(&mut self.loops, loops);
+        self.check_block_expr(&for_expr.iteration);
+        self.loops = loops;
+        PrimitiveType::Void.into()
+    }
+
+    pub fn check_if_expr(&mut self, if_expr: &IfExpr) -> Type {
+        let condition_ty = self.check_expr(&if_expr.condition);
+        self.expect_type(&if_expr.condition.identify(),
+                         &PrimitiveType::Bool.into(),
+                         &condition_ty);
+        let then_ty = self.check_block_expr(&if_expr.then_block);
+        let else_ty = self.check_block_expr(&if_expr.else_block);
+        // either branch can be optional, in which case the other is wrapped
+        let is_optional = |ty: Type| if let Type::Optional(..) = ty { true } else { false };
+        if !is_optional(self.resolve(&then_ty)) && is_optional(self.resolve(&else_ty)) {
+            self.expect_type(&if_expr.then_block.identify(), &else_ty, &then_ty);
+            return else_ty;
+        }
+        self.expect_type(&if_expr.else_block.identify(), &then_ty, &else_ty);
+        then_ty
+    }
+
+    pub fn check_item_expr(&mut self, item_expr: &ItemExpr) -> Type {
+        match item_expr.item {
+            Item::Function(ref function) => {
+                if !function.is_generic() {
+                    return function.lambda_type().into();
+                }
+                let type_args = function.type_params
+                    .iter()
+                    .map(|_| self.fresh(InferKind::Any, &item_expr.identify()))
+                    .collect::<Types>();
+                let substitution = function.type_params
+                    .iter()
+                    .zip(type_args.iter())
+                    .filter_map(|(param, type_arg)| match *param {
+                        Type::Generic(ref param) => Some((param.identify(), type_arg.clone())),
+                        _ => None,
+                    })
+                    .collect::<Substitution>();
+                self.type_args.insert(item_expr.identify(), type_args);
+                Type::from(function.lambda_type()).substitute(&substitution)
+            }
+            Item::Variable(ref variable) => variable.ty.clone(),
+            Item::Module(ref module) => {
+                let message = format!("expected a value, found module `{}`",
+                                      module.symbolise().name());
+                self.error(&item_expr.identify(), message);
+                PrimitiveType::Void.into()
+            }
+            Item::Type(ref ty) => {
+                let message = format!("expected a value, found type `{}`", ty);
+                self.error(&item_expr.identify(), message);
+                PrimitiveType::Void.into()
+            }
+        }
+    }
+
+    pub fn check_lambda_expr(&mut self, lambda_expr: &LambdaExpr) -> Type {
+        for formal in lambda_expr.formals.iter() {
+            if let Type::Infer(ref infer_type) = formal.ty {
+                self.infer_types.push((infer_type.as_ref().clone(), lambda_expr.identify()));
+            }
+        }
+        let loops = mem::replace(&mut self.loops, 0);
+        let body_ty = self.check_expr(&lambda_expr.body);
+        self.loops = loops;
+        self.expect_type(&lambda_expr.body.identify(), &lambda_expr.ret, &body_ty);
+        lambda_expr.lambda_type().into()
+    }
+
+    pub fn check_let_expr(&mut self, let_expr: &LetExpr) -> Type {
+        if let Type::Infer(ref infer_type) = let_expr.variable.ty {
+            self.infer_types.push((infer_type.as_ref().clone(), let_expr.identify()));
+        }
+        let definition_ty = self.check_expr(&let_expr.definition);
+        self.expect_type(&let_expr.definition.identify(),
+                         &let_expr.variable.ty,
+                         &definition_ty);
+        PrimitiveType::Void.into()
+    }
+
+    pub fn check_let_tuple_expr(&mut self, let_tuple_expr: &LetTupleExpr) -> Type {
+        for variable in let_tuple_expr.variables.iter() {
+            if let Type::Infer(ref infer_type) = variable.ty {
+                self.infer_types.push((infer_type.as_ref().clone(), let_tuple_expr.identify()));
+            }
+        }
+        let definition_ty = self.check_expr(&let_tuple_expr.definition);
+        let tuple_type = match self.resolve(&definition_ty) {
+            Type::Infer(..) => {
+                let tuple_type = TupleType::new(let_tuple_expr.variables
+                    .iter()
+                    .map(|_| Type::infer())
+                    .collect());
+                self.unify(&definition_ty, &tuple_type.clone().into());
+                tuple_type
+            }
+            Type::Tuple(tuple_type) => *tuple_type,
+            ty => {
+                let message = format!("expected a tuple, found `{}`", ty);
+                self.error(&let_tuple_expr.definition.identify(), message);
+                return PrimitiveType::Void.into();
+            }
+        };
+        if tuple_type.elements.len() != let_tuple_expr.variables.len() {
+            let message = format!("expected a tuple of {} elements, found {}",
+                                  let_tuple_expr.variables.len(),
+                                  tuple_type.elements.len());
+            self.error(&let_tuple_expr.definition.identify(), message);
+            return PrimitiveType::Void.into();
+        }
+        for (variable, element) in let_tuple_expr.variables.iter().zip(tuple_type.elements.iter()) {
+            if !self.unify(&variable.ty, element) {
+                let message = format!("mismatched types: expected `{}`, found `{}`",
+                                      self.resolve(&variable.ty),
+                                      self.resolve(element));
+                self.error(&let_tuple_expr.definition.identify(), message);
+            }
+        }
+        PrimitiveType::Void.into()
+    }
+
+    pub fn check_list_expr(&mut self, list_expr: &ListExpr) -> Type {
+        if let Type::Infer(ref infer_type) = list_expr.ty {
+            self.infer_types.push((infer_type.as_ref().clone(), list_expr.identify()));
+        }
+        for element in list_expr.elements.iter() {
+            let element_ty = self.check_expr(element);
+            self.expect_type(&element.identify(), &list_expr.ty, &element_ty);
+        }
+        ListType::new(list_expr.ty.clone()).into()
+    }
+
+    pub fn check_list_op_expr(&mut self, list_op_expr: &ListOpExpr) -> Type {
+        let list = match list_op_expr.op {
+            ListOp::Index(ref list, _) |
+            ListOp::Len(ref list) |
+            ListOp::Pop(ref list) |
+            ListOp::Push(ref list, _) |
+            ListOp::Slice(ref list, _, _) => list,
+        };
+        let list_ty = self.check_expr(list);
+        let element_ty = self.check_list(&list.identify(), &list_ty);
+        self.unify(&list_op_expr.ty, &element_ty);
+        match list_op_expr.op {
+            ListOp::Index(_, ref index) => {
+                self.expect_index(index);
+                self.resolve(&list_op_expr.ty)
+            }
+            ListOp::Len(..) => PrimitiveType::I64.into(),
+            ListOp::Pop(..) => OptionalType::new(self.resolve(&list_op_expr.ty)).into(),
+            ListOp::Push(_, ref value) => {
+                let value_ty = self.check_expr(value);
+                self.expect_type(&value.identify(), &list_op_expr.ty, &value_ty);
+                PrimitiveType::Void.into()
+            }
+            ListOp::Slice(_, ref start, ref end) => {
+                self.expect_index(start);
+                self.expect_index(end);
+                ListType::new(self.resolve(&list_op_expr.ty)).into()
+            }
+        }
+    }
+
+    pub fn check_literal_expr(&mut self, literal_expr: &LiteralExpr) -> Type {
+        match literal_expr.literal {
+            Literal::Bool(..) => PrimitiveType::Bool.into(),
+            Literal::Char(..) => PrimitiveType::Char.into(),
+            Literal::F32(..) => PrimitiveType::F32.into(),
+            Literal::F64(..) => PrimitiveType::F64.into(),
+            Literal::Float(..) => self.fresh(InferKind::Float, &literal_expr.identify()),
+            Literal::I8(..) => PrimitiveType::I8.into(),
+            Literal::I16(..) => PrimitiveType::I16.into(),
+            Literal::I32(..) => PrimitiveType::I32.into(),
+            Literal::I64(..) => PrimitiveType::I64.into(),
+            Literal::Int(..) => self.fresh(InferKind::Int, &literal_expr.identify()),
+            Literal::Str(..) => PrimitiveType::Str.into(),
+            Literal::U8(..) => PrimitiveType::U8.into(),
+            Literal::U16(..) => PrimitiveType::U16.into(),
+            Literal::U32(..) => PrimitiveType::U32.into(),
+            Literal::U64(..) => PrimitiveType::U64.into(),
+            Literal::USize(..) => PrimitiveType::USize.into(),
+        }
+    }
+
+    pub fn check_match_expr(&mut self, match_expr: &MatchExpr) -> Type {
+        let subject_ty = self.check_expr(&match_expr.subject);
+        let enum_type = match self.resolve(&subject_ty) {
+            Type::Enum(enum_type) => enum_type,
+            ty => {
+                let message = format!("expected an enum, found `{}`", ty);
+                self.error(&match_expr.subject.identify(), message);
+                return PrimitiveType::Void.into();
+            }
+        };
+        let mut ty = None;
+        let mut covered = Vec::new();
+        let mut is_exhaustive = false;
+        for arm in match_expr.arms.iter() {
+            let mut is_unreachable = is_exhaustive;
+            for binding in arm.bindings.iter() {
+                if let Type::Infer(ref infer_type) = binding.ty {
+                    self.infer_types.push((infer_type.as_ref().clone(), arm.identify()));
+                }
+            }
+            match arm.variant {
+                Some(ref variant) => {
+                    match enum_type.variant(variant) {
+                        Some(variant) => {
+                            if covered.contains(&variant.symbol) {
+                                is_unreachable = true;
+                            }
+                            covered.push(variant.symbol.clone());
+                            let elements = EnumType::variant_elements(variant);
+                            if elements.len() != arm.bindings.len() {
+                                let message = format!("variant `{}` has {} fields, found {}",
+                                                      variant.symbol.name(),
+                                                      elements.len(),
+                                                      arm.bindings.len());
+                                self.error(&arm.identify(), message);
+                            } else {
+                                for (element, binding) in elements.iter().zip(arm.bindings.iter()) {
+                                    self.expect_type(&arm.identify(), &binding.ty, &element.ty);
+                                }
+                            }
+                        }
+                        None => {
+                            let message = format!("enum `{}` has no variant named `{}`",
+                                                  enum_type.symbol.name(),
+                                                  variant.symbol.name());
+                            self.error(&arm.identify(), message);
+                        }
+                    }
+                }
+                None => is_exhaustive = true,
+            }
+            if is_unreachable {
+                self.error(&arm.identify(), "unreachable pattern".to_string());
+            }
+            let body_ty = self.check_block_expr(&arm.body);
+            match ty {
+                Some(ref ty) => self.expect_type(&arm.body.identify(), ty, &body_ty),
+                None => ty = Some(body_ty),
+            }
+        }
+        let missing = enum_type.variants
+            .elements
+            .iter()
+            .filter(|variant| !covered.contains(&variant.symbol))
+            .map(|variant| format!("`{}`", variant.symbol.name()))
+            .collect::<Vec<_>>();
+        if !is_exhaustive && !missing.is_empty() {
+            let message = format!("non-exhaustive patterns: {} not covered", missing.join(", "));
+            self.error(&match_expr.identify(), message);
+        }
+        self.resolve(&ty.unwrap_or(PrimitiveType::Void.into()))
+    }
+
+    pub fn check_optional_expr(&mut self, optional_expr: &OptionalExpr) -> Type {
+        if let Type::Infer(ref infer_type) = optional_expr.ty {
+            self.infer_types.push((infer_type.as_ref().clone(), optional_expr.identify()));
+        }
+        if let Some(ref value) = optional_expr.value {
+            let value_ty = self.check_expr(value);
+            self.expect_type(&value.identify(), &optional_expr.ty, &value_ty);
+        }
+        OptionalType::new(optional_expr.ty.clone()).into()
+    }
+
+    /// The value of a process is never seen, so its body must be `void`.
+    pub fn check_process_expr(&mut self, process_expr: &ProcessExpr) -> Type {
+        let loops = mem::replace(&mut self.loops, 0);
+        let body_ty = self.check_block_expr(&process_expr.body);
+        self.loops = loops;
+        self.expect_type(&process_expr.body.identify(), &PrimitiveType::Void.into(), &body_ty);
+        PrimitiveType::Void.into()
+    }
+
+    pub fn check_range_expr(&mut self, range_expr: &RangeExpr) -> Type {
+        let ty = self.fresh(InferKind::Int, &range_expr.identify());
+        self.unify(&range_expr.ty, &ty);
+        let start_ty = self.check_expr(&range_expr.start);
+        self.expect_type(&range_expr.start.identify(), &range_expr.ty, &start_ty);
+        let end_ty = self.check_expr(&range_expr.end);
+        self.expect_type(&range_expr.end.identify(), &range_expr.ty, &end_ty);
+        ListType::new(range_expr.ty.clone()).into()
+    }
+
+    pub fn check_ref_expr(&mut self, ref_expr: &RefExpr) -> Type {
+        let inner_ty = self.check_expr(&ref_expr.inner);
+        if ref_expr.is_mut {
+            RefType::new_mut(inner_ty).into()
+        } else {
+            RefType::new(inner_ty).into()
+        }
+    }
+
+    pub fn check_select_expr(&mut self, select_expr: &SelectExpr) -> Type {
+        let mut ty = None;
+        for guard in select_expr.guards.iter() {
+            let channel = match guard.op {
+                SelectOp::Recv(ref channel, _) |
+                SelectOp::Send(ref channel, _) => channel,
+            };
+            let channel_ty = self.check_expr(channel);
+            let element_ty = self.check_channel(&channel.identify(), &channel_ty);
+            self.unify(&guard.ty, &element_ty);
+            match guard.op {
+                SelectOp::Recv(_, Some(ref variable)) => {
+                    if let Type::Infer(ref infer_type) = variable.ty {
+                        self.infer_types.push((infer_type.as_ref().clone(), guard.identify()));
+                    }
+                    self.expect_type(&guard.identify(), &variable.ty, &guard.ty);
+                }
+                SelectOp::Recv(_, None) => (),
+                SelectOp::Send(_, ref value) => {
+                    let value_ty = self.check_expr(value);
+                    self.expect_type(&value.identify(), &guard.ty, &value_ty);
+                }
+            }
+            let body_ty = self.check_block_expr(&guard.body);
+            match ty {
+                Some(ref ty) => self.expect_type(&guard.body.identify(), ty, &body_ty),
+                None => ty = Some(body_ty),
+            }
+        }
+        if let Some(ref else_body) = select_expr.else_body {
+            let else_ty = self.check_block_expr(else_body);
+            match ty {
+                Some(ref ty) => self.expect_type(&else_body.identify(), ty, &else_ty),
+                None => ty = Some(else_ty),
+            }
+        }
+        if select_expr.guards.is_empty() {
+            self.error(&select_expr.identify(), "expected at least 1 guard in `select`".to_string());
+        }
+        self.resolve(&ty.unwrap_or(PrimitiveType::Void.into()))
+    }
+
+    pub fn check_struct_expr(&mut self, struct_expr: &StructExpr) -> Type {
+        let struct_type = match self.resolve(&struct_expr.ty) {
+            Type::Struct(struct_type) => struct_type,
+            ty => {
+                let message = format!("expected a struct type, found `{}`", ty);
+                self.error(&struct_expr.identify(), message);
+                return struct_expr.ty.clone();
+            }
+        };
+        for &(ref variable, ref expr) in struct_expr.elements.iter() {
+            let expr_ty = self.check_expr(expr);
+            match struct_type.elements.iter().find(|element| element.symbol == variable.symbol) {
+                Some(element) => self.expect_type(&expr.identify(), &element.ty, &expr_ty),
+                None => {
+                    let message = format!("struct `{}` has no field named `{}`",
+                                          struct_type.symbol.name(),
+                                          variable.symbol.name());
+                    self.error(&expr.identify(), message);
+                }
+            }
+        }
+        for element in struct_type.elements.iter() {
+            if !struct_expr.elements.iter().any(|&(ref variable, _)| variable.symbol == element.symbol) {
+                let message = format!("missing field `{}` in struct `{}`",
+                                      element.symbol.name(),
+                                      struct_type.symbol.name());
+                self.error(&struct_expr.identify(), message);
+            }
+        }
+        struct_expr.ty.clone()
+    }
+
+    pub fn check_tuple_expr(&mut self, tuple_expr: &TupleExpr) -> Type {
+        TupleType::new(tuple_expr.elements.iter().map(|element| self.check_expr(element)).collect()).into()
+    }
+
+    pub fn check_unwrap_expr(&mut self, unwrap_expr: &UnwrapExpr) -> Type {
+        let inner_ty = self.check_expr(&unwrap_expr.inner);
+        let inner_ty = self.check_optional(&unwrap_expr.inner.identify(), &inner_ty);
+        self.unify(&unwrap_expr.ty, &inner_ty);
+        self.resolve(&unwrap_expr.ty)
+    }
+
+    pub fn check_variable_expr(&mut self, variable_expr: &VariableExpr) -> Type {
+        let parent = match variable_expr.parent.first() {
+            Some(parent) => parent,
+            None => return variable_expr.variable.ty.clone(),
+        };
+        // elements can be accessed through a reference to a struct
+        let parent_ty = self.check_expr(parent);
+        let parent_ty = match self.resolve(&parent_ty) {
+            Type::Ref(ty) => self.resolve(&ty.inner),
+            ty => ty,
+        };
+        let struct_type = match parent_ty {
+            Type::Struct(struct_type) => struct_type,
+            ty => {
+                let message = format!("no field `{}` on type `{}`",
+                                      variable_expr.variable.symbol.name(),
+                                      ty);
+                self.error(&variable_expr.identify(), message);
+                return variable_expr.variable.ty.clone();
+            }
+        };
+        match struct_type.elements.iter().find(|element| element.symbol == variable_expr.variable.symbol) {
+            Some(element) => element.ty.clone(),
+            None => {
+                let message = format!("struct `{}` has no field named `{}`",
+                                      struct_type.symbol.name(),
+                                      variable_expr.variable.symbol.name());
+                self.error(&variable_expr.identify(), message);
+                variable_expr.variable.ty.clone()
+            }
+        }
+    }
+
+    pub fn check_void_expr(&mut self, _: &VoidExpr) -> Type {
+        PrimitiveType::Void.into()
+    }
+
+    pub fn check_expr(&mut self, expr: &Expr) -> Type {
+        let ty = match *expr {
+            Expr::Assign(ref expr) => self.check_assign_expr(expr),
+            Expr::Block(ref expr) => self.check_block_expr(expr),
+            Expr::Break(ref expr) => self.check_break_expr(expr),
+            Expr::Call(ref expr) => self.check_call_expr(expr),
+            Expr::Channel(ref expr) => self.check_channel_expr(expr),
+            Expr::ChannelOp(ref expr) => self.check_channel_op_expr(expr),
+            Expr::Coalesce(ref expr) => self.check_coalesce_expr(expr),
+            Expr::Continue(ref expr) => self.check_continue_expr(expr),
+            Expr::Deref(ref expr) => self.check_deref_expr(expr),
+            Expr::Do(ref expr) => self.check_do_expr(expr),
+            Expr::Enum(ref expr) => self.check_enum_expr(expr),
+            Expr::For(ref expr) => self.check_for_expr(expr),
+            Expr::If(ref expr) => self.check_if_expr(expr),
+            Expr::Item(ref expr) => self.check_item_expr(expr),
+            Expr::Lambda(ref expr) => self.check_lambda_expr(expr),
+            Expr::Let(ref expr) => self.check_let_expr(expr),
+            Expr::LetTuple(ref expr) => self.check_let_tuple_expr(expr),
+            Expr::List(ref expr) => self.check_list_expr(expr),
+            Expr::ListOp(ref expr) => self.check_list_op_expr(expr),
+            Expr::Literal(ref expr) => self.check_literal_expr(expr),
+            Expr::Match(ref expr) => self.check_match_expr(expr),
+            Expr::Optional(ref expr) => self.check_optional_expr(expr),
+            Expr::Process(ref expr) => self.check_process_expr(expr),
+            Expr::Range(ref expr) => self.check_range_expr(expr),
+            Expr::Ref(ref expr) => self.check_ref_expr(expr),
+            Expr::Select(ref expr) => self.check_select_expr(expr),
+            Expr::Struct(ref expr) => self.check_struct_expr(expr),
+            Expr::Tuple(ref expr) => self.check_tuple_expr(expr),
+            Expr::Unwrap(ref expr) => self.check_unwrap_expr(expr),
+            Expr::Variable(ref expr) => self.check_variable_expr(expr),
+            Expr::Void(ref expr) => self.check_void_expr(expr),
+        };
+        self.types.insert(expr.identify(), ty.clone());
+        ty
+    }
+
+    /// Get the `Type` that was assigned to an expression.
+    ///
+    /// # Arguments
+    /// * `expr` - An expression that has been checked.
+    ///
+    /// # Return
+    /// The `Type` of the expression, or `None` if the expression has not
+    /// been checked.
+    pub fn type_of(&self, expr: &Expr) -> Option<Type> {
+        self.types.get(&expr.identify()).map(|ty| self.resolve(ty))
+    }
+
+    pub fn apply_module(&mut self, module: &mut Module) {
+        for function in module.function_table.values_mut() {
+            self.apply_function(function);
+        }
+        for module in module.module_table.values_mut() {
+            self.apply_module(module);
+        }
+    }
+
+    /// Rewrite a `Function` that has been checked, replacing every inferred
+    /// type with its solution and giving every integer and float literal the
+    /// width that was inferred for it.
+    ///
+    /// # Arguments
+    /// * `function` - A function that has been checked.
+    pub fn apply_function(&mut self, function: &mut Function) {
+        for formal in function.formals.iter_mut() {
+            self.apply_variable(formal);
+        }
+        function.ret = self.resolve(&function.ret);
+        if let Some(ref mut body) = function.body {
+            self.apply_expr(body);
+        }
+    }
+
+    pub fn apply_block_expr(&mut self, block_expr: &mut BlockExpr) {
+        for function in block_expr.function_table.values_mut() {
+            self.apply_function(function);
+        }
+        for module in block_expr.module_table.values_mut() {
+            self.apply_module(module);
+        }
+        for expr in block_expr.body.iter_mut() {
+            self.apply_expr(expr);
+        }
+        self.apply_expr(&mut block_expr.ret);
+        // a block is wrapped by wrapping the value that it returns
+        if self.wraps.contains(&block_expr.identify()) {
+            self.apply_wrap(&mut block_expr.ret);
+        }
+    }
+
+    pub fn apply_literal_expr(&mut self, literal_expr: &mut LiteralExpr) {
+        let identifier = literal_expr.identify();
+        let ty = match self.types.get(&identifier) {
+            Some(ty) => self.resolve(ty),
+            None => return,
+        };
+        let ty = match ty {
+            Type::Primitive(ty) => *ty,
+            _ => return,
+        };
+        let literal = match literal_expr.literal {
+            Literal::Float(value) => {
+                match ty {
+                    PrimitiveType::F32 => Literal::F32(value as f32),
+                    PrimitiveType::F64 => Literal::F64(value),
+                    _ => return,
+                }
+            }
+            Literal::Int(value) => {
+                let (min, max) = match ty {
+                    PrimitiveType::I8 => (i8::MIN as i128, i8::MAX as i128),
+                    PrimitiveType::I16 => (i16::MIN as i128, i16::MAX as i128),
+                    PrimitiveType::I32 => (i32::MIN as i128, i32::MAX as i128),
+                    PrimitiveType::I64 => (i64::MIN as i128, i64::MAX as i128),
+                    PrimitiveType::U8 => (0, u8::MAX as i128),
+                    PrimitiveType::U16 => (0, u16::MAX as i128),
+                    PrimitiveType::U32 => (0, u32::MAX as i128),
+                    PrimitiveType::U64 | PrimitiveType::USize => (0, u64::MAX as i128),
+                    _ => return,
+                };
+                if value < min || value > max {
+                    let message = format!("literal out of range for `{}`", ty);
+                    self.error(&identifier, message);
+                    return;
+                }
+                match ty {
+                    PrimitiveType::I8 => Literal::I8(value as i8),
+                    PrimitiveType::I16 => Literal::I16(value as i16),
+                    PrimitiveType::I32 => Literal::I32(value as i32),
+                    PrimitiveType::I64 => Literal::I64(value as i64),
+                    PrimitiveType::U8 => Literal::U8(value as u8),
+                    PrimitiveType::U16 => Literal::U16(value as u16),
+                    PrimitiveType::U32 => Literal::U32(value as u32),
+                    PrimitiveType::U64 => Literal::U64(value as u64),
+                    _ => Literal::USize(value as usize),
+                }
+            }
+            _ => return,
+        };
+        literal_expr.literal = literal;
+    }
+
+    pub fn apply_variable(&mut self, variable: &mut Variable) {
+        variable.ty = self.resolve(&variable.ty);
+    }
+
+    pub fn apply_expr(&mut self, expr: &mut Expr) {
+        match *expr {
+            Expr::Assign(ref mut expr) => {
+                self.apply_expr(&mut expr.lhs);
+                self.apply_expr(&mut expr.rhs);
+            }
+            Expr::Block(ref mut expr) => self.apply_block_expr(expr),
+            Expr::Break(..) => (),
+            Expr::Call(ref mut expr) => {
+                self.apply_expr(&mut expr.target);
+                for argument in expr.arguments.iter_mut() {
+                    self.apply_expr(argument);
+                }
+            }
+            Expr::Channel(ref mut expr) => {
+                if let Some(ref mut capacity) = expr.capacity {
+                    self.apply_expr(capacity);
+                }
+                expr.ty = self.resolve(&expr.ty);
+            }
+            Expr::ChannelOp(ref mut expr) => {
+                match expr.op {
+                    ChannelOp::Close(ref mut channel) |
+                    ChannelOp::Recv(ref mut channel) => self.apply_expr(channel),
+                    ChannelOp::Send(ref mut channel, ref mut value) => {
+                        self.apply_expr(channel);
+                        self.apply_expr(value);
+                    }
+                }
+                expr.ty = self.resolve(&expr.ty);
+            }
+            Expr::Coalesce(ref mut expr) => {
+                self.apply_expr(&mut expr.lhs);
+                self.apply_expr(&mut expr.rhs);
+                expr.ty = self.resolve(&expr.ty);
+            }
+            Expr::Continue(..) => (),
+            Expr::Deref(ref mut expr) => self.apply_expr(&mut expr.inner),
+            Expr::Do(ref mut expr) => {
+                self.apply_expr(&mut expr.do_expr);
+                self.apply_expr(&mut expr.then_expr);
+            }
+            Expr::Enum(ref mut expr) => {
+                for &mut (ref mut variable, ref mut expr) in expr.elements.iter_mut() {
+                    self.apply_variable(variable);
+                    self.apply_expr(expr);
+                }
+            }
+            Expr::For(ref mut expr) => {
+                for formal in expr.formals.iter_mut() {
+                    self.apply_variable(formal);
+                }
+                self.apply_expr(&mut expr.iterator);
+                self.apply_block_expr(&mut expr.iteration);
+                expr.ty = self.resolve(&expr.ty);
+            }
+            Expr::If(ref mut expr) => {
+                self.apply_expr(&mut expr.condition);
+                self.apply_block_expr(&mut expr.then_block);
+                self.apply_block_expr(&mut expr.else_block);
+            }
+            Expr::Item(ref mut expr) => {
+                if let Item::Variable(ref mut variable) = expr.item {
+                    self.apply_variable(variable);
+                }
+                if let Some(type_args) = self.type_args.get(&expr.identify()) {
+                    expr.type_args = type_args.iter().map(|type_arg| self.resolve(type_arg)).collect();
+                }
+            }
+            Expr::Lambda(ref mut expr) => {
+                for formal in expr.formals.iter_mut() {
+                    self.apply_variable(formal);
+                }
+                self.apply_expr(&mut expr.body);
+                expr.ret = self.resolve(&expr.ret);
+            }
+            Expr::Let(ref mut expr) => {
+                self.apply_variable(&mut expr.variable);
+                self.apply_expr(&mut expr.definition);
+            }
+            Expr::LetTuple(ref mut expr) => {
+                for variable in expr.variables.iter_mut() {
+                    self.apply_variable(variable);
+                }
+                self.apply_expr(&mut expr.definition);
+            }
+            Expr::List(ref mut expr) => {
+                for element in expr.elements.iter_mut() {
+                    self.apply_expr(element);
+                }
+                expr.ty = self.resolve(&expr.ty);
+            }
+            Expr::ListOp(ref mut expr) => {
+                match expr.op {
+                    ListOp::Index(ref mut list, ref mut operand) |
+                    ListOp::Push(ref mut list, ref mut operand) => {
+                        self.apply_expr(list);
+                        self.apply_expr(operand);
+                    }
+                    ListOp::Len(ref mut list) |
+                    ListOp::Pop(ref mut list) => self.apply_expr(list),
+                    ListOp::Slice(ref mut list, ref mut start, ref mut end) => {
+                        self.apply_expr(list);
+                        self.apply_expr(start);
+                        self.apply_expr(end);
+                    }
+                }
+                expr.ty = self.resolve(&expr.ty);
+            }
+            Expr::Literal(ref mut expr) => self.apply_literal_expr(expr),
+            Expr::Match(ref mut expr) => {
+                self.apply_expr(&mut expr.subject);
+                for arm in expr.arms.iter_mut() {
+                    for binding in arm.bindings.iter_mut() {
+                        self.apply_variable(binding);
+                    }
+                    self.apply_block_expr(&mut arm.body);
+                }
+            }
+            Expr::Optional(ref mut expr) => {
+                for value in expr.value.iter_mut() {
+                    self.apply_expr(value);
+                }
+                expr.ty = self.resolve(&expr.ty);
+            }
+            Expr::Process(ref mut expr) => self.apply_block_expr(&mut expr.body),
+            Expr::Range(ref mut expr) => {
+                self.apply_expr(&mut expr.start);
+                self.apply_expr(&mut expr.end);
+                expr.ty = self.resolve(&expr.ty);
+            }
+            Expr::Ref(ref mut expr) => self.apply_expr(&mut expr.inner),
+            Expr::Select(ref mut expr) => {
+                for guard in expr.guards.iter_mut() {
+                    match guard.op {
+                        SelectOp::Recv(ref mut channel, ref mut variable) => {
+                            self.apply_expr(channel);
+                            if let Some(ref mut variable) = *variable {
+                                self.apply_variable(variable);
+                            }
+                        }
+                        SelectOp::Send(ref mut channel, ref mut value) => {
+                            self.apply_expr(channel);
+                            self.apply_expr(value);
+                        }
+                    }
+                    self.apply_block_expr(&mut guard.body);
+                    guard.ty = self.resolve(&guard.ty);
+                }
+                if let Some(ref mut else_body) = expr.else_body {
+                    self.apply_block_expr(else_body);
+                }
+            }
+            Expr::Struct(ref mut expr) => {
+                for &mut (ref mut variable, ref mut expr) in expr.elements.iter_mut() {
+                    self.apply_variable(variable);
+                    self.apply_expr(expr);
+                }
+            }
+            Expr::Tuple(ref mut expr) => {
+                for element in expr.elements.iter_mut() {
+                    self.apply_expr(element);
+                }
+            }
+            Expr::Unwrap(ref mut expr) => {
+                self.apply_expr(&mut expr.inner);
+                expr.ty = self.resolve(&expr.ty);
+            }
+            Expr::Variable(ref mut expr) => {
+                self.apply_variable(&mut expr.variable);
+                for parent in expr.parent.iter_mut() {
+                    self.apply_expr(parent);
+                }
+            }
+            Expr::Void(..) => (),
+        }
+        match *expr {
+            Expr::Block(..) => (),
+            _ => {
+                if self.wraps.contains(&expr.identify()) {
+                    self.apply_wrap(expr);
+                }
+            }
+        }
+    }
+
+    /// Wrap an expression that is used as an optional in an `OptionalExpr`.
+    fn apply_wrap(&mut self, expr: &mut Expr) {
+        let ty = match self.types.get(&expr.identify()) {
+            Some(ty) => self.resolve(ty),
+            None => return,
+        };
+        let value = mem::replace(expr, VoidExpr::new(Identifier::id()).into());
+        let mut optional_expr = OptionalExpr::new(Identifier::id(), value);
+        optional_expr.ty = ty;
+        *expr = optional_expr.into();
+    }
+
+    pub fn errors(&self) -> &Vec<TypeErr> {
+        &self.errors
+    }
+
+    pub fn print_errors(&self) {
+        for err in self.errors.iter() {
+            println!("{}", err);
+        }
+    }
+
+    /// Check that a `Type` is a channel.
+    ///
+    /// # Return
+    /// The `Type` of the values in the channel.
+    fn check_channel(&mut self, identifier: &Identifier, ty: &Type) -> Type {
+        match self.resolve(ty) {
+            Type::Infer(..) => {
+                let element_ty = Type::infer();
+                if self.unify(ty, &ChannelType::new(element_ty.clone()).into()) {
+                    return element_ty;
+                }
+            }
+            Type::Channel(ty) => return ty.element.clone(),
+            _ => (),
+        }
+        let ty = self.resolve(ty);
+        let message = format!("expected a channel, found `{}`", ty);
+        self.error(identifier, message);
+        ty
+    }
+
+    /// Check that a `Type` is a list.
+    ///
+    /// # Return
+    /// The `Type` of the elements of the list.
+    fn check_list(&mut self, identifier: &Identifier, ty: &Type) -> Type {
+        match self.resolve(ty) {
+            Type::Infer(..) => {
+                let element_ty = Type::infer();
+                if self.unify(ty, &ListType::new(element_ty.clone()).into()) {
+                    return element_ty;
+                }
+            }
+            Type::List(ty) => return ty.element.clone(),
+            _ => (),
+        }
+        let ty = self.resolve(ty);
+        let message = format!("expected a list, found `{}`", ty);
+        self.error(identifier, message);
+        ty
+    }
+
+    /// Check that an expression can be used to index a list.
+    fn expect_index(&mut self, index: &Expr) {
+        let index_ty = self.check_expr(index);
+        self.expect_type(&index.identify(), &PrimitiveType::I64.into(), &index_ty);
+    }
+
+    /// Check that a `Type` is optional.
+    ///
+    /// # Return
+    /// The `Type` inside of the optional.
+    fn check_optional(&mut self, identifier: &Identifier, ty: &Type) -> Type {
+        match self.resolve(ty) {
+            Type::Infer(..) => {
+                let inner_ty = Type::infer();
+                self.unify(ty, &OptionalType::new(inner_ty.clone()).into());
+                inner_ty
+            }
+            Type::Optional(ty) => ty.inner.clone(),
+            ty => {
+                let message = format!("expected an optional, found `{}`", ty);
+                self.error(identifier, message);
+                ty
+            }
+        }
+    }
+
+    /// Unify the `Type` of an expression with the `Type` that is expected of
+    /// it. If a `T?` is expected and a `T` is found, then the expression is
+    /// wrapped when the solution is applied.
+    ///
+    /// # Return
+    /// True if the expression can be used as the expected type, otherwise
+    /// false.
+    fn coerce(&mut self, identifier: &Identifier, expected: &Type, found: &Type) -> bool {
+        if self.unify(expected, found) {
+            return true;
+        }
+        match (self.resolve(expected), self.resolve(found)) {
+            // a `mut ref` can be used wherever a `ref` is expected
+            (Type::Ref(ref expected), Type::Ref(ref found)) if !expected.is_mut && found.is_mut => {
+                self.unify(&expected.inner, &found.inner)
+            }
+            (Type::Optional(..), Type::Optional(..)) => false,
+            (Type::Optional(expected), _) => {
+                if self.unify(&expected.inner, found) {
+                    self.wraps.insert(identifier.clone());
+                    true
+                } else {
+                    false
+                }
+            }
+            _ => false,
+        }
+    }
+
+    fn expect_type(&mut self, identifier: &Identifier, expected: &Type, found: &Type) {
+        if !self.coerce(identifier, expected, found) {
+            let message = format!("mismatched types: expected `{}`, found `{}`",
+                                  self.resolve(expected),
+                                  self.resolve(found));
+            self.error(identifier, message);
+        }
+    }
+
+    /// Create a new `InferType` that will be solved by the end of the
+    /// function that is being checked.
+    fn fresh(&mut self, kind: InferKind, identifier: &Identifier) -> Type {
+        let infer_type = InferType::new(kind);
+        self.infer_types.push((infer_type.clone(), identifier.clone()));
+        infer_type.into()
+    }
+
+    /// Solve every `InferType` that was not constrained by its usage. Integer
+    /// and float types are given a default, and everything else is an error.
+    fn default_infer_types(&mut self) {
+        let infer_types = mem::replace(&mut self.infer_types, Vec::new());
+        for (infer_type, identifier) in infer_types {
+            if let Type::Infer(infer_type) = self.resolve(&infer_type.into()) {
+                match infer_type.kind {
+                    InferKind::Any => {
+                        self.error(&identifier, "type annotations needed".to_string());
+                    }
+                    InferKind::Float => {
+                        self.substitution.insert(infer_type.identify(), PrimitiveType::F64.into());
+                    }
+                    InferKind::Int => {
+                        self.substitution.insert(infer_type.identify(), PrimitiveType::I64.into());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Substitute the solution of every `InferType` into a `Type`. Aliases
+    /// are substituted by the `Type` that they alias.
+    fn resolve(&self, ty: &Type) -> Type {
+        match *ty {
+            Type::Alias(ref ty) => self.resolve(&ty.inner),
+            Type::Channel(ref ty) => ChannelType::new(self.resolve(&ty.element)).into(),
+            Type::Infer(ref infer_type) => {
+                match self.substitution.get(&infer_type.identify()) {
+                    Some(ty) => self.resolve(ty),
+                    None => ty.clone(),
+                }
+            }
+            Type::Lambda(ref ty) => {
+                LambdaType::new(ty.formals.iter().map(|formal| self.resolve(formal)).collect(),
+                                self.resolve(&ty.ret))
+                    .into()
+            }
+            Type::List(ref ty) => ListType::new(self.resolve(&ty.element)).into(),
+            Type::Optional(ref ty) => OptionalType::new(self.resolve(&ty.inner)).into(),
+            Type::Ptr(ref ty) => Type::Ptr(Box::new(PtrType { inner: self.resolve(&ty.inner) })),
+            Type::Ref(ref ty) => Type::Ref(Box::new(RefType { inner: self.resolve(&ty.inner), is_mut: ty.is_mut })),
+            Type::Tuple(ref ty) => {
+                TupleType::new(ty.elements.iter().map(|element| self.resolve(element)).collect()).into()
+            }
+            _ => ty.clone(),
+        }
+    }
+
+    /// Unify two types, solving any `InferType` that they contain.
+    ///
+    /// # Return
+    /// True if the types can be made equal, otherwise false.
+    fn unify(&mut self, lhs: &Type, rhs: &Type) -> bool {
+        match (self.resolve(lhs), self.resolve(rhs)) {
+            (Type::Infer(lhs), Type::Infer(rhs)) => {
+                if lhs.identifier == rhs.identifier {
+                    return true;
+                }
+                if lhs.kind == InferKind::Any || lhs.kind == rhs.kind {
+                    self.substitution.insert(lhs.identify(), Type::Infer(rhs));
+                    true
+                } else if rhs.kind == InferKind::Any {
+                    self.substitution.insert(rhs.identify(), Type::Infer(lhs));
+                    true
+                } else {
+                    false
+                }
+            }
+            (Type::Infer(infer_type), ty) |
+            (ty, Type::Infer(infer_type)) => {
+                let is_solution = match infer_type.kind {
+                    InferKind::Any => !self.occurs(&infer_type.identify(), &ty),
+                    InferKind::Float => {
+                        if let Type::Primitive(ref ty) = ty { ty.is_float() } else { false }
+                    }
+                    InferKind::Int => {
+                        if let Type::Primitive(ref ty) = ty { ty.is_int() } else { false }
+                    }
+                };
+                if is_solution {
+                    self.substitution.insert(infer_type.identify(), ty);
+                }
+                is_solution
+            }
+            (Type::Channel(lhs), Type::Channel(rhs)) => self.unify(&lhs.element, &rhs.element),
+            (Type::Lambda(lhs), Type::Lambda(rhs)) => {
+                lhs.formals.len() == rhs.formals.len() &&
+                lhs.formals
+                    .iter()
+                    .zip(rhs.formals.iter())
+                    .all(|(lhs, rhs)| self.unify(lhs, rhs)) &&
+                self.unify(&lhs.ret, &rhs.ret)
+            }
+            (Type::List(lhs), Type::List(rhs)) => self.unify(&lhs.element, &rhs.element),
+            (Type::Optional(lhs), Type::Optional(rhs)) => self.unify(&lhs.inner, &rhs.inner),
+            (Type::Ptr(lhs), Type::Ptr(rhs)) => self.unify(&lhs.inner, &rhs.inner),
+            (Type::Ref(lhs), Type::Ref(rhs)) => lhs.is_mut == rhs.is_mut && self.unify(&lhs.inner, &rhs.inner),
+            (Type::Tuple(lhs), Type::Tuple(rhs)) => {
+                lhs.elements.len() == rhs.elements.len() &&
+                lhs.elements
+                    .iter()
+                    .zip(rhs.elements.iter())
+                    .all(|(lhs, rhs)| self.unify(lhs, rhs))
+            }
+            (lhs, rhs) => lhs == rhs,
+        }
+    }
+
+    /// Check whether an `InferType` occurs within a `Type`. An `InferType`
+    /// cannot be solved by a `Type` in which it occurs.
+    fn occurs(&self, identifier: &Identifier, ty: &Type) -> bool {
+        match self.resolve(ty) {
+            Type::Channel(ref ty) => self.occurs(identifier, &ty.element),
+            Type::Infer(ref infer_type) => infer_type.identify() == *identifier,
+            Type::Lambda(ref ty) => {
+                ty.formals.iter().any(|formal| self.occurs(identifier, formal)) ||
+                self.occurs(identifier, &ty.ret)
+            }
+            Type::List(ref ty) => self.occurs(identifier, &ty.element),
+            Type::Optional(ref ty) => self.occurs(identifier, &ty.inner),
+            Type::Ptr(ref ty) => self.occurs(identifier, &ty.inner),
+            Type::Ref(ref ty) => self.occurs(identifier, &ty.inner),
+            Type::Tuple(ref ty) => ty.elements.iter().any(|element| self.occurs(identifier, element)),
+            _ => false,
+        }
+    }
+
+    fn error(&mut self, identifier: &Identifier, message: String) {
+        let span = self.context.span_table.get(identifier).cloned();
+        self.errors.push(TypeErr::new(span, message));
+    }
+}
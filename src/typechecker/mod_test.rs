@@ -0,0 +1,858 @@
+use super::*;
+use super::super::air::*;
+use super::super::identifier::{Identifier, Symbol};
+use super::super::lexer::Span;
+
+#[test]
+fn check_function_ret() {
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_function(&Function::new(
+        Symbol::new("answer"),
+        Variables::new(),
+        PrimitiveType::I64.into(),
+        Some(LiteralExpr::new(Identifier::id(), Literal::I64(42)).into()),
+    ));
+    assert_eq!(type_checker.errors().len(), 0);
+
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_function(&Function::new(
+        Symbol::new("answer"),
+        Variables::new(),
+        PrimitiveType::I64.into(),
+        Some(LiteralExpr::new(Identifier::id(), Literal::Bool(true)).into()),
+    ));
+    assert_eq!(type_checker.errors().len(), 1);
+}
+
+#[test]
+fn check_call_expr() {
+    let add = Function::new(
+        Symbol::new("add"),
+        vec![
+            Variable::new(Symbol::new("x"), PrimitiveType::I64.into()),
+            Variable::new(Symbol::new("y"), PrimitiveType::I64.into()),
+        ],
+        PrimitiveType::I64.into(),
+        None,
+    );
+    let call_expr = |arguments: Exprs| -> Expr {
+        CallExpr::new(Identifier::id(), ItemExpr::new(Identifier::id(), add.clone().into()).into(), arguments).into()
+    };
+
+    let mut type_checker = TypeChecker::new(Context::new());
+    let expr = call_expr(vec![
+        LiteralExpr::new(Identifier::id(), Literal::I64(1)).into(),
+        LiteralExpr::new(Identifier::id(), Literal::I64(2)).into(),
+    ]);
+    assert_eq!(type_checker.check_expr(&expr), PrimitiveType::I64.into());
+    assert_eq!(type_checker.type_of(&expr), Some(PrimitiveType::I64.into()));
+    assert_eq!(type_checker.errors().len(), 0);
+
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&call_expr(vec![
+        LiteralExpr::new(Identifier::id(), Literal::I64(1)).into(),
+        LiteralExpr::new(Identifier::id(), Literal::F64(2.0)).into(),
+    ]));
+    assert_eq!(type_checker.errors().len(), 1);
+
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&call_expr(vec![
+        LiteralExpr::new(Identifier::id(), Literal::I64(1)).into(),
+    ]));
+    assert_eq!(type_checker.errors().len(), 1);
+}
+
+#[test]
+fn check_let_expr() {
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&LetExpr::new(
+        Identifier::id(),
+        Variable::new(Symbol::new("x"), PrimitiveType::Str.into()),
+        LiteralExpr::new(Identifier::id(), Literal::Str("hello".to_string())).into(),
+    ).into());
+    assert_eq!(type_checker.errors().len(), 0);
+
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&LetExpr::new(
+        Identifier::id(),
+        Variable::new(Symbol::new("x"), PrimitiveType::Str.into()),
+        LiteralExpr::new(Identifier::id(), Literal::Char('x')).into(),
+    ).into());
+    assert_eq!(type_checker.errors().len(), 1);
+}
+
+#[test]
+fn check_errors_are_located() {
+    let definition = Identifier::id();
+    let mut context = Context::new();
+    context.span_table.insert(definition.clone(), Span::new("main.arvo", 3, 16, 3, 19));
+
+    let mut type_checker = TypeChecker::new(context);
+    type_checker.check_expr(&LetExpr::new(
+        Identifier::id(),
+        Variable::new(Symbol::new("x"), PrimitiveType::I64.into()),
+        LiteralExpr::new(definition, Literal::Bool(true)).into(),
+    ).into());
+    assert_eq!(type_checker.errors().len(), 1);
+    assert_eq!(
+        format!("{}", type_checker.errors()[0]),
+        "main.arvo:3:16: mismatched types: expected `i64`, found `bool`"
+    );
+}
+
+#[test]
+fn infer_let_expr() {
+    let x = Variable::new(Symbol::new("x"), Type::infer());
+    let mut function = Function::new(
+        Symbol::new("byte"),
+        Variables::new(),
+        PrimitiveType::U8.into(),
+        Some(BlockExpr::new(
+            Identifier::id(),
+            vec![
+                LetExpr::new(Identifier::id(), x.clone(), LiteralExpr::new(Identifier::id(), Literal::Int(255)).into()).into(),
+            ],
+            VariableExpr::new(Identifier::id(), x.clone()).into(),
+            FunctionTable::new(),
+            ModuleTable::new(),
+            TypeTable::new(),
+        ).into()),
+    );
+
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_function(&function);
+    type_checker.apply_function(&mut function);
+    assert_eq!(type_checker.errors().len(), 0);
+
+    match function.body {
+        Some(Expr::Block(ref block_expr)) => match block_expr.body[0] {
+            Expr::Let(ref let_expr) => {
+                assert_eq!(let_expr.variable.ty, PrimitiveType::U8.into());
+                assert_eq!(let_expr.definition, LiteralExpr::new(let_expr.definition.identify(), Literal::U8(255)).into());
+            }
+            _ => panic!("expected a let expression"),
+        },
+        _ => panic!("expected a block expression"),
+    }
+}
+
+#[test]
+fn infer_literal_default() {
+    let x = Variable::new(Symbol::new("x"), Type::infer());
+    let mut function = Function::new(
+        Symbol::new("main"),
+        Variables::new(),
+        PrimitiveType::Void.into(),
+        Some(BlockExpr::new(
+            Identifier::id(),
+            vec![
+                LetExpr::new(Identifier::id(), x.clone(), LiteralExpr::new(Identifier::id(), Literal::Int(42)).into()).into(),
+            ],
+            VoidExpr::new(Identifier::id()).into(),
+            FunctionTable::new(),
+            ModuleTable::new(),
+            TypeTable::new(),
+        ).into()),
+    );
+
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_function(&function);
+    type_checker.apply_function(&mut function);
+    assert_eq!(type_checker.errors().len(), 0);
+
+    match function.body {
+        Some(Expr::Block(ref block_expr)) => match block_expr.body[0] {
+            Expr::Let(ref let_expr) => assert_eq!(let_expr.variable.ty, PrimitiveType::I64.into()),
+            _ => panic!("expected a let expression"),
+        },
+        _ => panic!("expected a block expression"),
+    }
+}
+
+#[test]
+fn infer_literal_out_of_range() {
+    let mut function = Function::new(
+        Symbol::new("byte"),
+        Variables::new(),
+        PrimitiveType::U8.into(),
+        Some(LiteralExpr::new(Identifier::id(), Literal::Int(256)).into()),
+    );
+
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_function(&function);
+    type_checker.apply_function(&mut function);
+    assert_eq!(type_checker.errors().len(), 1);
+}
+
+#[test]
+fn infer_u64_literal_range() {
+    let literal_fn = |value: i128| -> Function {
+        Function::new(
+            Symbol::new("word"),
+            Variables::new(),
+            PrimitiveType::U64.into(),
+            Some(LiteralExpr::new(Identifier::id(), Literal::Int(value)).into()),
+        )
+    };
+
+    // every u64 is in range, including those above the largest i64
+    let mut function = literal_fn(u64::MAX as i128);
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_function(&function);
+    type_checker.apply_function(&mut function);
+    assert_eq!(type_checker.errors().len(), 0);
+    let body = function.body.unwrap();
+    assert_eq!(body, LiteralExpr::new(body.identify(), Literal::U64(u64::MAX)).into());
+
+    let mut function = literal_fn(u64::MAX as i128 + 1);
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_function(&function);
+    type_checker.apply_function(&mut function);
+    assert_eq!(type_checker.errors().len(), 1);
+}
+
+#[test]
+fn check_generic_function() {
+    let a = GenericType::new(Identifier::name("a"));
+    let x = Variable::new(Symbol::new("x"), a.clone().into());
+    let id = Function::new_generic(
+        Symbol::new("id"),
+        vec![a.clone().into()],
+        vec![x.clone()],
+        a.clone().into(),
+        Some(VariableExpr::new(Identifier::id(), x.clone()).into()),
+    );
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_function(&id);
+    assert_eq!(type_checker.errors().len(), 0);
+
+    // type parameters are opaque within the body of a generic function
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_function(&Function::new_generic(
+        Symbol::new("id"),
+        vec![a.into()],
+        vec![x.clone()],
+        PrimitiveType::I64.into(),
+        Some(VariableExpr::new(Identifier::id(), x).into()),
+    ));
+    assert_eq!(type_checker.errors().len(), 1);
+
+    let mut function = Function::new(
+        Symbol::new("byte"),
+        Variables::new(),
+        PrimitiveType::U8.into(),
+        Some(CallExpr::new(
+            Identifier::id(),
+            ItemExpr::new(Identifier::id(), id.into()).into(),
+            vec![LiteralExpr::new(Identifier::id(), Literal::Int(42)).into()],
+        ).into()),
+    );
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_function(&function);
+    type_checker.apply_function(&mut function);
+    assert_eq!(type_checker.errors().len(), 0);
+
+    match function.body {
+        Some(Expr::Call(ref call_expr)) => match call_expr.target {
+            Expr::Item(ref item_expr) => assert_eq!(item_expr.type_args, vec![PrimitiveType::U8.into()]),
+            _ => panic!("expected an item expression"),
+        },
+        _ => panic!("expected a call expression"),
+    }
+}
+
+#[test]
+fn check_struct_elements() {
+    let x = Variable::new(Symbol::new("x"), PrimitiveType::F64.into());
+    let y = Variable::new(Symbol::new("y"), PrimitiveType::F64.into());
+    let point = StructType::new(Symbol::new("Point"), Types::new(), vec![x.clone(), y.clone()]);
+    let p = Variable::new(Symbol::new("p"), point.clone().into());
+    let p_ref = Variable::new(Symbol::new("p"), RefType::new(point.clone().into()).into());
+    let element = |parent: &Variable, element: &Variable| -> Expr {
+        VariableExpr::new_with_parent(Identifier::id(), VariableExpr::new(Identifier::id(), parent.clone()).into(), element.clone()).into()
+    };
+
+    // construction
+    let mut type_checker = TypeChecker::new(Context::new());
+    let expr = StructExpr::new(
+        Identifier::id(),
+        vec![
+            (x.clone(), LiteralExpr::new(Identifier::id(), Literal::Float(1.0)).into()),
+            (y.clone(), LiteralExpr::new(Identifier::id(), Literal::Float(2.0)).into()),
+        ],
+        point.clone().into(),
+    ).into();
+    assert_eq!(type_checker.check_expr(&expr), point.clone().into());
+    assert_eq!(type_checker.errors().len(), 0);
+
+    // reads, by value and through a reference
+    let mut type_checker = TypeChecker::new(Context::new());
+    assert_eq!(type_checker.check_expr(&element(&p, &x)), PrimitiveType::F64.into());
+    assert_eq!(type_checker.check_expr(&element(&p_ref, &y)), PrimitiveType::F64.into());
+    assert_eq!(type_checker.errors().len(), 0);
+
+    // writes
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&AssignExpr::new(Identifier::id(), element(&p_ref, &x), element(&p, &y)).into());
+    assert_eq!(type_checker.errors().len(), 0);
+
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&AssignExpr::new(Identifier::id(), element(&p, &x), LiteralExpr::new(Identifier::id(), Literal::Bool(true)).into()).into());
+    assert_eq!(type_checker.errors().len(), 1);
+
+    // unknown elements
+    let z = Variable::new(Symbol::new("z"), PrimitiveType::F64.into());
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&element(&p, &z));
+    assert_eq!(type_checker.errors().len(), 1);
+
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&element(&x, &y));
+    assert_eq!(type_checker.errors().len(), 1);
+}
+
+#[test]
+fn check_ref_exprs() {
+    let x = Variable::new_mut(Symbol::new("x"), PrimitiveType::I64.into());
+    let let_ref = |ty: RefType, ref_expr: RefExpr| -> Expr {
+        LetExpr::new(Identifier::id(), Variable::new(Symbol::new("r"), ty.into()), ref_expr.into()).into()
+    };
+    let x_expr = || -> Expr { VariableExpr::new(Identifier::id(), x.clone()).into() };
+
+    // a `mut ref` can be used where a `ref` is expected, but not the reverse
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&let_ref(RefType::new(PrimitiveType::I64.into()), RefExpr::new_mut(Identifier::id(), x_expr())));
+    type_checker.check_expr(&let_ref(RefType::new_mut(PrimitiveType::I64.into()), RefExpr::new_mut(Identifier::id(), x_expr())));
+    assert_eq!(type_checker.errors().len(), 0);
+
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&let_ref(RefType::new_mut(PrimitiveType::I64.into()), RefExpr::new(Identifier::id(), x_expr())));
+    assert_eq!(type_checker.errors().len(), 1);
+    assert_eq!(type_checker.errors()[0].message(),
+               "mismatched types: expected `mut ref i64`, found `ref i64`");
+}
+
+#[test]
+fn check_match_expr() {
+    let value = Variable::new(Symbol::new("value"), PrimitiveType::I64.into());
+    let some = Variable::new(Symbol::new("Some"), StructType::new(Symbol::new("Some"), Types::new(), vec![value.clone()]).into());
+    let nil = Variable::new(Symbol::new("Nil"), StructType::new(Symbol::new("Nil"), Types::new(), Variables::new()).into());
+    let option = EnumType::new(Symbol::new("Option"), Types::new(), vec![some.clone(), nil.clone()]);
+    let o = Variable::new(Symbol::new("o"), option.clone().into());
+    let a = Variable::new(Symbol::new("a"), Type::infer());
+    let block = |ret: Expr| -> BlockExpr {
+        BlockExpr::new(Identifier::id(), Exprs::new(), ret, FunctionTable::new(), ModuleTable::new(), TypeTable::new())
+    };
+    let arm = |variant: Option<Variable>, bindings: Variables, ret: Expr| -> MatchArm {
+        MatchArm::new(Identifier::id(), variant, bindings, block(ret))
+    };
+    let match_expr = |arms: MatchArms| -> Expr {
+        MatchExpr::new(Identifier::id(), VariableExpr::new(Identifier::id(), o.clone()).into(), arms).into()
+    };
+    let zero = || -> Expr { LiteralExpr::new(Identifier::id(), Literal::I64(0)).into() };
+
+    // construction
+    let mut type_checker = TypeChecker::new(Context::new());
+    let expr = EnumExpr::new(Identifier::id(), some.clone(), vec![(value.clone(), zero())], option.clone().into()).into();
+    assert_eq!(type_checker.check_expr(&expr), option.clone().into());
+    assert_eq!(type_checker.errors().len(), 0);
+
+    // exhaustive
+    let mut type_checker = TypeChecker::new(Context::new());
+    let expr = match_expr(vec![
+        arm(Some(some.clone()), vec![a.clone()], VariableExpr::new(Identifier::id(), a.clone()).into()),
+        arm(Some(nil.clone()), Variables::new(), zero()),
+    ]);
+    assert_eq!(type_checker.check_expr(&expr), PrimitiveType::I64.into());
+    assert_eq!(type_checker.errors().len(), 0);
+
+    // a wildcard covers the remaining variants
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&match_expr(vec![arm(Some(nil.clone()), Variables::new(), zero()), arm(None, Variables::new(), zero())]));
+    assert_eq!(type_checker.errors().len(), 0);
+
+    // non-exhaustive
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&match_expr(vec![arm(Some(nil.clone()), Variables::new(), zero())]));
+    assert_eq!(type_checker.errors().len(), 1);
+    assert_eq!(type_checker.errors()[0].message(), "non-exhaustive patterns: `Some` not covered");
+
+    // unreachable
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&match_expr(vec![
+        arm(None, Variables::new(), zero()),
+        arm(Some(nil.clone()), Variables::new(), zero()),
+    ]));
+    assert_eq!(type_checker.errors().len(), 1);
+
+    // an arm that is unreachable for two reasons is reported once
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&match_expr(vec![
+        arm(Some(nil.clone()), Variables::new(), zero()),
+        arm(None, Variables::new(), zero()),
+        arm(Some(nil.clone()), Variables::new(), zero()),
+    ]));
+    assert_eq!(type_checker.errors().len(), 1);
+    assert_eq!(type_checker.errors()[0].message(), "unreachable pattern");
+
+    // wrong number of bindings
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&match_expr(vec![
+        arm(Some(some.clone()), Variables::new(), zero()),
+        arm(Some(nil.clone()), Variables::new(), zero()),
+    ]));
+    assert_eq!(type_checker.errors().len(), 1);
+    assert_eq!(type_checker.errors()[0].message(), "variant `Some` has 1 fields, found 0");
+}
+
+#[test]
+fn check_optional_exprs() {
+    let x = Variable::new(Symbol::new("x"), PrimitiveType::I64.into());
+    let y = Variable::new(Symbol::new("y"), OptionalType::new(PrimitiveType::I64.into()).into());
+    let c = Variable::new(Symbol::new("c"), PrimitiveType::Bool.into());
+    let block = |ret: Expr| -> BlockExpr {
+        BlockExpr::new(Identifier::id(), Exprs::new(), ret, FunctionTable::new(), ModuleTable::new(), TypeTable::new())
+    };
+
+    // values are wrapped where an optional is expected
+    let mut function = Function::new(
+        Symbol::new("div"),
+        vec![c.clone(), x.clone()],
+        OptionalType::new(PrimitiveType::I64.into()).into(),
+        Some(IfExpr::new(
+            Identifier::id(),
+            VariableExpr::new(Identifier::id(), c.clone()).into(),
+            block(OptionalExpr::nil(Identifier::id()).into()),
+            block(VariableExpr::new(Identifier::id(), x.clone()).into()),
+        ).into()),
+    );
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_function(&function);
+    type_checker.apply_function(&mut function);
+    assert_eq!(type_checker.errors().len(), 0);
+    match function.body {
+        Some(Expr::If(ref if_expr)) => {
+            match if_expr.then_block.ret {
+                Expr::Optional(ref optional_expr) => {
+                    assert_eq!(optional_expr.value, None);
+                    assert_eq!(optional_expr.ty, PrimitiveType::I64.into());
+                }
+                _ => panic!("expected an optional expression"),
+            }
+            match if_expr.else_block.ret {
+                Expr::Optional(ref optional_expr) => {
+                    assert!(optional_expr.value.is_some());
+                    assert_eq!(optional_expr.ty, PrimitiveType::I64.into());
+                }
+                _ => panic!("expected an optional expression"),
+            }
+        }
+        _ => panic!("expected an if expression"),
+    }
+
+    // unwrapping and coalescing
+    let mut type_checker = TypeChecker::new(Context::new());
+    let expr = UnwrapExpr::new(Identifier::id(), VariableExpr::new(Identifier::id(), y.clone()).into()).into();
+    assert_eq!(type_checker.check_expr(&expr), PrimitiveType::I64.into());
+    let expr = CoalesceExpr::new(
+        Identifier::id(),
+        VariableExpr::new(Identifier::id(), y.clone()).into(),
+        LiteralExpr::new(Identifier::id(), Literal::Int(0)).into(),
+    ).into();
+    assert_eq!(type_checker.check_expr(&expr), PrimitiveType::I64.into());
+    assert_eq!(type_checker.errors().len(), 0);
+
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&UnwrapExpr::new(Identifier::id(), VariableExpr::new(Identifier::id(), x.clone()).into()).into());
+    assert_eq!(type_checker.errors().len(), 1);
+
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&CoalesceExpr::new(
+        Identifier::id(),
+        VariableExpr::new(Identifier::id(), y.clone()).into(),
+        LiteralExpr::new(Identifier::id(), Literal::Bool(true)).into(),
+    ).into());
+    assert_eq!(type_checker.errors().len(), 1);
+}
+
+#[test]
+fn check_let_tuple_expr() {
+    let a = Variable::new(Symbol::new("a"), Type::infer());
+    let b = Variable::new(Symbol::new("b"), PrimitiveType::Bool.into());
+    let pair = |lhs: Literal, rhs: Literal| -> Expr {
+        TupleExpr::new(
+            Identifier::id(),
+            vec![
+                LiteralExpr::new(Identifier::id(), lhs).into(),
+                LiteralExpr::new(Identifier::id(), rhs).into(),
+            ],
+        ).into()
+    };
+
+    let mut type_checker = TypeChecker::new(Context::new());
+    let expr = pair(Literal::I64(1), Literal::Bool(true));
+    assert_eq!(
+        type_checker.check_expr(&expr),
+        TupleType::new(vec![PrimitiveType::I64.into(), PrimitiveType::Bool.into()]).into()
+    );
+    let mut expr = LetTupleExpr::new(Identifier::id(), vec![a.clone(), b.clone()], expr).into();
+    type_checker.check_expr(&expr);
+    type_checker.apply_expr(&mut expr);
+    assert_eq!(type_checker.errors().len(), 0);
+    match expr {
+        Expr::LetTuple(ref let_tuple_expr) => assert_eq!(let_tuple_expr.variables[0].ty, PrimitiveType::I64.into()),
+        _ => panic!("expected a let tuple expression"),
+    }
+
+    // mismatched elements
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&LetTupleExpr::new(Identifier::id(), vec![a.clone(), b.clone()], pair(Literal::Bool(true), Literal::I64(1))).into());
+    assert_eq!(type_checker.errors().len(), 1);
+
+    // mismatched arity
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&LetTupleExpr::new(Identifier::id(), vec![b.clone()], pair(Literal::Bool(true), Literal::I64(1))).into());
+    assert_eq!(type_checker.errors().len(), 1);
+}
+
+#[test]
+fn check_list_exprs() {
+    let xs = Variable::new(Symbol::new("xs"), ListType::new(PrimitiveType::I32.into()).into());
+    let int = |value: i128| -> Expr { LiteralExpr::new(Identifier::id(), Literal::Int(value)).into() };
+    let list = || -> Expr { VariableExpr::new(Identifier::id(), xs.clone()).into() };
+
+    // the elements of a list literal share a type
+    let mut type_checker = TypeChecker::new(Context::new());
+    let expr = ListExpr::new(Identifier::id(), vec![int(1), int(2)]).into();
+    match type_checker.check_expr(&expr) {
+        Type::List(..) => (),
+        _ => panic!("expected a list type"),
+    }
+    let expr = ListExpr::new(
+        Identifier::id(),
+        vec![int(1), LiteralExpr::new(Identifier::id(), Literal::Bool(true)).into()],
+    ).into();
+    type_checker.check_expr(&expr);
+    assert_eq!(type_checker.errors().len(), 1);
+
+    // operations on a list
+    let mut type_checker = TypeChecker::new(Context::new());
+    let expr = ListOpExpr::new(Identifier::id(), ListOp::Index(list(), int(0))).into();
+    assert_eq!(type_checker.check_expr(&expr), PrimitiveType::I32.into());
+    let expr = ListOpExpr::new(Identifier::id(), ListOp::Len(list())).into();
+    assert_eq!(type_checker.check_expr(&expr), PrimitiveType::I64.into());
+    let expr = ListOpExpr::new(Identifier::id(), ListOp::Pop(list())).into();
+    assert_eq!(type_checker.check_expr(&expr), OptionalType::new(PrimitiveType::I32.into()).into());
+    let expr = ListOpExpr::new(Identifier::id(), ListOp::Slice(list(), int(0), int(1))).into();
+    assert_eq!(type_checker.check_expr(&expr), xs.ty.clone());
+    let expr = ListOpExpr::new(Identifier::id(), ListOp::Push(list(), int(3))).into();
+    assert_eq!(type_checker.check_expr(&expr), PrimitiveType::Void.into());
+    assert_eq!(type_checker.errors().len(), 0);
+
+    // iterating over a range gives its integers
+    let i = Variable::new(Symbol::new("i"), Type::infer());
+    let mut function = Function::new(
+        Symbol::new("count"),
+        Variables::new(),
+        PrimitiveType::Void.into(),
+        Some(ForExpr::new(
+            Identifier::id(),
+            false,
+            vec![i.clone()],
+            RangeExpr::new(Identifier::id(), int(1), LiteralExpr::new(Identifier::id(), Literal::U8(10)).into()).into(),
+            BlockExpr::new(Identifier::id(),
+                           Exprs::new(),
+                           VoidExpr::new(Identifier::id()).into(),
+                           FunctionTable::new(),
+                           ModuleTable::new(),
+                           TypeTable::new()),
+        ).into()),
+    );
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_function(&function);
+    type_checker.apply_function(&mut function);
+    assert_eq!(type_checker.errors().len(), 0);
+    match function.body {
+        Some(Expr::For(ref for_expr)) => assert_eq!(for_expr.formals[0].ty, PrimitiveType::U8.into()),
+        _ => panic!("expected a for expression"),
+    }
+
+    // only lists have a length
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&ListOpExpr::new(Identifier::id(), ListOp::Len(int(1))).into());
+    assert_eq!(type_checker.errors().len(), 1);
+
+    // only lists, ranges and channels can be iterated over
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&ForExpr::new(
+        Identifier::id(),
+        false,
+        vec![Variable::new(Symbol::new("i"), PrimitiveType::I64.into())],
+        LiteralExpr::new(Identifier::id(), Literal::I64(1)).into(),
+        BlockExpr::new(Identifier::id(),
+                       Exprs::new(),
+                       VoidExpr::new(Identifier::id()).into(),
+                       FunctionTable::new(),
+                       ModuleTable::new(),
+                       TypeTable::new()),
+    ).into());
+    assert_eq!(type_checker.errors().len(), 1);
+}
+
+#[test]
+fn check_channel_exprs() {
+    let ch = Variable::new(Symbol::new("ch"), ChannelType::new(PrimitiveType::I32.into()).into());
+    let int = |value: i128| -> Expr { LiteralExpr::new(Identifier::id(), Literal::Int(value)).into() };
+    let channel = || -> Expr { VariableExpr::new(Identifier::id(), ch.clone()).into() };
+
+    // a new channel infers the type of its values
+    let mut type_checker = TypeChecker::new(Context::new());
+    match type_checker.check_expr(&ChannelExpr::new(Identifier::id(), None).into()) {
+        Type::Channel(..) => (),
+        _ => panic!("expected a channel type"),
+    }
+
+    // the capacity of a bounded channel is an integer
+    type_checker.check_expr(&ChannelExpr::new(Identifier::id(), Some(int(0))).into());
+    assert_eq!(type_checker.errors().len(), 0);
+    let capacity = LiteralExpr::new(Identifier::id(), Literal::Bool(true)).into();
+    type_checker.check_expr(&ChannelExpr::new(Identifier::id(), Some(capacity)).into());
+    assert_eq!(type_checker.errors().len(), 1);
+
+    // operations on a channel
+    let mut type_checker = TypeChecker::new(Context::new());
+    let expr = ChannelOpExpr::new(Identifier::id(), ChannelOp::Recv(channel())).into();
+    assert_eq!(type_checker.check_expr(&expr), PrimitiveType::I32.into());
+    let expr = ChannelOpExpr::new(Identifier::id(), ChannelOp::Send(channel(), int(1))).into();
+    assert_eq!(type_checker.check_expr(&expr), PrimitiveType::Void.into());
+    let expr = ChannelOpExpr::new(Identifier::id(), ChannelOp::Close(channel())).into();
+    assert_eq!(type_checker.check_expr(&expr), PrimitiveType::Void.into());
+    assert_eq!(type_checker.errors().len(), 0);
+
+    // values sent must match the channel
+    let expr = ChannelOpExpr::new(
+        Identifier::id(),
+        ChannelOp::Send(channel(), LiteralExpr::new(Identifier::id(), Literal::Bool(true)).into()),
+    ).into();
+    type_checker.check_expr(&expr);
+    assert_eq!(type_checker.errors().len(), 1);
+
+    // iterating over a channel gives its values
+    let x = Variable::new(Symbol::new("x"), Type::infer());
+    let mut function = Function::new(
+        Symbol::new("drain"),
+        vec![ch.clone()],
+        PrimitiveType::Void.into(),
+        Some(ForExpr::new(
+            Identifier::id(),
+            false,
+            vec![x.clone()],
+            channel(),
+            BlockExpr::new(Identifier::id(),
+                           Exprs::new(),
+                           VoidExpr::new(Identifier::id()).into(),
+                           FunctionTable::new(),
+                           ModuleTable::new(),
+                           TypeTable::new()),
+        ).into()),
+    );
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_function(&function);
+    type_checker.apply_function(&mut function);
+    assert_eq!(type_checker.errors().len(), 0);
+    match function.body {
+        Some(Expr::For(ref for_expr)) => {
+            assert_eq!(for_expr.formals[0].ty, PrimitiveType::I32.into());
+            assert_eq!(for_expr.ty, ch.ty);
+        }
+        _ => panic!("expected a for expression"),
+    }
+
+    // only channels can be received from
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&ChannelOpExpr::new(Identifier::id(), ChannelOp::Recv(int(1))).into());
+    assert_eq!(type_checker.errors().len(), 1);
+}
+
+#[test]
+fn check_select_exprs() {
+    let xs = Variable::new(Symbol::new("xs"), ChannelType::new(PrimitiveType::I32.into()).into());
+    let ys = Variable::new(Symbol::new("ys"), ChannelType::new(PrimitiveType::Bool.into()).into());
+    let int = |value: i128| -> Expr { LiteralExpr::new(Identifier::id(), Literal::Int(value)).into() };
+    let channel = |variable: &Variable| -> Expr { VariableExpr::new(Identifier::id(), variable.clone()).into() };
+    let block = |ret: Expr| -> BlockExpr {
+        BlockExpr::new(Identifier::id(),
+                       Exprs::new(),
+                       ret,
+                       FunctionTable::new(),
+                       ModuleTable::new(),
+                       TypeTable::new())
+    };
+
+    // a received value is bound to the variable of its guard, and every
+    // body has the same type
+    let x = Variable::new(Symbol::new("x"), Type::infer());
+    let mut function = Function::new(
+        Symbol::new("poll"),
+        vec![xs.clone(), ys.clone()],
+        PrimitiveType::I32.into(),
+        Some(SelectExpr::new(
+            Identifier::id(),
+            vec![
+                SelectGuard::new(Identifier::id(),
+                                 SelectOp::Recv(channel(&xs), Some(x.clone())),
+                                 block(VariableExpr::new(Identifier::id(), x.clone()).into())),
+                SelectGuard::new(Identifier::id(),
+                                 SelectOp::Send(channel(&ys), LiteralExpr::new(Identifier::id(), Literal::Bool(true)).into()),
+                                 block(int(1))),
+            ],
+            Some(block(int(0))),
+        ).into()),
+    );
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_function(&function);
+    type_checker.apply_function(&mut function);
+    assert_eq!(type_checker.errors().len(), 0);
+    match function.body {
+        Some(Expr::Select(ref select_expr)) => match select_expr.guards[0].op {
+            SelectOp::Recv(_, Some(ref x)) => assert_eq!(x.ty, PrimitiveType::I32.into()),
+            _ => panic!("expected a receive guard"),
+        },
+        _ => panic!("expected a select expression"),
+    }
+
+    // values sent must match the channel, and bodies must agree
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&SelectExpr::new(
+        Identifier::id(),
+        vec![SelectGuard::new(Identifier::id(), SelectOp::Send(channel(&ys), int(1)), block(int(1)))],
+        Some(block(LiteralExpr::new(Identifier::id(), Literal::Bool(false)).into())),
+    ).into());
+    assert_eq!(type_checker.errors().len(), 2);
+}
+
+#[test]
+fn check_process_exprs() {
+    let block = |ret: Expr| -> BlockExpr {
+        BlockExpr::new(Identifier::id(),
+                       Exprs::new(),
+                       ret,
+                       FunctionTable::new(),
+                       ModuleTable::new(),
+                       TypeTable::new())
+    };
+
+    // a process is void, and so is its body
+    let mut type_checker = TypeChecker::new(Context::new());
+    let expr = ProcessExpr::new(Identifier::id(), block(VoidExpr::new(Identifier::id()).into())).into();
+    assert_eq!(type_checker.check_expr(&expr), PrimitiveType::Void.into());
+    assert_eq!(type_checker.errors().len(), 0);
+    let expr = ProcessExpr::new(Identifier::id(), block(LiteralExpr::new(Identifier::id(), Literal::Bool(true)).into())).into();
+    type_checker.check_expr(&expr);
+    assert_eq!(type_checker.errors().len(), 1);
+}
+
+#[test]
+fn check_lambda_exprs() {
+    let x = Variable::new(Symbol::new("x"), Type::infer());
+    let f = Variable::new(Symbol::new("f"), Type::infer());
+    let lambda_expr: Expr = LambdaExpr::new(
+        Identifier::id(),
+        vec![x.clone()],
+        VariableExpr::new(Identifier::id(), x.clone()).into(),
+    ).into();
+    let call_expr: Expr = CallExpr::new(
+        Identifier::id(),
+        VariableExpr::new(Identifier::id(), f.clone()).into(),
+        vec![LiteralExpr::new(Identifier::id(), Literal::I32(1)).into()],
+    ).into();
+    let mut function = Function::new(
+        Symbol::new("apply"),
+        Variables::new(),
+        PrimitiveType::I32.into(),
+        Some(BlockExpr::new(
+            Identifier::id(),
+            vec![LetExpr::new(Identifier::id(), f.clone(), lambda_expr).into()],
+            call_expr,
+            FunctionTable::new(),
+            ModuleTable::new(),
+            TypeTable::new(),
+        ).into()),
+    );
+
+    // the formals and return type of the lambda are inferred from its call
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_function(&function);
+    type_checker.apply_function(&mut function);
+    assert_eq!(type_checker.errors().len(), 0);
+    match function.body {
+        Some(Expr::Block(ref block_expr)) => match block_expr.body[0] {
+            Expr::Let(ref let_expr) => match let_expr.definition {
+                Expr::Lambda(ref lambda_expr) => {
+                    assert_eq!(lambda_expr.formals[0].ty, PrimitiveType::I32.into());
+                    assert_eq!(lambda_expr.ret, PrimitiveType::I32.into());
+                }
+                _ => panic!("expected a lambda expression"),
+            },
+            _ => panic!("expected a let expression"),
+        },
+        _ => panic!("expected a block expression"),
+    }
+
+    // a lambda can not be called with the wrong arguments
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&CallExpr::new(
+        Identifier::id(),
+        LambdaExpr::new(
+            Identifier::id(),
+            vec![Variable::new(Symbol::new("y"), PrimitiveType::Bool.into())],
+            LiteralExpr::new(Identifier::id(), Literal::I64(1)).into(),
+        ).into(),
+        vec![LiteralExpr::new(Identifier::id(), Literal::I64(1)).into()],
+    ).into());
+    assert_eq!(type_checker.errors().len(), 1);
+}
+
+#[test]
+fn check_loop_control_exprs() {
+    let block = |ret: Expr| -> BlockExpr {
+        BlockExpr::new(Identifier::id(),
+                       Exprs::new(),
+                       ret,
+                       FunctionTable::new(),
+                       ModuleTable::new(),
+                       TypeTable::new())
+    };
+    let for_expr = |is_concurrent: bool, ret: Expr| -> Expr {
+        ForExpr::new(
+            Identifier::id(),
+            is_concurrent,
+            vec![Variable::new(Symbol::new("i"), Type::infer())],
+            RangeExpr::new(Identifier::id(),
+                           LiteralExpr::new(Identifier::id(), Literal::Int(0)).into(),
+                           LiteralExpr::new(Identifier::id(), Literal::Int(10)).into()).into(),
+            block(ret),
+        ).into()
+    };
+
+    // `break` and `continue` are void within a loop
+    let mut type_checker = TypeChecker::new(Context::new());
+    assert_eq!(type_checker.check_expr(&for_expr(false, BreakExpr::new(Identifier::id()).into())),
+               PrimitiveType::Void.into());
+    type_checker.check_expr(&for_expr(false, ContinueExpr::new(Identifier::id()).into()));
+    assert_eq!(type_checker.errors().len(), 0);
+
+    // but are errors outside of a loop
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&BreakExpr::new(Identifier::id()).into());
+    type_checker.check_expr(&ContinueExpr::new(Identifier::id()).into());
+    assert_eq!(type_checker.errors().len(), 2);
+
+    // the iterations of a `forall`, and processes, are not part of a loop
+    let mut type_checker = TypeChecker::new(Context::new());
+    type_checker.check_expr(&for_expr(true, BreakExpr::new(Identifier::id()).into()));
+    assert_eq!(type_checker.errors().len(), 1);
+    let process_expr = ProcessExpr::new(Identifier::id(), block(ContinueExpr::new(Identifier::id()).into()));
+    type_checker.check_expr(&for_expr(false, process_expr.into()));
+    assert_eq!(type_checker.errors().len(), 2);
+}